@@ -0,0 +1,276 @@
+//! Ticker and company extraction
+//!
+//! Spots cashtags (`$NVDA`), known ticker symbols and company names in
+//! item titles and summaries, and records them as symbol tags.
+
+use crate::models::FeedItem;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+/// Symbol list shipped with finterm
+const BUNDLED_SYMBOLS: &str = include_str!("symbols.csv");
+
+/// Longest company name (in words) matched against the text
+const MAX_NAME_WORDS: usize = 4;
+
+/// Symbols that collide with everyday words or acronyms; these only match as cashtags
+const AMBIGUOUS_SYMBOLS: &[&str] = &[
+    "AI", "ALL", "ARE", "ARM", "CAT", "DE", "FOR", "HD", "IT", "MA", "MS", "NOW", "ON", "PG", "SO",
+    "SPY",
+];
+
+/// Extracts ticker symbols from free text using a symbol list
+#[derive(Debug, Clone, Default)]
+pub struct EntityExtractor {
+    /// Known symbols (uppercase)
+    symbols: HashSet<String>,
+    /// Lowercased company names and aliases mapped to their symbol
+    names: HashMap<String, String>,
+}
+
+impl EntityExtractor {
+    /// Create an extractor with no known symbols (cashtags still match)
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create an extractor using the bundled symbol list
+    pub fn bundled() -> Self {
+        let mut extractor = Self::new();
+        extractor.load_symbols(BUNDLED_SYMBOLS);
+        extractor
+    }
+
+    /// Load additional symbols from a `symbol,name[,alias...]` file
+    pub fn load_file(&mut self, path: &Path) -> std::io::Result<()> {
+        let contents = fs::read_to_string(path)?;
+        self.load_symbols(&contents);
+        Ok(())
+    }
+
+    /// Parse `symbol,name[,alias...]` lines; blank lines and `#` comments are skipped
+    pub fn load_symbols(&mut self, contents: &str) {
+        for line in contents.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let mut fields = line.split(',').map(|f| f.trim());
+            let symbol = match fields.next() {
+                Some(s) if !s.is_empty() => s.trim_start_matches('$').to_uppercase(),
+                _ => continue,
+            };
+
+            for name in fields.filter(|n| !n.is_empty()) {
+                self.names.insert(normalize_name(name), symbol.clone());
+            }
+            self.symbols.insert(symbol);
+        }
+    }
+
+    /// Number of known symbols
+    pub fn len(&self) -> usize {
+        self.symbols.len()
+    }
+
+    /// Check if no symbols are known
+    pub fn is_empty(&self) -> bool {
+        self.symbols.is_empty()
+    }
+
+    /// Check if `symbol` is in the symbol list
+    pub fn is_symbol(&self, symbol: &str) -> bool {
+        self.symbols.contains(symbol)
+    }
+
+    /// Find symbols mentioned in text, in order of first appearance
+    pub fn extract(&self, text: &str) -> Vec<String> {
+        let tokens = tokenize(text);
+        let mut found = Vec::new();
+        let mut push = |symbol: String| {
+            if !found.contains(&symbol) {
+                found.push(symbol);
+            }
+        };
+
+        let mut idx = 0;
+        while idx < tokens.len() {
+            let token = tokens[idx];
+
+            // $NVDA style cashtags are always accepted
+            if let Some(tag) = token.strip_prefix('$') {
+                if is_symbol_like(tag) {
+                    push(tag.to_uppercase());
+                }
+                idx += 1;
+                continue;
+            }
+
+            // Company names, longest match first ("Bank of America" before "Bank")
+            if token.starts_with(|c: char| c.is_uppercase()) {
+                let longest = (1..=MAX_NAME_WORDS.min(tokens.len() - idx))
+                    .rev()
+                    .find_map(|len| {
+                        let name = normalize_name(&tokens[idx..idx + len].join(" "));
+                        self.names.get(&name).map(|symbol| (symbol, len))
+                    });
+                if let Some((symbol, len)) = longest {
+                    push(symbol.clone());
+                    idx += len;
+                    continue;
+                }
+            }
+
+            // Bare uppercase symbols such as NVDA or BRK.B
+            if token.len() >= 2
+                && token
+                    .chars()
+                    .all(|c| c.is_ascii_uppercase() || c == '.' || c.is_ascii_digit())
+                && self.symbols.contains(token)
+                && !AMBIGUOUS_SYMBOLS.contains(&token)
+            {
+                push(token.to_string());
+            }
+
+            idx += 1;
+        }
+
+        found
+    }
+
+    /// Add extracted symbols to an item's tags
+    pub fn tag_item(&self, item: &mut FeedItem) {
+        let mut text = item.title.clone();
+        if let Some(summary) = &item.summary {
+            text.push('\n');
+            text.push_str(summary);
+        }
+
+        for symbol in self.extract(&text) {
+            if !item.metadata.tags.contains(&symbol) {
+                item.metadata.tags.push(symbol);
+            }
+        }
+    }
+
+    /// Tag every item in a batch
+    pub fn tag_items(&self, items: &mut [FeedItem]) {
+        for item in items.iter_mut() {
+            self.tag_item(item);
+        }
+    }
+}
+
+/// Whether a tag looks like a ticker symbol (e.g. "TSLA", "BRK.B")
+pub fn is_symbol_tag(tag: &str) -> bool {
+    is_symbol_like(tag) && tag.chars().all(|c| !c.is_ascii_lowercase())
+}
+
+fn is_symbol_like(s: &str) -> bool {
+    let base = s.split('.').next().unwrap_or_default();
+    (1..=5).contains(&base.len())
+        && base.chars().all(|c| c.is_ascii_alphabetic())
+        && s.len() <= 7
+        && s.chars().all(|c| c.is_ascii_alphanumeric() || c == '.')
+}
+
+/// Lowercase a name and normalize inner whitespace
+fn normalize_name(name: &str) -> String {
+    name.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+/// Split text into word tokens, keeping `$`, `&`, `.` and `-` inside words
+fn tokenize(text: &str) -> Vec<&str> {
+    text.split(|c: char| !(c.is_alphanumeric() || matches!(c, '$' | '&' | '.' | '-')))
+        .map(|t| t.trim_matches(|c: char| c == '.' || c == '-'))
+        .filter(|t| !t.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    #[test]
+    fn test_cashtags_and_symbols() {
+        let extractor = EntityExtractor::bundled();
+
+        assert_eq!(
+            extractor.extract("$nvda and $AMD rally while TSLA slips"),
+            vec!["NVDA", "AMD", "TSLA"]
+        );
+        assert_eq!(extractor.extract("BRK.B hits a record"), vec!["BRK.B"]);
+    }
+
+    #[test]
+    fn test_company_names() {
+        let extractor = EntityExtractor::bundled();
+
+        assert_eq!(
+            extractor.extract("Nvidia's new chip impresses Bank of America analysts"),
+            vec!["NVDA", "BAC"]
+        );
+        // Lowercase words are not proper nouns
+        assert!(extractor.extract("an apple a day").is_empty());
+    }
+
+    #[test]
+    fn test_ambiguous_symbols_need_cashtag() {
+        let extractor = EntityExtractor::bundled();
+
+        assert!(extractor.extract("IT spending and AI hype").is_empty());
+        assert!(extractor
+            .extract("Ask HN: What are you working ON?")
+            .is_empty());
+        assert_eq!(extractor.extract("$MS upgraded"), vec!["MS"]);
+    }
+
+    #[test]
+    fn test_user_symbols() {
+        let mut extractor = EntityExtractor::new();
+        extractor.load_symbols("# custom\nACME, Acme Corp, Acme\n\n");
+
+        assert_eq!(extractor.len(), 1);
+        assert_eq!(
+            extractor.extract("Acme Corp beats, ACME up 5%"),
+            vec!["ACME"]
+        );
+    }
+
+    #[test]
+    fn test_tag_item_dedupes() {
+        let extractor = EntityExtractor::bundled();
+        let mut item = FeedItem::new(
+            "1".to_string(),
+            "finnhub".to_string(),
+            "Tesla deliveries top forecasts".to_string(),
+            "Test".to_string(),
+            Utc::now(),
+        )
+        .with_summary("$TSLA shares rose after hours".to_string());
+        item.metadata.tags = vec!["TSLA".to_string()];
+
+        extractor.tag_item(&mut item);
+        assert_eq!(item.metadata.tags, vec!["TSLA"]);
+    }
+
+    #[test]
+    fn test_is_symbol_tag() {
+        assert!(is_symbol_tag("TSLA"));
+        assert!(is_symbol_tag("BRK.B"));
+        assert!(!is_symbol_tag("r/rust"));
+        assert!(!is_symbol_tag("paper"));
+
+        // Uppercase flair looks like a symbol but isn't in the list
+        let extractor = EntityExtractor::bundled();
+        assert!(is_symbol_tag("YOLO"));
+        assert!(extractor.is_symbol("TSLA"));
+        assert!(!extractor.is_symbol("YOLO"));
+    }
+}
@@ -0,0 +1,10 @@
+//! Offline analysis of feed items
+//!
+//! Enrichment passes that run over every fetched item, independent of
+//! which provider supplied it.
+
+pub mod entities;
+pub mod sentiment;
+
+pub use entities::EntityExtractor;
+pub use sentiment::SentimentAnalyzer;
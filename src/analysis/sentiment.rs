@@ -0,0 +1,311 @@
+//! Lexicon-based financial sentiment
+//!
+//! Scores headlines and summaries offline using a small finance word list
+//! with negation and intensifier handling.
+
+use crate::models::{FeedItem, Sentiment, SentimentLabel};
+
+/// Scores at or beyond this magnitude are labelled positive/negative
+const LABEL_THRESHOLD: f32 = 0.1;
+
+/// Normalization constant: higher values need more evidence to reach +/-1
+const NORMALIZATION_ALPHA: f32 = 15.0;
+
+/// How many tokens a negation word reaches forward
+const NEGATION_WINDOW: usize = 3;
+
+/// Headline words count more than summary words
+const TITLE_WEIGHT: f32 = 1.5;
+
+/// Finance-oriented word weights, roughly -3 (very negative) to +3. Sentiment
+/// runs on every provider, so words common in tech headlines ("enabled by
+/// default", "high-performance", "record type") are left out.
+const LEXICON: &[(&str, f32)] = &[
+    // Positive
+    ("beat", 2.0),
+    ("beats", 2.0),
+    ("bullish", 2.5),
+    ("boost", 1.5),
+    ("boosts", 1.5),
+    ("breakthrough", 2.0),
+    ("climb", 1.5),
+    ("climbs", 1.5),
+    ("exceed", 2.0),
+    ("exceeds", 2.0),
+    ("expand", 1.0),
+    ("expands", 1.0),
+    ("gain", 1.5),
+    ("gains", 1.5),
+    ("growth", 1.5),
+    ("improve", 1.5),
+    ("improves", 1.5),
+    ("jump", 1.5),
+    ("jumps", 1.5),
+    ("optimistic", 2.0),
+    ("outperform", 2.0),
+    ("outperforms", 2.0),
+    ("profit", 1.5),
+    ("profitable", 2.0),
+    ("rally", 2.0),
+    ("rallies", 2.0),
+    ("rebound", 1.5),
+    ("rebounds", 1.5),
+    ("recover", 1.5),
+    ("recovers", 1.5),
+    ("recovery", 1.5),
+    ("rise", 1.0),
+    ("rises", 1.0),
+    ("soar", 2.5),
+    ("soars", 2.5),
+    ("strong", 1.5),
+    ("stronger", 1.5),
+    ("success", 2.0),
+    ("surge", 2.5),
+    ("surges", 2.5),
+    ("upbeat", 2.0),
+    ("upgrade", 2.0),
+    ("upgraded", 2.0),
+    ("upgrades", 2.0),
+    ("win", 1.5),
+    ("wins", 1.5),
+    // Negative
+    ("bankrupt", -3.0),
+    ("bankruptcy", -3.0),
+    ("bearish", -2.5),
+    ("crash", -3.0),
+    ("crashes", -3.0),
+    ("crisis", -2.5),
+    ("decline", -1.5),
+    ("declines", -1.5),
+    ("deficit", -1.5),
+    ("downgrade", -2.0),
+    ("downgraded", -2.0),
+    ("downgrades", -2.0),
+    ("drop", -1.5),
+    ("drops", -1.5),
+    ("fall", -1.5),
+    ("falls", -1.5),
+    ("fear", -2.0),
+    ("fears", -2.0),
+    ("fraud", -3.0),
+    ("investigation", -2.0),
+    ("lawsuit", -2.0),
+    ("layoffs", -2.5),
+    ("loss", -2.0),
+    ("losses", -2.0),
+    ("miss", -2.0),
+    ("misses", -2.0),
+    ("plunge", -2.5),
+    ("plunges", -2.5),
+    ("probe", -1.5),
+    ("recession", -2.5),
+    ("risk", -1.0),
+    ("risks", -1.0),
+    ("selloff", -2.5),
+    ("sink", -2.0),
+    ("sinks", -2.0),
+    ("slump", -2.5),
+    ("slumps", -2.5),
+    ("slowdown", -1.5),
+    ("tumble", -2.5),
+    ("tumbles", -2.5),
+    ("underperform", -2.0),
+    ("volatile", -1.0),
+    ("warning", -1.5),
+    ("warns", -1.5),
+    ("weak", -1.5),
+    ("weaker", -1.5),
+    ("worst", -2.5),
+];
+
+/// Words that flip the polarity of the following few words
+const NEGATIONS: &[&str] = &[
+    "not", "no", "never", "neither", "nor", "without", "cannot", "isn't", "aren't", "wasn't",
+    "weren't", "don't", "doesn't", "didn't", "won't", "hardly", "fails",
+];
+
+/// Words that amplify the next sentiment word
+const INTENSIFIERS: &[(&str, f32)] = &[
+    ("very", 1.3),
+    ("sharply", 1.5),
+    ("significantly", 1.4),
+    ("strongly", 1.4),
+    ("massive", 1.5),
+    ("slightly", 0.6),
+    ("modestly", 0.7),
+];
+
+/// Offline sentiment analyzer
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SentimentAnalyzer;
+
+impl SentimentAnalyzer {
+    /// Create a new analyzer using the built-in finance lexicon
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Score an item's title and summary
+    pub fn analyze_item(&self, item: &FeedItem) -> Sentiment {
+        let (title_sum, title_hits) = self.raw_score(&item.title);
+        let (summary_sum, summary_hits) = item
+            .summary
+            .as_deref()
+            .map(|s| self.raw_score(s))
+            .unwrap_or((0.0, 0));
+
+        Self::to_sentiment(
+            title_sum * TITLE_WEIGHT + summary_sum,
+            title_hits + summary_hits,
+        )
+    }
+
+    /// Score arbitrary text
+    pub fn analyze(&self, text: &str) -> Sentiment {
+        let (sum, hits) = self.raw_score(text);
+        Self::to_sentiment(sum, hits)
+    }
+
+    /// Sum of lexicon weights and number of matched words
+    fn raw_score(&self, text: &str) -> (f32, usize) {
+        let tokens = tokenize(text);
+        let mut sum = 0.0;
+        let mut hits = 0;
+        let mut negate_until = 0;
+        let mut boost = 1.0;
+
+        for (idx, token) in tokens.iter().enumerate() {
+            if NEGATIONS.contains(&token.as_str()) || token.ends_with("n't") {
+                negate_until = idx + NEGATION_WINDOW + 1;
+                continue;
+            }
+
+            if let Some((_, factor)) = INTENSIFIERS.iter().find(|(w, _)| w == token) {
+                boost = *factor;
+                continue;
+            }
+
+            if let Some((_, weight)) = LEXICON.iter().find(|(w, _)| w == token) {
+                let mut value = weight * boost;
+                if idx < negate_until {
+                    // Negated sentiment is weaker than its opposite ("not bad" != "good")
+                    value = -value * 0.75;
+                }
+                sum += value;
+                hits += 1;
+            }
+
+            boost = 1.0;
+        }
+
+        (sum, hits)
+    }
+
+    fn to_sentiment(sum: f32, hits: usize) -> Sentiment {
+        let score = if hits == 0 {
+            0.0
+        } else {
+            sum / (sum * sum + NORMALIZATION_ALPHA).sqrt()
+        };
+
+        let label = if score >= LABEL_THRESHOLD {
+            SentimentLabel::Positive
+        } else if score <= -LABEL_THRESHOLD {
+            SentimentLabel::Negative
+        } else {
+            SentimentLabel::Neutral
+        };
+
+        // More matched words and a stronger score both raise confidence
+        let evidence = hits as f32 / (hits as f32 + 2.0);
+        let confidence = if hits == 0 {
+            0.0
+        } else {
+            (evidence * 0.6 + score.abs() * 0.4).min(1.0)
+        };
+
+        Sentiment {
+            score,
+            label,
+            confidence,
+        }
+    }
+}
+
+/// Fill in sentiment for items that don't already carry one
+pub fn annotate(items: &mut [FeedItem]) {
+    let analyzer = SentimentAnalyzer::new();
+    for item in items.iter_mut() {
+        if item.metadata.sentiment.is_none() {
+            item.metadata.sentiment = Some(analyzer.analyze_item(item));
+        }
+    }
+}
+
+/// Split text into lowercase word tokens, keeping apostrophes
+fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !(c.is_alphanumeric() || c == '\'' || c == '’'))
+        .filter(|t| !t.is_empty())
+        .map(|t| t.replace('’', "'").to_lowercase())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    #[test]
+    fn test_positive_and_negative_headlines() {
+        let analyzer = SentimentAnalyzer::new();
+
+        let up = analyzer.analyze("Nvidia shares surge after earnings beat estimates");
+        assert_eq!(up.label, SentimentLabel::Positive);
+        assert!(up.score > 0.3);
+
+        let down = analyzer.analyze("Regional bank stocks plunge on fraud probe");
+        assert_eq!(down.label, SentimentLabel::Negative);
+        assert!(down.score < -0.3);
+    }
+
+    #[test]
+    fn test_neutral_without_matches() {
+        let sentiment = SentimentAnalyzer::new().analyze("Fed chair to speak on Tuesday");
+        assert_eq!(sentiment.label, SentimentLabel::Neutral);
+        assert_eq!(sentiment.score, 0.0);
+        assert_eq!(sentiment.confidence, 0.0);
+
+        let tech = SentimentAnalyzer::new()
+            .analyze("High-performance record types, enabled by default in Rust 1.90");
+        assert_eq!(tech.label, SentimentLabel::Neutral);
+    }
+
+    #[test]
+    fn test_negation_flips_polarity() {
+        let analyzer = SentimentAnalyzer::new();
+
+        let negated = analyzer.analyze("Retailer did not beat expectations");
+        assert_eq!(negated.label, SentimentLabel::Negative);
+
+        let contraction = analyzer.analyze("Guidance isn't weak despite the quarter");
+        assert_eq!(contraction.label, SentimentLabel::Positive);
+    }
+
+    #[test]
+    fn test_annotate_fills_missing_sentiment() {
+        let mut items = vec![FeedItem::new(
+            "1".to_string(),
+            "test".to_string(),
+            "Markets rally to record high".to_string(),
+            "Test".to_string(),
+            Utc::now(),
+        )
+        .with_summary("Tech gains lead the way".to_string())];
+
+        annotate(&mut items);
+
+        let sentiment = items[0].metadata.sentiment.as_ref().unwrap();
+        assert_eq!(sentiment.label, SentimentLabel::Positive);
+        assert!(sentiment.confidence > 0.5);
+    }
+}
@@ -0,0 +1,238 @@
+//! Atom export
+//!
+//! Re-publishes items as an Atom 1.0 (RFC 4287) feed so other readers can
+//! follow the stream finterm curates. Stories linked from several sources
+//! (e.g. the same article on HN and Reddit) are merged into one entry.
+
+use crate::bookmarks::Bookmark;
+use crate::models::FeedItem;
+use crate::utils::normalize_url;
+use chrono::{DateTime, Utc};
+use quick_xml::escape::escape;
+use std::collections::HashMap;
+use std::fmt::Write;
+
+/// Feed ids readers track the merged stream and bookmarks by
+pub const MERGED_FEED_ID: &str = "urn:finterm:feed:merged";
+pub const BOOKMARKS_FEED_ID: &str = "urn:finterm:feed:bookmarks";
+
+/// An Atom feed under construction
+#[derive(Debug, Clone)]
+pub struct AtomFeed {
+    id: String,
+    title: String,
+    /// Items with the time each entry was last updated
+    entries: Vec<(FeedItem, DateTime<Utc>)>,
+}
+
+impl AtomFeed {
+    /// Empty feed; `id` must stay the same across renders for readers to track it
+    pub fn new(id: &str, title: &str) -> Self {
+        Self {
+            id: id.to_string(),
+            title: title.to_string(),
+            entries: Vec::new(),
+        }
+    }
+
+    /// Feed of items, each updated when it was published
+    pub fn from_items(id: &str, title: &str, items: Vec<FeedItem>) -> Self {
+        let mut feed = Self::new(id, title);
+        for item in items {
+            let updated = item.published_at;
+            feed.push(item, updated);
+        }
+        feed
+    }
+
+    pub fn push(&mut self, item: FeedItem, updated: DateTime<Utc>) {
+        self.entries.push((item, updated));
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Serialize as an Atom document
+    pub fn render(&self) -> String {
+        let updated = self
+            .entries
+            .iter()
+            .map(|(_, updated)| *updated)
+            .max()
+            .unwrap_or_else(Utc::now);
+
+        let mut out = String::new();
+        let _ = writeln!(out, r#"<?xml version="1.0" encoding="utf-8"?>"#);
+        let _ = writeln!(out, r#"<feed xmlns="http://www.w3.org/2005/Atom">"#);
+        let _ = writeln!(out, "  <id>{}</id>", escape(&self.id));
+        let _ = writeln!(out, "  <title>{}</title>", escape(&self.title));
+        let _ = writeln!(out, "  <updated>{}</updated>", updated.to_rfc3339());
+        let _ = writeln!(out, "  <author><name>finterm</name></author>");
+        let _ = writeln!(
+            out,
+            r#"  <generator version="{}">finterm</generator>"#,
+            env!("CARGO_PKG_VERSION")
+        );
+
+        for (item, updated) in &self.entries {
+            write_entry(&mut out, item, *updated);
+        }
+
+        out.push_str("</feed>\n");
+        out
+    }
+}
+
+/// Feed of bookmarks, each entry updated when it was saved
+pub fn bookmarks_feed(bookmarks: Vec<Bookmark>) -> AtomFeed {
+    let mut feed = AtomFeed::new(BOOKMARKS_FEED_ID, "finterm bookmarks");
+    for bookmark in bookmarks {
+        feed.push(bookmark.item, bookmark.saved_at);
+    }
+    feed
+}
+
+fn write_entry(out: &mut String, item: &FeedItem, updated: DateTime<Utc>) {
+    let _ = writeln!(out, "  <entry>");
+    let _ = writeln!(out, "    <id>{}</id>", escape(&entry_id(item)));
+    let _ = writeln!(out, "    <title>{}</title>", escape(&item.title));
+    let _ = writeln!(out, "    <updated>{}</updated>", updated.to_rfc3339());
+    let _ = writeln!(
+        out,
+        "    <published>{}</published>",
+        item.published_at.to_rfc3339()
+    );
+
+    let discussion = item.discussion_url();
+    if let Some(url) = item.url.as_ref().or(discussion.as_ref()) {
+        let _ = writeln!(out, r#"    <link rel="alternate" href="{}"/>"#, escape(url));
+    }
+    if let (Some(_), Some(thread)) = (&item.url, &discussion) {
+        let _ = writeln!(
+            out,
+            r#"    <link rel="replies" href="{}"/>"#,
+            escape(thread)
+        );
+    }
+
+    let author = item.author.as_deref().unwrap_or(&item.source);
+    let _ = writeln!(out, "    <author><name>{}</name></author>", escape(author));
+    for tag in &item.metadata.tags {
+        let _ = writeln!(out, r#"    <category term="{}"/>"#, escape(tag));
+    }
+
+    let mut summary = item.summary.clone().unwrap_or_default();
+    let counts: Vec<String> = [
+        item.metadata.score.map(|s| format!("{} points", s)),
+        item.metadata.comments.map(|c| format!("{} comments", c)),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+    if !counts.is_empty() {
+        if !summary.is_empty() {
+            summary.push_str("\n\n");
+        }
+        summary.push_str(&format!("{} · {}", item.source, counts.join(" · ")));
+    }
+    if !summary.is_empty() {
+        let _ = writeln!(
+            out,
+            r#"    <summary type="text">{}</summary>"#,
+            escape(&summary)
+        );
+    }
+
+    let _ = writeln!(out, "  </entry>");
+}
+
+/// Stable entry id: the same item keeps its id across renders
+fn entry_id(item: &FeedItem) -> String {
+    format!("urn:finterm:{}:{}", item.provider_id, item.id)
+}
+
+/// Drop repeats of the same story, keeping the first and merging tags
+///
+/// Items are the same story when they share provider and id, or link to the
+/// same page once tracking parameters and `www.` are ignored.
+pub fn dedupe(items: Vec<FeedItem>) -> Vec<FeedItem> {
+    let mut kept: Vec<FeedItem> = Vec::with_capacity(items.len());
+    let mut by_key: HashMap<String, usize> = HashMap::new();
+
+    for item in items {
+        let mut keys = vec![format!("{}\u{1f}{}", item.provider_id, item.id)];
+        keys.extend(item.url.as_deref().and_then(normalize_url));
+
+        match keys.iter().find_map(|k| by_key.get(k).copied()) {
+            Some(idx) => {
+                let first = &mut kept[idx];
+                for tag in item.metadata.tags {
+                    if !first.metadata.tags.contains(&tag) {
+                        first.metadata.tags.push(tag);
+                    }
+                }
+            }
+            None => {
+                for key in keys {
+                    by_key.insert(key, kept.len());
+                }
+                kept.push(item);
+            }
+        }
+    }
+
+    kept
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dedupe_by_normalized_url() {
+        let mut hn = FeedItem::test("1", "hackernews")
+            .with_url("https://www.example.com/post/?utm_source=hn".to_string());
+        hn.metadata.tags = vec!["tech".to_string()];
+        let mut reddit = FeedItem::test("abc", "reddit")
+            .with_url("http://example.com/post#comments".to_string());
+        reddit.metadata.tags = vec!["r/rust".to_string()];
+        let other = FeedItem::test("2", "hackernews")
+            .with_url("https://example.com/post?page=2".to_string());
+        let repeat =
+            FeedItem::test("1", "hackernews").with_url("https://example.com/moved".to_string());
+
+        let items = dedupe(vec![hn, reddit, other, repeat]);
+
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].id, "1");
+        assert_eq!(items[0].metadata.tags, vec!["tech", "r/rust"]);
+        assert_eq!(items[1].id, "2");
+    }
+
+    #[test]
+    fn test_render_escapes_and_links() {
+        let mut story =
+            FeedItem::test("42", "hackernews").with_url("https://example.com/?a=1&b=2".to_string());
+        story.title = "Q3 <earnings> & guidance".to_string();
+        story.metadata.hn_id = Some(42);
+        story.metadata.score = Some(100);
+        story.metadata.tags = vec!["AAPL".to_string()];
+
+        let xml = AtomFeed::from_items("urn:finterm:feed:test", "Test", vec![story]).render();
+
+        assert!(xml.contains("<id>urn:finterm:hackernews:42</id>"));
+        assert!(xml.contains("<title>Q3 &lt;earnings&gt; &amp; guidance</title>"));
+        assert!(xml.contains(r#"<link rel="alternate" href="https://example.com/?a=1&amp;b=2"/>"#));
+        assert!(
+            xml.contains(r#"<link rel="replies" href="https://news.ycombinator.com/item?id=42"/>"#)
+        );
+        assert!(xml.contains(r#"<category term="AAPL"/>"#));
+        assert!(xml.contains("100 points"));
+        assert_eq!(xml.matches("<entry>").count(), 1);
+    }
+}
@@ -0,0 +1,133 @@
+//! Bookmarks
+//!
+//! Items saved with `s`, kept in their own database under the data directory
+//! so clearing the cache does not lose them.
+
+use crate::models::FeedItem;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum BookmarkError {
+    #[error("Database error: {0}")]
+    Database(#[from] sled::Error),
+
+    #[error("Serialization error: {0}")]
+    Serialization(String),
+}
+
+pub type Result<T> = std::result::Result<T, BookmarkError>;
+
+/// A saved item
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Bookmark {
+    pub item: FeedItem,
+    pub saved_at: DateTime<Utc>,
+}
+
+/// Persistent set of bookmarked items, keyed by provider and id
+pub struct Bookmarks {
+    db: sled::Db,
+}
+
+impl Bookmarks {
+    /// Open (or create) the bookmark database in `dir`
+    pub fn open(dir: &Path) -> Result<Self> {
+        Ok(Self {
+            db: sled::open(dir.join("bookmarks"))?,
+        })
+    }
+
+    /// Bookmark an item, or remove it if already saved; returns whether it is now saved
+    pub fn toggle(&self, item: &FeedItem) -> Result<bool> {
+        let key = key(item);
+        if self.db.remove(&key)?.is_some() {
+            self.db.flush()?;
+            return Ok(false);
+        }
+
+        let mut item = item.clone();
+        // Threads go stale; they are fetched again when opened
+        item.metadata.comments_data = None;
+        let bookmark = Bookmark {
+            item,
+            saved_at: Utc::now(),
+        };
+        let bytes = serde_json::to_vec(&bookmark)
+            .map_err(|e| BookmarkError::Serialization(e.to_string()))?;
+        self.db.insert(key, bytes)?;
+        self.db.flush()?;
+        Ok(true)
+    }
+
+    /// Whether an item is bookmarked
+    pub fn contains(&self, item: &FeedItem) -> bool {
+        self.db.contains_key(key(item)).unwrap_or(false)
+    }
+
+    /// All bookmarks, most recently saved first
+    pub fn list(&self) -> Result<Vec<Bookmark>> {
+        let mut bookmarks = self
+            .db
+            .iter()
+            .values()
+            .map(|bytes| {
+                serde_json::from_slice::<Bookmark>(&bytes?)
+                    .map_err(|e| BookmarkError::Serialization(e.to_string()))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        bookmarks.sort_by_key(|b| std::cmp::Reverse(b.saved_at));
+        Ok(bookmarks)
+    }
+
+    pub fn len(&self) -> usize {
+        self.db.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.db.is_empty()
+    }
+}
+
+fn key(item: &FeedItem) -> Vec<u8> {
+    format!("{}\u{1f}{}", item.provider_id, item.id).into_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_toggle_and_list() {
+        let dir = tempdir().unwrap();
+        let bookmarks = Bookmarks::open(dir.path()).unwrap();
+        let item = |id: &str| {
+            FeedItem::new(
+                id.to_string(),
+                "hackernews".to_string(),
+                format!("Story {}", id),
+                "HN".to_string(),
+                Utc::now(),
+            )
+        };
+
+        assert!(bookmarks.toggle(&item("1")).unwrap());
+        assert!(bookmarks.toggle(&item("2")).unwrap());
+        assert!(bookmarks.contains(&item("1")));
+
+        let saved: Vec<_> = bookmarks
+            .list()
+            .unwrap()
+            .into_iter()
+            .map(|b| b.item.id)
+            .collect();
+        assert_eq!(saved, vec!["2", "1"]);
+
+        assert!(!bookmarks.toggle(&item("1")).unwrap());
+        assert!(!bookmarks.contains(&item("1")));
+        assert_eq!(bookmarks.len(), 1);
+    }
+}
@@ -0,0 +1,364 @@
+//! Fetch archive
+//!
+//! Snapshots every fetched list with a timestamp so past front pages can be
+//! browsed later, and records each item's rank and score per snapshot.
+//! Entries older than the retention window are pruned when the archive is
+//! opened and then at most once an hour, since pruning walks every key.
+
+use crate::cache::{CacheError, CacheManager, Result};
+use crate::models::FeedItem;
+use chrono::{DateTime, Duration, SubsecRound, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicI64, Ordering};
+
+const SNAPSHOTS_TREE: &str = "archive:snapshots";
+const HISTORY_TREE: &str = "archive:history";
+const SEPARATOR: u8 = 0;
+/// Minimum time between prunes while recording
+const PRUNE_INTERVAL: Duration = Duration::hours(1);
+
+/// A feed list as it was at one point in time
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub feed: String,
+    pub taken_at: DateTime<Utc>,
+    pub items: Vec<FeedItem>,
+}
+
+/// An item's position in one snapshot
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistoryPoint {
+    pub taken_at: DateTime<Utc>,
+    /// 1-based position in the list
+    pub rank: usize,
+    pub score: Option<i32>,
+    pub comments: Option<i32>,
+}
+
+/// Timestamped snapshots of fetched feeds
+pub struct Archive {
+    snapshots: sled::Tree,
+    history: sled::Tree,
+    retention: Duration,
+    /// When the last prune ran, in milliseconds
+    last_prune: AtomicI64,
+}
+
+impl Archive {
+    /// Open the archive stored alongside the cache
+    pub fn open(cache: &CacheManager, retention_days: u64) -> Result<Self> {
+        let archive = Self {
+            snapshots: cache.open_tree(SNAPSHOTS_TREE)?,
+            history: cache.open_tree(HISTORY_TREE)?,
+            retention: Duration::days(retention_days as i64),
+            last_prune: AtomicI64::new(0),
+        };
+        archive.prune(Utc::now())?;
+        Ok(archive)
+    }
+
+    /// Snapshot a feed as fetched now
+    pub fn record(&self, feed: &str, items: &[FeedItem]) -> Result<()> {
+        self.record_at(feed, items, Utc::now())
+    }
+
+    /// Snapshot a feed at a given time; prunes if the last prune was over an hour ago
+    pub fn record_at(&self, feed: &str, items: &[FeedItem], taken_at: DateTime<Utc>) -> Result<()> {
+        if items.is_empty() {
+            return Ok(());
+        }
+        // Keys hold milliseconds; keep the stored time equal to what they decode to
+        let taken_at = taken_at.trunc_subsecs(3);
+
+        let items: Vec<FeedItem> = items
+            .iter()
+            .cloned()
+            .map(|mut item| {
+                // Bodies and threads are large; the list is what we want to keep
+                item.content = None;
+                item.metadata.comments_data = None;
+                item.metadata.link_preview = None;
+                item
+            })
+            .collect();
+
+        for (idx, item) in items.iter().enumerate() {
+            let point = HistoryPoint {
+                taken_at,
+                rank: idx + 1,
+                score: item.metadata.score,
+                comments: item.metadata.comments,
+            };
+            self.history
+                .insert(history_key(feed, item, taken_at), to_json(&point)?)?;
+        }
+
+        let snapshot = Snapshot {
+            feed: feed.to_string(),
+            taken_at,
+            items,
+        };
+        self.snapshots
+            .insert(time_key(feed.as_bytes(), taken_at), to_json(&snapshot)?)?;
+
+        let since_prune = taken_at.timestamp_millis() - self.last_prune.load(Ordering::Relaxed);
+        if since_prune >= PRUNE_INTERVAL.num_milliseconds() {
+            self.prune(taken_at)?;
+        }
+        Ok(())
+    }
+
+    /// When each snapshot of a feed was taken, oldest first
+    pub fn snapshot_times(&self, feed: &str) -> Vec<DateTime<Utc>> {
+        self.snapshots
+            .scan_prefix(feed_prefix(feed.as_bytes()))
+            .keys()
+            .filter_map(|k| k.ok())
+            .filter_map(|k| decode_time(&k))
+            .collect()
+    }
+
+    /// The latest snapshot of a feed taken at or before `at`
+    pub fn snapshot_at(&self, feed: &str, at: DateTime<Utc>) -> Result<Option<Snapshot>> {
+        let start = feed_prefix(feed.as_bytes());
+        let end = time_key(feed.as_bytes(), at);
+
+        match self.snapshots.range(start..=end).next_back() {
+            Some(entry) => {
+                let (_, bytes) = entry?;
+                from_json(&bytes).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// The snapshot `step` places after (or, when negative, before) the one
+    /// taken at `from`; `None` past either end
+    pub fn step_from(
+        &self,
+        feed: &str,
+        from: DateTime<Utc>,
+        step: isize,
+    ) -> Result<Option<Snapshot>> {
+        let times = self.snapshot_times(feed);
+        let target = times
+            .iter()
+            .position(|t| *t == from)
+            .and_then(|idx| idx.checked_add_signed(step))
+            .and_then(|idx| times.get(idx));
+        match target {
+            Some(at) => self.snapshot_at(feed, *at),
+            None => Ok(None),
+        }
+    }
+
+    /// Rank and score of an item across a feed's snapshots, oldest first
+    pub fn history(&self, feed: &str, item: &FeedItem) -> Result<Vec<HistoryPoint>> {
+        self.history
+            .scan_prefix(feed_prefix(&item_prefix(feed, item)))
+            .values()
+            .map(|bytes| from_json(&bytes?))
+            .collect()
+    }
+
+    /// Remove snapshots and history older than the retention window
+    pub fn prune(&self, now: DateTime<Utc>) -> Result<usize> {
+        self.last_prune
+            .store(now.timestamp_millis(), Ordering::Relaxed);
+        let cutoff = now - self.retention;
+        let mut removed = 0;
+
+        for tree in [&self.snapshots, &self.history] {
+            for key in tree.iter().keys() {
+                let key = key?;
+                if decode_time(&key).is_some_and(|t| t < cutoff) {
+                    tree.remove(&key)?;
+                    removed += 1;
+                }
+            }
+        }
+
+        Ok(removed)
+    }
+}
+
+fn feed_prefix(feed: &[u8]) -> Vec<u8> {
+    let mut key = feed.to_vec();
+    key.push(SEPARATOR);
+    key
+}
+
+fn item_prefix(feed: &str, item: &FeedItem) -> Vec<u8> {
+    let mut key = feed_prefix(feed.as_bytes());
+    key.extend_from_slice(format!("{}\u{1f}{}", item.provider_id, item.id).as_bytes());
+    key
+}
+
+/// `prefix \0 big-endian millis`, so keys sort by time within a prefix
+fn time_key(prefix: &[u8], at: DateTime<Utc>) -> Vec<u8> {
+    let mut key = feed_prefix(prefix);
+    key.extend_from_slice(&(at.timestamp_millis() as u64).to_be_bytes());
+    key
+}
+
+fn history_key(feed: &str, item: &FeedItem, at: DateTime<Utc>) -> Vec<u8> {
+    time_key(&item_prefix(feed, item), at)
+}
+
+fn decode_time(key: &[u8]) -> Option<DateTime<Utc>> {
+    let millis = key.len().checked_sub(8).map(|start| &key[start..])?;
+    let millis = u64::from_be_bytes(millis.try_into().ok()?) as i64;
+    Utc.timestamp_millis_opt(millis).single()
+}
+
+fn to_json<T: Serialize>(value: &T) -> Result<Vec<u8>> {
+    serde_json::to_vec(value).map_err(|e| CacheError::Serialization(e.to_string()))
+}
+
+fn from_json<T: serde::de::DeserializeOwned>(bytes: &[u8]) -> Result<T> {
+    serde_json::from_slice(bytes).map_err(|e| CacheError::Serialization(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_snapshot_at_and_history() {
+        let dir = tempdir().unwrap();
+        let cache = CacheManager::new(dir.path().to_path_buf(), 10).unwrap();
+        let archive = Archive::open(&cache, 30).unwrap();
+
+        let nine = Utc::now() - Duration::hours(3);
+        let ten = nine + Duration::hours(1);
+        archive
+            .record_at(
+                "hackernews",
+                &[
+                    FeedItem::test("a", "hackernews").with_score(10),
+                    FeedItem::test("b", "hackernews").with_score(5),
+                ],
+                nine,
+            )
+            .unwrap();
+        archive
+            .record_at(
+                "hackernews",
+                &[
+                    FeedItem::test("b", "hackernews").with_score(80),
+                    FeedItem::test("a", "hackernews").with_score(12),
+                ],
+                ten,
+            )
+            .unwrap();
+
+        assert_eq!(archive.snapshot_times("hackernews").len(), 2);
+        assert!(archive.snapshot_times("reddit").is_empty());
+
+        let at_930 = archive
+            .snapshot_at("hackernews", nine + Duration::minutes(30))
+            .unwrap()
+            .unwrap();
+        assert_eq!(at_930.taken_at, nine.trunc_subsecs(3));
+        assert!(archive
+            .snapshot_times("hackernews")
+            .contains(&at_930.taken_at));
+        assert_eq!(at_930.items[0].id, "a");
+        assert!(archive
+            .snapshot_at("hackernews", nine - Duration::minutes(1))
+            .unwrap()
+            .is_none());
+
+        let history = archive
+            .history(
+                "hackernews",
+                &FeedItem::test("b", "hackernews").with_score(0),
+            )
+            .unwrap();
+        let ranks: Vec<_> = history.iter().map(|p| (p.rank, p.score)).collect();
+        assert_eq!(ranks, vec![(2, Some(5)), (1, Some(80))]);
+    }
+
+    #[test]
+    fn test_step_between_snapshots() {
+        let dir = tempdir().unwrap();
+        let cache = CacheManager::new(dir.path().to_path_buf(), 10).unwrap();
+        let archive = Archive::open(&cache, 30).unwrap();
+
+        // Sub-millisecond precision, as from Utc::now()
+        let first = Utc::now() - Duration::hours(2) + Duration::nanoseconds(123_456);
+        let second = first + Duration::hours(1);
+        archive
+            .record_at(
+                "hackernews",
+                &[FeedItem::test("a", "hackernews").with_score(1)],
+                first,
+            )
+            .unwrap();
+        archive
+            .record_at(
+                "hackernews",
+                &[FeedItem::test("b", "hackernews").with_score(1)],
+                second,
+            )
+            .unwrap();
+
+        let oldest = archive.snapshot_at("hackernews", first).unwrap().unwrap();
+        let next = archive
+            .step_from("hackernews", oldest.taken_at, 1)
+            .unwrap()
+            .unwrap();
+        assert_eq!(next.items[0].id, "b");
+        let back = archive
+            .step_from("hackernews", next.taken_at, -1)
+            .unwrap()
+            .unwrap();
+        assert_eq!(back.items[0].id, "a");
+        assert!(archive
+            .step_from("hackernews", next.taken_at, 1)
+            .unwrap()
+            .is_none());
+        assert!(archive
+            .step_from("hackernews", oldest.taken_at, -1)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_retention_prunes_old_entries() {
+        let dir = tempdir().unwrap();
+        let cache = CacheManager::new(dir.path().to_path_buf(), 10).unwrap();
+        let archive = Archive::open(&cache, 7).unwrap();
+
+        let now = Utc::now();
+        archive
+            .record_at(
+                "hackernews",
+                &[FeedItem::test("old", "hackernews").with_score(1)],
+                now - Duration::days(10),
+            )
+            .unwrap();
+        archive
+            .record_at(
+                "hackernews",
+                &[FeedItem::test("new", "hackernews").with_score(1)],
+                now,
+            )
+            .unwrap();
+        // Just pruned on open, so recording alone leaves the old entry
+        assert_eq!(archive.snapshot_times("hackernews").len(), 2);
+
+        // Reopening prunes it, snapshot and history both
+        drop(archive);
+        let archive = Archive::open(&cache, 7).unwrap();
+        assert_eq!(archive.snapshot_times("hackernews").len(), 1);
+        assert!(archive
+            .history(
+                "hackernews",
+                &FeedItem::test("old", "hackernews").with_score(0)
+            )
+            .unwrap()
+            .is_empty());
+    }
+}
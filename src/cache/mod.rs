@@ -1,3 +1,6 @@
+pub mod archive;
+pub mod threads;
+
 use crate::models::{CacheEntry, CacheKey, CacheStats};
 use serde::{de::DeserializeOwned, Serialize};
 use std::path::PathBuf;
@@ -16,6 +19,9 @@ pub enum CacheError {
 
     #[error("Cache entry not found")]
     NotFound,
+
+    #[error("Cache at {0} is in use by another finterm process")]
+    Locked(String),
 }
 
 pub type Result<T> = std::result::Result<T, CacheError>;
@@ -30,9 +36,14 @@ pub struct CacheManager {
 impl CacheManager {
     /// Create a new cache manager
     pub fn new(cache_path: PathBuf, max_size_mb: u64) -> Result<Self> {
-        // Try to open database, if it fails due to lock or corruption, clear and retry
+        // Try to open database, if it fails due to corruption, clear and retry.
+        // A lock held by another process (the TUI, `serve`, `watch`) is not
+        // corruption: clearing would pull the cache out from under it.
         let db = match sled::open(&cache_path) {
             Ok(db) => db,
+            Err(e) if is_lock_error(&e) => {
+                return Err(CacheError::Locked(cache_path.display().to_string()));
+            }
             Err(e) => {
                 tracing::warn!("Cache database error, clearing and retrying: {}", e);
                 // Remove corrupted/locked database
@@ -151,6 +162,11 @@ impl CacheManager {
         Ok(())
     }
 
+    /// Open a named tree in the cache database that is not subject to TTL or eviction
+    pub fn open_tree(&self, name: &str) -> Result<sled::Tree> {
+        Ok(self.db.open_tree(name)?)
+    }
+
     /// Flush to disk
     pub fn flush(&self) -> Result<()> {
         self.db.flush()?;
@@ -164,6 +180,11 @@ impl Drop for CacheManager {
     }
 }
 
+/// Whether sled failed to open because another process holds the database lock
+fn is_lock_error(error: &sled::Error) -> bool {
+    matches!(error, sled::Error::Io(e) if e.to_string().contains("could not acquire lock"))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -182,4 +203,20 @@ mod tests {
         let result: String = cache.get(key).unwrap();
         assert_eq!(result, "test data");
     }
+
+    #[test]
+    fn test_locked_cache_is_left_alone() {
+        let dir = tempdir().unwrap();
+        let mut cache = CacheManager::new(dir.path().to_path_buf(), 10).unwrap();
+        cache
+            .set(CacheKey::HnStory(1), "kept".to_string(), 3600)
+            .unwrap();
+
+        // A second opener (e.g. `finterm serve` next to the TUI) is refused
+        let second = CacheManager::new(dir.path().to_path_buf(), 10);
+        assert!(matches!(second, Err(CacheError::Locked(_))));
+
+        let kept: String = cache.get(CacheKey::HnStory(1)).unwrap();
+        assert_eq!(kept, "kept");
+    }
 }
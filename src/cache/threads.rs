@@ -0,0 +1,181 @@
+//! Thread visits
+//!
+//! Remembers which comments of a Hacker News or Reddit thread have been seen,
+//! so new ones can be highlighted when the thread is opened again and story
+//! lists can show how many comments arrived since the last visit.
+
+use crate::cache::{CacheError, CacheManager, Result};
+use crate::models::{Comment, FeedItem};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+const VISITS_TREE: &str = "threads:visits";
+
+/// Threads not opened for this long are forgotten
+const RETENTION_DAYS: i64 = 90;
+
+/// What was seen of a thread the last time it was opened
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Visit {
+    pub visited_at: DateTime<Utc>,
+    /// Comment count reported by the story at the time
+    pub comment_count: i32,
+    pub seen: HashSet<String>,
+}
+
+/// Seen comments per thread, keyed by story
+pub struct ThreadVisits {
+    visits: sled::Tree,
+}
+
+impl ThreadVisits {
+    /// Open the visit store alongside the cache, dropping stale threads
+    pub fn open(cache: &CacheManager) -> Result<Self> {
+        let visits = Self {
+            visits: cache.open_tree(VISITS_TREE)?,
+        };
+        visits.prune(Utc::now() - Duration::days(RETENTION_DAYS))?;
+        Ok(visits)
+    }
+
+    /// The last visit to an item's thread, if any
+    pub fn get(&self, item: &FeedItem) -> Result<Option<Visit>> {
+        let Some(key) = thread_key(item) else {
+            return Ok(None);
+        };
+        match self.visits.get(key)? {
+            Some(bytes) => serde_json::from_slice(&bytes)
+                .map(Some)
+                .map_err(|e| CacheError::Serialization(e.to_string())),
+            None => Ok(None),
+        }
+    }
+
+    /// Record a visit and return the comments that were not there last time.
+    /// The first visit to a thread reports nothing as new.
+    pub fn record(&self, item: &FeedItem, comments: &[Comment]) -> Result<HashSet<String>> {
+        let Some(key) = thread_key(item) else {
+            return Ok(HashSet::new());
+        };
+
+        let mut ids = HashSet::new();
+        collect_ids(comments, &mut ids);
+
+        let previous = self.get(item)?;
+        let new_ids = match &previous {
+            Some(visit) => ids.difference(&visit.seen).cloned().collect(),
+            None => HashSet::new(),
+        };
+
+        // Keep earlier ids too; a collapsed or paged-out reply is still seen
+        let mut seen = previous.map(|v| v.seen).unwrap_or_default();
+        seen.extend(ids);
+
+        let visit = Visit {
+            visited_at: Utc::now(),
+            comment_count: item.metadata.comments.unwrap_or(seen.len() as i32),
+            seen,
+        };
+        let bytes =
+            serde_json::to_vec(&visit).map_err(|e| CacheError::Serialization(e.to_string()))?;
+        self.visits.insert(key, bytes)?;
+
+        Ok(new_ids)
+    }
+
+    /// Comments added to a visited thread since the last visit
+    pub fn new_comments(&self, item: &FeedItem) -> Option<i32> {
+        let visit = self.get(item).ok()??;
+        let count = item.metadata.comments? - visit.comment_count;
+        (count > 0).then_some(count)
+    }
+
+    /// Remove threads last visited before `cutoff`
+    pub fn prune(&self, cutoff: DateTime<Utc>) -> Result<usize> {
+        let mut removed = 0;
+        for entry in self.visits.iter() {
+            let (key, bytes) = entry?;
+            let stale = serde_json::from_slice::<Visit>(&bytes)
+                .map(|v| v.visited_at < cutoff)
+                .unwrap_or(true);
+            if stale {
+                self.visits.remove(key)?;
+                removed += 1;
+            }
+        }
+        Ok(removed)
+    }
+}
+
+/// Store key for an item's comment thread; only HN and Reddit have threads
+pub fn thread_key(item: &FeedItem) -> Option<String> {
+    if let Some(id) = item.metadata.hn_id {
+        return Some(format!("hn:{}", id));
+    }
+    item.metadata
+        .reddit_id
+        .as_ref()
+        .map(|id| format!("reddit:{}", id))
+}
+
+fn collect_ids(comments: &[Comment], ids: &mut HashSet<String>) {
+    for comment in comments {
+        ids.insert(comment.id.clone());
+        collect_ids(&comment.replies, ids);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn comment(id: &str, replies: Vec<Comment>) -> Comment {
+        let mut comment = Comment::new(
+            id.to_string(),
+            "pg".to_string(),
+            "text".to_string(),
+            Utc::now(),
+        );
+        comment.replies = replies;
+        comment
+    }
+
+    #[test]
+    fn test_record_reports_new_comments() {
+        let dir = tempdir().unwrap();
+        let cache = CacheManager::new(dir.path().to_path_buf(), 10).unwrap();
+        let visits = ThreadVisits::open(&cache).unwrap();
+
+        let mut story = FeedItem::new(
+            "1".to_string(),
+            "hackernews".to_string(),
+            "Story".to_string(),
+            "HN".to_string(),
+            Utc::now(),
+        );
+        story.metadata.hn_id = Some(1);
+        story.metadata.comments = Some(2);
+
+        let first = vec![comment("a", vec![comment("b", vec![])])];
+        assert!(visits.record(&story, &first).unwrap().is_empty());
+        assert_eq!(visits.new_comments(&story), None);
+
+        story.metadata.comments = Some(4);
+        assert_eq!(visits.new_comments(&story), Some(2));
+
+        let second = vec![
+            comment("a", vec![comment("b", vec![]), comment("c", vec![])]),
+            comment("d", vec![]),
+        ];
+        let new_ids = visits.record(&story, &second).unwrap();
+        assert_eq!(new_ids, HashSet::from(["c".to_string(), "d".to_string()]));
+        assert_eq!(visits.new_comments(&story), None);
+
+        // Items without a thread are ignored
+        story.metadata.hn_id = None;
+        assert!(visits.record(&story, &second).unwrap().is_empty());
+        assert!(visits.get(&story).unwrap().is_none());
+    }
+}
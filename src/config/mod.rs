@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::PathBuf;
 use thiserror::Error;
@@ -29,6 +30,9 @@ pub struct Config {
     #[serde(default)]
     pub reddit: RedditConfig,
 
+    #[serde(default)]
+    pub arxiv: ArxivConfig,
+
     #[serde(default)]
     pub ui: UiConfig,
 
@@ -37,6 +41,21 @@ pub struct Config {
 
     #[serde(default)]
     pub keybindings: KeybindingsConfig,
+
+    #[serde(default)]
+    pub entities: EntitiesConfig,
+
+    #[serde(default)]
+    pub rules: Vec<RuleConfig>,
+
+    #[serde(default)]
+    pub webhooks: Vec<WebhookConfig>,
+
+    #[serde(default)]
+    pub server: ServerConfig,
+
+    #[serde(default)]
+    pub notifications: NotificationsConfig,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -63,6 +82,9 @@ pub struct HackerNewsConfig {
     pub include_dead: bool,
     #[serde(default = "default_true")]
     pub fetch_full_content: bool,
+    /// Stream rank, score and comment changes into the open feed
+    #[serde(default)]
+    pub live: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -75,6 +97,46 @@ pub struct RedditConfig {
     pub max_posts: usize,
     #[serde(default = "default_true")]
     pub enabled: bool,
+    /// "json" for listings with scores and paging, or "rss"
+    #[serde(default = "default_reddit_api")]
+    pub api: String,
+    /// Time range for `sort = "top"`: hour, day, week, month, year or all
+    #[serde(default = "default_reddit_time")]
+    pub time: String,
+    /// Named groups of subreddits shown as one tab
+    #[serde(default)]
+    pub multireddits: BTreeMap<String, Vec<String>>,
+    /// Sort and time range per subreddit or multireddit name
+    #[serde(default)]
+    pub overrides: BTreeMap<String, RedditOverride>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArxivConfig {
+    /// Categories followed together, e.g. "cs.AI", "q-fin.TR" or "q-fin.ST"
+    #[serde(default = "default_arxiv_categories")]
+    pub categories: Vec<String>,
+    /// Papers filed elsewhere and cross-listed to a followed category
+    #[serde(default = "default_true")]
+    pub include_cross_lists: bool,
+    /// New versions of papers announced before
+    #[serde(default)]
+    pub include_replacements: bool,
+    /// Where downloaded PDFs go (default: the data directory's `papers`)
+    #[serde(default)]
+    pub papers_dir: Option<String>,
+    /// `.bib` file entries are appended to (default: `papers.bib` in the papers directory)
+    #[serde(default)]
+    pub bibtex_file: Option<String>,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+}
+
+/// Listing options for one subreddit or multireddit tab
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RedditOverride {
+    pub sort: Option<String>,
+    pub time: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -98,6 +160,15 @@ pub struct CacheConfig {
     #[serde(default = "default_max_cache_size")]
     pub max_size_mb: u64,
     pub path: Option<String>,
+    /// Keep a local full-text index of every fetched item
+    #[serde(default = "default_true")]
+    pub search_index: bool,
+    /// Snapshot every fetch for browsing past lists and rank history
+    #[serde(default)]
+    pub archive: bool,
+    /// Days to keep archive snapshots
+    #[serde(default = "default_archive_retention")]
+    pub archive_retention_days: u64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -110,6 +181,91 @@ pub struct KeybindingsConfig {
     pub help: String,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EntitiesConfig {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    #[serde(default = "default_true")]
+    pub bundled_symbols: bool,
+    pub symbols_file: Option<String>,
+}
+
+/// `finterm serve` settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServerConfig {
+    #[serde(default = "default_server_bind")]
+    pub bind: String,
+    /// Seconds between background fetches feeding the event stream
+    #[serde(default = "default_refresh_interval")]
+    pub poll_interval: u64,
+    /// Web origins allowed to read the API from a browser (CORS)
+    #[serde(default)]
+    pub allowed_origins: Vec<String>,
+}
+
+/// Desktop notifications and bell for new high-priority items in the TUI
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotificationsConfig {
+    /// One of osc9, osc777, bell or none
+    #[serde(default = "default_notify_method")]
+    pub method: String,
+    /// Also ring the bell alongside a desktop notification
+    #[serde(default)]
+    pub bell: bool,
+    /// Title regexes (case-insensitive) that notify, on top of `notify` rules
+    #[serde(default)]
+    pub keywords: Vec<String>,
+    /// Notify for items scoring at least this much
+    pub min_score: Option<i32>,
+}
+
+/// A `[[rules]]` entry: every matcher that is set must match for the actions to apply
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RuleConfig {
+    #[serde(default)]
+    pub name: String,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+
+    // Matchers (regexes are case-insensitive)
+    pub title: Option<String>,
+    pub domain: Option<String>,
+    pub provider: Option<String>,
+    pub author: Option<String>,
+    pub tag: Option<String>,
+    pub min_score: Option<i32>,
+    pub min_comments: Option<i32>,
+
+    // Actions
+    #[serde(default)]
+    pub hide: bool,
+    pub highlight: Option<String>,
+    #[serde(default)]
+    pub add_tags: Vec<String>,
+    #[serde(default)]
+    pub pin: bool,
+    #[serde(default)]
+    pub notify: bool,
+}
+
+/// A `[[webhooks]]` entry: POSTs items matched by `[[rules]]` to a URL
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookConfig {
+    #[serde(default)]
+    pub name: String,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    pub url: String,
+    /// Rule names that trigger the webhook; empty means every `notify` rule
+    #[serde(default)]
+    pub rules: Vec<String>,
+    /// Request body with `{{field}}` placeholders; defaults to the item as JSON
+    pub template: Option<String>,
+    /// Extra attempts after a failed delivery, backing off exponentially
+    #[serde(default = "default_webhook_retries")]
+    pub max_retries: u32,
+}
+
 // Default value functions
 fn default_finnhub_url() -> String {
     "https://finnhub.io/api/v1".to_string()
@@ -149,10 +305,22 @@ fn default_subreddits() -> Vec<String> {
     ]
 }
 
+fn default_arxiv_categories() -> Vec<String> {
+    vec!["cs.AI".to_string()]
+}
+
 fn default_reddit_sort() -> String {
     "hot".to_string()
 }
 
+fn default_reddit_api() -> String {
+    "json".to_string()
+}
+
+fn default_reddit_time() -> String {
+    "day".to_string()
+}
+
 fn default_max_posts() -> usize {
     50
 }
@@ -173,6 +341,22 @@ fn default_max_cache_size() -> u64 {
     100
 }
 
+fn default_archive_retention() -> u64 {
+    30
+}
+
+fn default_server_bind() -> String {
+    "127.0.0.1:8080".to_string()
+}
+
+fn default_notify_method() -> String {
+    "osc9".to_string()
+}
+
+fn default_webhook_retries() -> u32 {
+    3
+}
+
 fn default_quit_key() -> String {
     "q".to_string()
 }
@@ -208,6 +392,7 @@ impl Default for HackerNewsConfig {
             categories: default_categories(),
             include_dead: false,
             fetch_full_content: true,
+            live: false,
         }
     }
 }
@@ -219,6 +404,23 @@ impl Default for RedditConfig {
             sort: default_reddit_sort(),
             max_posts: default_max_posts(),
             enabled: true,
+            api: default_reddit_api(),
+            time: default_reddit_time(),
+            multireddits: BTreeMap::new(),
+            overrides: BTreeMap::new(),
+        }
+    }
+}
+
+impl Default for ArxivConfig {
+    fn default() -> Self {
+        Self {
+            categories: default_arxiv_categories(),
+            include_cross_lists: true,
+            include_replacements: false,
+            papers_dir: None,
+            bibtex_file: None,
+            enabled: true,
         }
     }
 }
@@ -241,6 +443,9 @@ impl Default for CacheConfig {
             ttl: default_cache_ttl(),
             max_size_mb: default_max_cache_size(),
             path: None,
+            search_index: true,
+            archive: false,
+            archive_retention_days: default_archive_retention(),
         }
     }
 }
@@ -255,6 +460,37 @@ impl Default for KeybindingsConfig {
     }
 }
 
+impl Default for EntitiesConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            bundled_symbols: true,
+            symbols_file: None,
+        }
+    }
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self {
+            bind: default_server_bind(),
+            poll_interval: default_refresh_interval(),
+            allowed_origins: Vec::new(),
+        }
+    }
+}
+
+impl Default for NotificationsConfig {
+    fn default() -> Self {
+        Self {
+            method: default_notify_method(),
+            bell: false,
+            keywords: Vec::new(),
+            min_score: None,
+        }
+    }
+}
+
 impl Config {
     /// Load configuration from file
     pub fn load(path: &PathBuf) -> Result<Self> {
@@ -278,6 +514,45 @@ impl Config {
         Ok(())
     }
 
+    /// Write `subreddits` to `[reddit]` in the file at `path`, leaving the
+    /// rest of the file (comments and other settings) as it is
+    pub fn save_subreddits(path: &PathBuf, subreddits: &[String]) -> Result<()> {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e.into()),
+        };
+        let mut doc = contents
+            .parse::<toml_edit::DocumentMut>()
+            .map_err(|e| ConfigError::Validation(e.to_string()))?;
+
+        let reddit = doc
+            .entry("reddit")
+            .or_insert_with(toml_edit::table)
+            .as_table_like_mut()
+            .ok_or_else(|| ConfigError::Validation("[reddit] is not a table".to_string()))?;
+        let names = subreddits.iter().map(String::as_str);
+        match reddit.get_mut("subreddits").and_then(|v| v.as_array_mut()) {
+            // Refill the existing array so its trailing comment survives
+            Some(existing) => {
+                existing.clear();
+                existing.extend(names);
+            }
+            None => {
+                reddit.insert(
+                    "subreddits",
+                    toml_edit::value(names.collect::<toml_edit::Array>()),
+                );
+            }
+        }
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, doc.to_string())?;
+        Ok(())
+    }
+
     /// Validate configuration
     fn validate(&self) -> Result<()> {
         // API key is optional - if not set, Finnhub will be skipped (demo mode)
@@ -288,6 +563,27 @@ impl Config {
             ));
         }
 
+        if self.cache.archive && self.cache.archive_retention_days == 0 {
+            return Err(ConfigError::Validation(
+                "Archive retention must be at least 1 day".to_string(),
+            ));
+        }
+
+        if self.server.poll_interval == 0 {
+            return Err(ConfigError::Validation(
+                "Server poll interval must be greater than 0".to_string(),
+            ));
+        }
+
+        crate::rules::RuleSet::compile(&self.rules)
+            .map_err(|e| ConfigError::Validation(e.to_string()))?;
+
+        crate::notifications::Notifier::from_config(&self.notifications)
+            .map_err(|e| ConfigError::Validation(e.to_string()))?;
+
+        crate::webhooks::Webhook::compile_all(&self.webhooks, &self.rules)
+            .map_err(|e| ConfigError::Validation(e.to_string()))?;
+
         Ok(())
     }
 
@@ -310,8 +606,71 @@ impl Config {
         }
     }
 
+    /// Get the directory for user data (bookmarks), kept by `cache-clear`
+    pub fn data_dir(&self) -> PathBuf {
+        dirs::data_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("finterm")
+    }
+
+    /// Get the directory downloaded arXiv PDFs are saved to
+    pub fn papers_dir(&self) -> PathBuf {
+        match &self.arxiv.papers_dir {
+            Some(path) => PathBuf::from(shellexpand::tilde(path).to_string()),
+            None => self.data_dir().join("papers"),
+        }
+    }
+
+    /// Get the BibTeX file paper entries are appended to
+    pub fn bibtex_file(&self) -> PathBuf {
+        match &self.arxiv.bibtex_file {
+            Some(path) => PathBuf::from(shellexpand::tilde(path).to_string()),
+            None => self.papers_dir().join("papers.bib"),
+        }
+    }
+
+    /// Get the user-supplied ticker symbol file, if configured
+    pub fn symbols_file(&self) -> Option<PathBuf> {
+        self.entities
+            .symbols_file
+            .as_ref()
+            .map(|path| PathBuf::from(shellexpand::tilde(path).to_string()))
+    }
+
     /// Create example configuration file
     pub fn create_example() -> String {
         toml::to_string_pretty(&Config::default()).unwrap()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_save_subreddits_keeps_rest_of_file() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        fs::write(
+            &path,
+            "# my settings\n[cache]\nenabled = true  # keep\n\n[reddit]\nsubreddits = [\"rust\"]  # tabs\nsort = \"top\"\n",
+        )
+        .unwrap();
+
+        Config::save_subreddits(&path, &["rust".to_string(), "golang".to_string()]).unwrap();
+        let saved = fs::read_to_string(&path).unwrap();
+        assert_eq!(
+            saved,
+            "# my settings\n[cache]\nenabled = true  # keep\n\n[reddit]\nsubreddits = [\"rust\", \"golang\"]  # tabs\nsort = \"top\"\n"
+        );
+
+        // A file without [reddit] gets the table added
+        let bare = dir.path().join("bare.toml");
+        fs::write(&bare, "[ui]\nvim_mode = false\n").unwrap();
+        Config::save_subreddits(&bare, &["rust".to_string()]).unwrap();
+        let config = Config::load(&bare).unwrap();
+        assert_eq!(config.reddit.subreddits, vec!["rust"]);
+        assert!(!config.ui.vim_mode);
+    }
+}
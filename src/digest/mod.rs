@@ -0,0 +1,562 @@
+//! Digest
+//!
+//! Builds a brief of recent items: the top stories per provider by score,
+//! grouped by tag, with summaries, link previews and comment counts. Rendered
+//! as Markdown or as a standalone HTML page.
+
+use crate::models::FeedItem;
+use crate::providers::link_preview::{fetch_link_preview, is_extractable_url};
+use crate::providers::ProviderRegistry;
+use crate::search::{Query, SearchIndex};
+use crate::utils::parser::truncate;
+use chrono::{DateTime, Local, Utc};
+use futures::stream::{self, StreamExt};
+use reqwest::Client;
+use std::collections::HashSet;
+use std::fmt::Write;
+use std::path::Path;
+use std::str::FromStr;
+use thiserror::Error;
+
+/// Link previews fetched at once
+const PREVIEW_CONCURRENCY: usize = 8;
+const SUMMARY_CHARS: usize = 320;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum DigestError {
+    #[error("Unknown digest format '{0}' (use md or html)")]
+    UnknownFormat(String),
+}
+
+/// Output format
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DigestFormat {
+    Markdown,
+    Html,
+}
+
+impl DigestFormat {
+    /// Guess the format from an output file name, defaulting to Markdown
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("html") || ext.eq_ignore_ascii_case("htm") => {
+                DigestFormat::Html
+            }
+            _ => DigestFormat::Markdown,
+        }
+    }
+}
+
+impl FromStr for DigestFormat {
+    type Err = DigestError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "md" | "markdown" => Ok(DigestFormat::Markdown),
+            "html" | "htm" => Ok(DigestFormat::Html),
+            _ => Err(DigestError::UnknownFormat(s.to_string())),
+        }
+    }
+}
+
+/// Items sharing a tag within a provider section, best first
+#[derive(Debug, Clone)]
+pub struct DigestGroup {
+    pub tag: Option<String>,
+    pub items: Vec<FeedItem>,
+}
+
+/// One provider's top items
+#[derive(Debug, Clone)]
+pub struct DigestSection {
+    pub provider_id: String,
+    pub name: String,
+    pub groups: Vec<DigestGroup>,
+}
+
+/// A brief of the top recent items per provider
+#[derive(Debug, Clone)]
+pub struct Digest {
+    pub generated_at: DateTime<Utc>,
+    pub since: DateTime<Utc>,
+    pub sections: Vec<DigestSection>,
+}
+
+/// Fetch fresh items from every ready provider, plus anything indexed in the window
+///
+/// Fresh copies come first so they win over older indexed ones when deduplicated.
+pub async fn collect(
+    registry: &ProviderRegistry,
+    index: Option<&SearchIndex>,
+    since: DateTime<Utc>,
+    limit_per_provider: usize,
+) -> Vec<FeedItem> {
+    let mut items = registry.fetch_all(limit_per_provider).await;
+
+    if let Some(index) = index {
+        if let Err(e) = index.index_items(&items) {
+            tracing::warn!("Failed to index items: {}", e);
+        }
+
+        let query = Query {
+            since: Some(since),
+            ..Query::default()
+        };
+        match index.search(&query, usize::MAX) {
+            Ok(hits) => items.extend(hits.into_iter().map(|hit| hit.item)),
+            Err(e) => tracing::warn!("Failed to read search index: {}", e),
+        }
+    }
+
+    items
+}
+
+impl Digest {
+    /// Pick the top `per_provider` items published since `since` for each provider
+    ///
+    /// `providers` lists `(id, display name)` in section order; items from
+    /// providers not listed get trailing sections named by their id.
+    pub fn build(
+        providers: &[(String, String)],
+        items: Vec<FeedItem>,
+        since: DateTime<Utc>,
+        per_provider: usize,
+    ) -> Self {
+        let mut seen = HashSet::new();
+        let items: Vec<FeedItem> = items
+            .into_iter()
+            .filter(|item| item.published_at >= since)
+            .filter(|item| seen.insert((item.provider_id.clone(), item.id.clone())))
+            .collect();
+
+        let mut order: Vec<(String, String)> = providers.to_vec();
+        for item in &items {
+            if !order.iter().any(|(id, _)| *id == item.provider_id) {
+                order.push((item.provider_id.clone(), item.provider_id.clone()));
+            }
+        }
+
+        let sections = order
+            .into_iter()
+            .filter_map(|(provider_id, name)| {
+                let mut top: Vec<FeedItem> = items
+                    .iter()
+                    .filter(|item| item.provider_id == provider_id)
+                    .cloned()
+                    .collect();
+                top.sort_by_key(|item| {
+                    (
+                        std::cmp::Reverse(item.metadata.score),
+                        std::cmp::Reverse(item.metadata.comments),
+                        std::cmp::Reverse(item.published_at),
+                    )
+                });
+                top.truncate(per_provider);
+                if top.is_empty() {
+                    return None;
+                }
+
+                Some(DigestSection {
+                    groups: group_by_tag(&provider_id, top),
+                    provider_id,
+                    name,
+                })
+            })
+            .collect();
+
+        Self {
+            generated_at: Utc::now(),
+            since,
+            sections,
+        }
+    }
+
+    /// Number of items across all sections
+    pub fn item_count(&self) -> usize {
+        self.items().count()
+    }
+
+    fn items(&self) -> impl Iterator<Item = &FeedItem> {
+        self.sections
+            .iter()
+            .flat_map(|s| s.groups.iter())
+            .flat_map(|g| g.items.iter())
+    }
+
+    /// Fetch link previews for items that link to an article
+    pub async fn attach_previews(&mut self, client: &Client) {
+        let urls: Vec<String> = self
+            .items()
+            .filter(|item| item.metadata.link_preview.is_none())
+            .filter_map(|item| item.url.clone())
+            .filter(|url| is_extractable_url(url))
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+
+        let previews: Vec<_> = stream::iter(urls)
+            .map(|url| async move {
+                let preview = fetch_link_preview(client, &url).await;
+                (url, preview)
+            })
+            .buffer_unordered(PREVIEW_CONCURRENCY)
+            .collect()
+            .await;
+
+        for (url, preview) in previews {
+            let Some(preview) = preview else {
+                continue;
+            };
+            for item in self
+                .sections
+                .iter_mut()
+                .flat_map(|s| s.groups.iter_mut())
+                .flat_map(|g| g.items.iter_mut())
+                .filter(|item| item.url.as_deref() == Some(url.as_str()))
+            {
+                item.metadata.link_preview = Some(preview.clone());
+            }
+        }
+    }
+
+    /// Render in the given format
+    pub fn render(&self, format: DigestFormat) -> String {
+        match format {
+            DigestFormat::Markdown => self.to_markdown(),
+            DigestFormat::Html => self.to_html(),
+        }
+    }
+
+    fn subtitle(&self) -> String {
+        format!(
+            "{} · {} items since {}",
+            self.generated_at
+                .with_timezone(&Local)
+                .format("%a %d %b %Y %H:%M"),
+            self.item_count(),
+            self.since.with_timezone(&Local).format("%a %d %b %H:%M"),
+        )
+    }
+
+    fn to_markdown(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "# finterm digest\n\n_{}_", self.subtitle());
+
+        for section in &self.sections {
+            let _ = writeln!(out, "\n## {}", section.name);
+            let show_tags = section.groups.len() > 1;
+
+            for group in &section.groups {
+                if show_tags {
+                    let tag = group.tag.as_deref().unwrap_or("Other");
+                    let _ = writeln!(out, "\n### {}", tag);
+                }
+                out.push('\n');
+
+                for (idx, item) in group.items.iter().enumerate() {
+                    let title = escape_markdown(&item.title);
+                    match &item.url {
+                        Some(url) => {
+                            let _ = writeln!(out, "{}. **[{}]({})**", idx + 1, title, url);
+                        }
+                        None => {
+                            let _ = writeln!(out, "{}. **{}**", idx + 1, title);
+                        }
+                    }
+
+                    let meta: Vec<String> = meta_parts(item)
+                        .into_iter()
+                        .map(|(text, link)| match link {
+                            Some(link) => format!("[{}]({})", text, link),
+                            None => escape_markdown(&text),
+                        })
+                        .collect();
+                    let _ = writeln!(out, "   {}", meta.join(" · "));
+
+                    if let Some(summary) = summary_text(item) {
+                        let _ = writeln!(out, "\n   {}", escape_markdown(&summary));
+                    }
+                    if let Some(preview) = preview_line(item) {
+                        let _ = writeln!(out, "\n   > {}", escape_markdown(&preview));
+                    }
+                    out.push('\n');
+                }
+            }
+        }
+
+        out
+    }
+
+    fn to_html(&self) -> String {
+        let mut out = String::new();
+        out.push_str(concat!(
+            "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n",
+            "<title>finterm digest</title>\n<style>\n",
+            "body{font-family:-apple-system,Segoe UI,Helvetica,Arial,sans-serif;",
+            "max-width:760px;margin:2em auto;padding:0 1em;color:#222;line-height:1.45}\n",
+            "h2{border-bottom:2px solid #f60;padding-bottom:.2em}\n",
+            "h3{color:#555;font-size:1em;text-transform:uppercase}\n",
+            "li{margin-bottom:1em}\n.meta{color:#777;font-size:.9em}\n",
+            ".preview{border-left:3px solid #ddd;padding-left:.6em;color:#555;font-size:.9em}\n",
+            "</style>\n</head>\n<body>\n",
+        ));
+        let _ = writeln!(
+            out,
+            "<h1>finterm digest</h1>\n<p class=\"meta\">{}</p>",
+            escape_html(&self.subtitle())
+        );
+
+        for section in &self.sections {
+            let _ = writeln!(out, "<h2>{}</h2>", escape_html(&section.name));
+            let show_tags = section.groups.len() > 1;
+
+            for group in &section.groups {
+                if show_tags {
+                    let tag = group.tag.as_deref().unwrap_or("Other");
+                    let _ = writeln!(out, "<h3>{}</h3>", escape_html(tag));
+                }
+                out.push_str("<ol>\n");
+
+                for item in &group.items {
+                    let title = escape_html(&item.title);
+                    match &item.url {
+                        Some(url) => {
+                            let _ = write!(
+                                out,
+                                "<li><strong><a href=\"{}\">{}</a></strong>",
+                                escape_html(url),
+                                title
+                            );
+                        }
+                        None => {
+                            let _ = write!(out, "<li><strong>{}</strong>", title);
+                        }
+                    }
+
+                    let meta: Vec<String> = meta_parts(item)
+                        .into_iter()
+                        .map(|(text, link)| match link {
+                            Some(link) => format!(
+                                "<a href=\"{}\">{}</a>",
+                                escape_html(&link),
+                                escape_html(&text)
+                            ),
+                            None => escape_html(&text),
+                        })
+                        .collect();
+                    let _ = write!(out, "<br><span class=\"meta\">{}</span>", meta.join(" · "));
+
+                    if let Some(summary) = summary_text(item) {
+                        let _ = write!(out, "<p>{}</p>", escape_html(&summary));
+                    }
+                    if let Some(preview) = preview_line(item) {
+                        let _ = write!(out, "<p class=\"preview\">{}</p>", escape_html(&preview));
+                    }
+                    out.push_str("</li>\n");
+                }
+                out.push_str("</ol>\n");
+            }
+        }
+
+        out.push_str("</body>\n</html>\n");
+        out
+    }
+}
+
+/// Split ranked items by their first meaningful tag, keeping rank order
+fn group_by_tag(provider_id: &str, items: Vec<FeedItem>) -> Vec<DigestGroup> {
+    let mut groups: Vec<DigestGroup> = Vec::new();
+
+    for item in items {
+        let tag = item
+            .metadata
+            .tags
+            .iter()
+            .find(|t| !t.eq_ignore_ascii_case(provider_id))
+            .cloned();
+        match groups.iter_mut().find(|g| g.tag == tag) {
+            Some(group) => group.items.push(item),
+            None => groups.push(DigestGroup {
+                tag,
+                items: vec![item],
+            }),
+        }
+    }
+
+    // Untagged leftovers read best at the end
+    groups.sort_by_key(|g| g.tag.is_none());
+    groups
+}
+
+/// Points, comments (linking to the thread), source, author and age
+fn meta_parts(item: &FeedItem) -> Vec<(String, Option<String>)> {
+    let mut parts = Vec::new();
+    if let Some(score) = item.metadata.score {
+        parts.push((format!("{} points", score), None));
+    }
+    if let Some(comments) = item.metadata.comments {
+        parts.push((format!("{} comments", comments), item.discussion_url()));
+    }
+    parts.push((item.source.clone(), None));
+    if let Some(author) = &item.author {
+        parts.push((format!("by {}", author), None));
+    }
+    parts.push((item.time_ago(), None));
+    parts
+}
+
+/// The item's own summary, or the linked page's description
+fn summary_text(item: &FeedItem) -> Option<String> {
+    let preview_description = item
+        .metadata
+        .link_preview
+        .as_ref()
+        .and_then(|p| p.description.as_deref());
+    let text = item.summary.as_deref().or(preview_description)?;
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    (!text.is_empty()).then(|| truncate(&text, SUMMARY_CHARS))
+}
+
+/// Site, reading time and description from the link preview
+fn preview_line(item: &FeedItem) -> Option<String> {
+    let preview = item.metadata.link_preview.as_ref()?;
+    let mut parts = Vec::new();
+    if let Some(site) = &preview.site_name {
+        parts.push(site.clone());
+    }
+    if let Some(minutes) = preview.reading_time {
+        parts.push(format!("~{} min read", minutes));
+    }
+    // The description is already the summary when the item has none
+    if let (Some(description), Some(_)) = (&preview.description, &item.summary) {
+        parts.push(truncate(description.trim(), SUMMARY_CHARS));
+    }
+    (!parts.is_empty()).then(|| parts.join(" · "))
+}
+
+fn escape_markdown(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '[' | ']' | '*' | '_' | '`' | '<' | '>') {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn providers() -> Vec<(String, String)> {
+        vec![
+            ("hackernews".to_string(), "Hacker News".to_string()),
+            ("reddit".to_string(), "Reddit".to_string()),
+        ]
+    }
+
+    #[test]
+    fn test_build_ranks_filters_and_groups() {
+        let items = vec![
+            FeedItem::test("r1", "reddit")
+                .with_score(50)
+                .with_age(Duration::hours(2))
+                .with_tags(&["r/stocks"]),
+            FeedItem::test("r2", "reddit")
+                .with_score(300)
+                .with_age(Duration::hours(3))
+                .with_tags(&["r/investing"]),
+            FeedItem::test("r3", "reddit")
+                .with_score(120)
+                .with_age(Duration::hours(1))
+                .with_tags(&["r/stocks"]),
+            FeedItem::test("old", "reddit")
+                .with_score(9000)
+                .with_age(Duration::hours(48))
+                .with_tags(&["r/stocks"]),
+            FeedItem::test("h1", "hackernews")
+                .with_score(10)
+                .with_age(Duration::hours(1)),
+            // Older indexed copy of a fresh item
+            FeedItem::test("h1", "hackernews")
+                .with_score(2)
+                .with_age(Duration::hours(1)),
+        ];
+
+        let digest = Digest::build(&providers(), items, Utc::now() - Duration::hours(24), 3);
+
+        assert_eq!(digest.item_count(), 4);
+        assert_eq!(digest.sections[0].name, "Hacker News");
+        assert_eq!(
+            digest.sections[0].groups[0].items[0].metadata.score,
+            Some(10)
+        );
+
+        let reddit = &digest.sections[1];
+        let groups: Vec<_> = reddit
+            .groups
+            .iter()
+            .map(|g| {
+                (
+                    g.tag.as_deref().unwrap(),
+                    g.items.iter().map(|i| i.id.as_str()).collect::<Vec<_>>(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            groups,
+            vec![("r/investing", vec!["r2"]), ("r/stocks", vec!["r3", "r1"])]
+        );
+    }
+
+    #[test]
+    fn test_render_markdown_and_html() {
+        let mut hn = FeedItem::test("h1", "hackernews")
+            .with_url("https://example.com/h1".to_string())
+            .with_score(42)
+            .with_age(Duration::hours(1))
+            .with_summary("Fast <b>tree</b>".into());
+        hn.title = "Show HN: [beta] A & B".to_string();
+        hn.metadata.comments = Some(7);
+        hn.metadata.hn_id = Some(123);
+
+        let digest = Digest::build(&providers(), vec![hn], Utc::now() - Duration::hours(24), 10);
+
+        let md = digest.render(DigestFormat::Markdown);
+        assert!(md.contains("## Hacker News"));
+        assert!(md.contains("1. **[Show HN: \\[beta\\] A & B](https://example.com/h1)**"));
+        assert!(md.contains("42 points · [7 comments](https://news.ycombinator.com/item?id=123)"));
+        assert!(!md.contains("###"));
+
+        let html = digest.render(DigestFormat::Html);
+        assert!(html.contains("<a href=\"https://example.com/h1\">Show HN: [beta] A &amp; B</a>"));
+        assert!(html.contains("<p>Fast &lt;b&gt;tree&lt;/b&gt;</p>"));
+    }
+
+    #[test]
+    fn test_format_parsing() {
+        assert_eq!("md".parse(), Ok(DigestFormat::Markdown));
+        assert_eq!("HTML".parse(), Ok(DigestFormat::Html));
+        assert_eq!(
+            "pdf".parse::<DigestFormat>(),
+            Err(DigestError::UnknownFormat("pdf".to_string()))
+        );
+        assert_eq!(
+            DigestFormat::from_path(Path::new("brief.html")),
+            DigestFormat::Html
+        );
+        assert_eq!(
+            DigestFormat::from_path(Path::new("brief.txt")),
+            DigestFormat::Markdown
+        );
+    }
+}
@@ -1,9 +1,19 @@
+pub mod analysis;
+pub mod atom;
+pub mod bookmarks;
 pub mod cache;
 pub mod config;
+pub mod digest;
 pub mod models;
+pub mod notifications;
+pub mod papers;
 pub mod providers;
+pub mod rules;
+pub mod search;
+pub mod server;
 pub mod ui;
 pub mod utils;
+pub mod webhooks;
 
 pub use cache::CacheManager;
 pub use config::Config;
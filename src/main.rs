@@ -1,10 +1,23 @@
+use anyhow::Context;
+use chrono::{Local, Utc};
 use clap::{Parser, Subcommand};
 use crossterm::{
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
-use finterm::{App, Config};
+use finterm::atom::{self, AtomFeed};
+use finterm::bookmarks::Bookmarks;
+use finterm::cache::CacheError;
+use finterm::digest::{self, Digest, DigestFormat};
+use finterm::models::Comment;
+use finterm::providers;
+use finterm::search::{Query, SearchIndex};
+use finterm::server::ApiServer;
+use finterm::utils::parser::parse_since;
+use finterm::webhooks::{Webhook, WebhookDispatcher};
+use finterm::{App, CacheManager, Config, ProviderRegistry};
 use ratatui::{backend::CrosstermBackend, Terminal};
+use std::collections::HashSet;
 use std::io;
 use std::path::PathBuf;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
@@ -51,10 +64,72 @@ enum Commands {
     /// View Hacker News only
     Hn,
 
-    /// Search across all sources
+    /// Search everything fetched so far (offline, from the local index)
     Search {
-        /// Search query
+        /// Search query, e.g. "fed rates provider:finnhub since:7d"
         query: String,
+
+        /// Maximum number of results
+        #[arg(short = 'n', long, default_value_t = 20)]
+        limit: usize,
+    },
+
+    /// Find Hacker News and Reddit discussions of a link
+    Discuss {
+        /// Link to look up
+        url: String,
+
+        /// Print the comments of the Nth discussion (1 = most discussed)
+        #[arg(short, long, value_name = "N")]
+        thread: Option<usize>,
+    },
+
+    /// Write a digest of the top recent items per provider
+    Digest {
+        /// How far back to look, e.g. 24h, 7d or 2026-01-31
+        #[arg(long, default_value = "24h")]
+        since: String,
+
+        /// Output format: md or html (default: from --out, else md)
+        #[arg(long)]
+        format: Option<DigestFormat>,
+
+        /// Write to this file instead of stdout
+        #[arg(short, long, value_name = "FILE")]
+        out: Option<PathBuf>,
+
+        /// Items per provider
+        #[arg(short = 'n', long, default_value_t = 10)]
+        top: usize,
+    },
+
+    /// Write an Atom feed of the merged, rule-filtered stream (or of bookmarks)
+    Atom {
+        /// Publish bookmarks instead of a fresh fetch
+        #[arg(long)]
+        bookmarks: bool,
+
+        /// Write to this file instead of stdout
+        #[arg(short, long, value_name = "FILE")]
+        out: Option<PathBuf>,
+    },
+
+    /// Serve providers, search and new items over a local HTTP/JSON API
+    Serve {
+        /// Address to listen on (default: [server] bind, 127.0.0.1:8080)
+        #[arg(long, value_name = "ADDR")]
+        bind: Option<String>,
+    },
+
+    /// Fetch on a schedule without the TUI, sending rule matches to webhooks
+    Watch {
+        /// Seconds between fetches (default: [finnhub] refresh_interval)
+        #[arg(long, value_name = "SECS")]
+        interval: Option<u64>,
+
+        /// Fetch and deliver once, then exit (for cron)
+        #[arg(long)]
+        once: bool,
     },
 }
 
@@ -103,7 +178,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     }
 
     // Create app
-    let mut app = App::new(config)?;
+    let mut app = App::new(config)?.with_config_path(config_path);
 
     // Setup terminal
     enable_raw_mode()?;
@@ -182,11 +257,289 @@ async fn handle_command(
             // Would launch TUI in HN-only mode
         }
 
-        Commands::Search { query } => {
-            println!("Searching for: {}", query);
-            // Would perform search and display results
+        Commands::Search { query, limit } => {
+            let config = if config_path.exists() {
+                Config::load(&config_path)?
+            } else {
+                Config::default()
+            };
+
+            let cache = CacheManager::new(config.cache_dir(), config.cache.max_size_mb)?;
+            let index = SearchIndex::open(&cache)?;
+            let hits = index.search(&Query::parse(&query)?, limit)?;
+
+            if hits.is_empty() {
+                println!("No results ({} items indexed)", index.len());
+            }
+            for hit in hits {
+                let item = hit.item;
+                let mut meta = vec![item.provider_id.clone(), item.time_ago()];
+                meta.extend(item.score_display());
+                meta.extend(item.comments_display());
+                println!("{}", item.title);
+                println!("  {}", meta.join(" | "));
+                if let Some(url) = item.url {
+                    println!("  {}", url);
+                }
+            }
+        }
+
+        Commands::Discuss { url, thread } => {
+            let discussions = providers::find_discussions(&url).await?;
+            if discussions.is_empty() {
+                println!("No discussions found for {}", url);
+            }
+
+            match thread {
+                None => {
+                    for (n, item) in discussions.iter().enumerate() {
+                        let mut meta = vec![item.source.clone(), item.time_ago()];
+                        meta.extend(item.score_display());
+                        meta.extend(item.comments_display());
+                        println!("{:>2}. {}", n + 1, item.title);
+                        println!("    {}", meta.join(" | "));
+                        if let Some(link) = item.discussion_url() {
+                            println!("    {}", link);
+                        }
+                    }
+                }
+                Some(n) => {
+                    let item = discussions.get(n.wrapping_sub(1)).ok_or_else(|| {
+                        format!("No discussion #{} ({} found)", n, discussions.len())
+                    })?;
+                    println!("{}", item.title);
+                    if let Some(link) = item.discussion_url() {
+                        println!("{}", link);
+                    }
+                    println!();
+                    print_comments(&providers::fetch_comments(item, 3).await?);
+                }
+            }
+        }
+
+        Commands::Digest {
+            since,
+            format,
+            out,
+            top,
+        } => {
+            let config = if config_path.exists() {
+                Config::load(&config_path)?
+            } else {
+                Config::default()
+            };
+            let since = parse_since(&since, Utc::now()).ok_or_else(|| {
+                format!(
+                    "Invalid --since '{}' (use e.g. 24h, 7d or 2026-01-31)",
+                    since
+                )
+            })?;
+
+            let registry = ProviderRegistry::from_config(&config)?;
+            let cache = CacheManager::new(config.cache_dir(), config.cache.max_size_mb)?;
+            let index = if config.cache.search_index {
+                Some(SearchIndex::open(&cache)?)
+            } else {
+                None
+            };
+
+            let limit = config.finnhub.max_articles.max(100);
+            let items = digest::collect(&registry, index.as_ref(), since, limit).await;
+            let providers: Vec<(String, String)> = registry
+                .all()
+                .iter()
+                .map(|p| (p.id().to_string(), p.name().to_string()))
+                .collect();
+
+            let mut digest = Digest::build(&providers, items, since, top);
+            let client = reqwest::Client::builder()
+                .timeout(std::time::Duration::from_secs(15))
+                .build()?;
+            digest.attach_previews(&client).await;
+
+            let format = format.unwrap_or_else(|| {
+                out.as_deref()
+                    .map(DigestFormat::from_path)
+                    .unwrap_or(DigestFormat::Markdown)
+            });
+            let text = digest.render(format);
+
+            match out {
+                Some(path) => {
+                    std::fs::write(&path, text)?;
+                    println!("Wrote {} items to {}", digest.item_count(), path.display());
+                }
+                None => print!("{}", text),
+            }
+        }
+
+        Commands::Atom { bookmarks, out } => {
+            let config = if config_path.exists() {
+                Config::load(&config_path)?
+            } else {
+                Config::default()
+            };
+
+            let feed = if bookmarks {
+                atom::bookmarks_feed(Bookmarks::open(&config.data_dir())?.list()?)
+            } else {
+                let registry = ProviderRegistry::from_config(&config)?;
+                let items = registry
+                    .fetch_all(config.finnhub.max_articles.max(100))
+                    .await;
+                AtomFeed::from_items(atom::MERGED_FEED_ID, "finterm", atom::dedupe(items))
+            };
+
+            match out {
+                Some(path) => {
+                    std::fs::write(&path, feed.render())?;
+                    println!("Wrote {} entries to {}", feed.len(), path.display());
+                }
+                None => print!("{}", feed.render()),
+            }
+        }
+
+        Commands::Serve { bind } => {
+            let config = if config_path.exists() {
+                Config::load(&config_path)?
+            } else {
+                Config::default()
+            };
+            let bind = bind.unwrap_or_else(|| config.server.bind.clone());
+
+            let registry = ProviderRegistry::from_config(&config)?;
+            // The TUI holds the cache lock while it runs; serve without the
+            // local index rather than refusing to start
+            let cache = match CacheManager::new(config.cache_dir(), config.cache.max_size_mb) {
+                Ok(cache) => Some(cache),
+                Err(CacheError::Locked(path)) => {
+                    eprintln!(
+                        "Cache at {} is in use (is the TUI running?); /search will only query providers",
+                        path
+                    );
+                    None
+                }
+                Err(e) => return Err(e.into()),
+            };
+            let index = match &cache {
+                Some(cache) if config.cache.search_index => Some(SearchIndex::open(cache)?),
+                _ => None,
+            };
+
+            let mut server = ApiServer::new(
+                registry,
+                index,
+                std::time::Duration::from_secs(config.server.poll_interval),
+                config.finnhub.max_articles.max(100),
+            )
+            .with_allowed_origins(config.server.allowed_origins.clone());
+            if let Some(cache) = cache.filter(|_| config.cache.enabled) {
+                server = server.with_cache(cache, config.cache.ttl);
+            }
+            match Bookmarks::open(&config.data_dir()) {
+                Ok(bookmarks) => server = server.with_bookmarks(bookmarks),
+                Err(e) => eprintln!("Bookmarks unavailable: {}", e),
+            }
+            let listener = tokio::net::TcpListener::bind(&bind).await?;
+            println!("Serving on http://{}", listener.local_addr()?);
+            server.serve(listener).await?;
+        }
+
+        Commands::Watch { interval, once } => {
+            let config = if config_path.exists() {
+                Config::load(&config_path)?
+            } else {
+                Config::default()
+            };
+            let interval = interval.unwrap_or(config.finnhub.refresh_interval).max(1);
+
+            let registry = ProviderRegistry::from_config(&config)?;
+            // Delivered keys live in the cache so the TUI and watch never send
+            // an item twice; only one of them can hold it at a time
+            let cache = CacheManager::new(config.cache_dir(), config.cache.max_size_mb).context(
+                "Could not open the cache. The TUI delivers webhooks while it is open; \
+                     run `finterm watch` once it is closed.",
+            )?;
+            let index = if config.cache.search_index {
+                Some(SearchIndex::open(&cache)?)
+            } else {
+                None
+            };
+            let hooks = Webhook::compile_all(&config.webhooks, &config.rules)?;
+            if hooks.is_empty() {
+                eprintln!("No [[webhooks]] configured; printing rule matches only");
+            }
+            let webhooks = WebhookDispatcher::open(hooks, &cache)?;
+
+            let mut ticker = tokio::time::interval(std::time::Duration::from_secs(interval));
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            let mut announced = HashSet::new();
+            loop {
+                tokio::select! {
+                    _ = ticker.tick() => {}
+                    _ = tokio::signal::ctrl_c() => break,
+                }
+
+                let items = registry
+                    .fetch_all(config.finnhub.max_articles.max(100))
+                    .await;
+                if let Some(index) = &index {
+                    if let Err(e) = index.index_items(&items) {
+                        eprintln!("Failed to index items: {}", e);
+                    }
+                }
+
+                for item in items.iter().filter(|item| item.metadata.notify) {
+                    if announced.insert((item.provider_id.clone(), item.id.clone())) {
+                        println!(
+                            "{} 🔔 [{}] {}",
+                            Local::now().format("%H:%M"),
+                            item.metadata.matched_rules.join(", "),
+                            item.title
+                        );
+                    }
+                }
+
+                for handle in webhooks.dispatch(&items) {
+                    let Ok(delivery) = handle.await else {
+                        continue;
+                    };
+                    match delivery.result {
+                        Ok(attempts) => println!(
+                            "  → {}: sent '{}' ({} attempt{})",
+                            delivery.webhook,
+                            delivery.title,
+                            attempts,
+                            if attempts == 1 { "" } else { "s" }
+                        ),
+                        Err(e) => println!("  ✗ {}: '{}': {}", delivery.webhook, delivery.title, e),
+                    }
+                }
+
+                if once {
+                    break;
+                }
+            }
         }
     }
 
     Ok(())
 }
+
+/// Print a comment tree, indenting replies
+fn print_comments(comments: &[Comment]) {
+    for comment in comments {
+        let indent = "  ".repeat(comment.depth as usize);
+        let score = comment
+            .score
+            .map(|s| format!(" ▲{}", s))
+            .unwrap_or_default();
+        println!("{}{}{}", indent, comment.author, score);
+        let text = comment.text_plain.as_ref().unwrap_or(&comment.text);
+        for line in text.lines().filter(|l| !l.trim().is_empty()) {
+            println!("{}  {}", indent, line.trim());
+        }
+        println!();
+        print_comments(&comment.replies);
+    }
+}
@@ -43,6 +43,9 @@ pub enum CacheKey {
     HnComments(u64),
     HnContent(String),   // URL hash
     HnStoryList(String), // Category name
+    LinkPreview(String), // URL hash
+    /// Items served by the HTTP API for a provider ("all" for the merged feed)
+    ProviderItems(String),
 }
 
 impl CacheKey {
@@ -61,6 +64,11 @@ impl CacheKey {
                 format!("hn:content:{}", hash)
             }
             CacheKey::HnStoryList(category) => format!("hn:list:{}", category),
+            CacheKey::LinkPreview(url) => {
+                let hash = Self::hash_string(url);
+                format!("preview:{}", hash)
+            }
+            CacheKey::ProviderItems(provider) => format!("items:{}", provider),
         }
     }
 
@@ -53,6 +53,79 @@ pub struct FeedItemMetadata {
     pub hn_id: Option<u64>,
     /// Reddit post ID for fetching comments
     pub reddit_id: Option<String>,
+    /// Post flair text (Reddit-specific)
+    #[serde(default)]
+    pub flair: Option<String>,
+    /// Marked not safe for work (Reddit-specific)
+    #[serde(default)]
+    pub nsfw: bool,
+    /// Marked as a spoiler (Reddit-specific)
+    #[serde(default)]
+    pub spoiler: bool,
+    /// Kind of post (Reddit-specific)
+    #[serde(default)]
+    pub post_type: Option<PostType>,
+    /// Image, gallery or video URLs in display order (Reddit-specific)
+    #[serde(default)]
+    pub media_urls: Vec<String>,
+    /// Subreddit a crosspost was taken from (Reddit-specific)
+    #[serde(default)]
+    pub crosspost_from: Option<String>,
+    /// Every author in listed order (arXiv-specific)
+    #[serde(default)]
+    pub authors: Vec<String>,
+    /// Identifier without version, e.g. 2401.00001 (arXiv-specific)
+    #[serde(default)]
+    pub arxiv_id: Option<String>,
+    /// Version of the paper the item describes (arXiv-specific)
+    #[serde(default)]
+    pub arxiv_version: Option<u32>,
+    /// Link to the paper's PDF (arXiv-specific)
+    #[serde(default)]
+    pub pdf_url: Option<String>,
+    /// Names of config rules that matched this item
+    #[serde(default)]
+    pub matched_rules: Vec<String>,
+    /// Highlight colour set by a rule
+    #[serde(default)]
+    pub highlight: Option<String>,
+    /// Pinned to the top of the list by a rule
+    #[serde(default)]
+    pub pinned: bool,
+    /// Hidden by a rule
+    #[serde(default)]
+    pub hidden: bool,
+    /// A matching rule asked for a notification
+    #[serde(default)]
+    pub notify: bool,
+}
+
+/// Kind of Reddit post
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PostType {
+    /// Self-post with text
+    Text,
+    /// Link to another site
+    Link,
+    Image,
+    Video,
+    Gallery,
+    /// Repost of another post
+    Crosspost,
+}
+
+impl PostType {
+    pub fn as_str(&self) -> &str {
+        match self {
+            PostType::Text => "text",
+            PostType::Link => "link",
+            PostType::Image => "image",
+            PostType::Video => "video",
+            PostType::Gallery => "gallery",
+            PostType::Crosspost => "crosspost",
+        }
+    }
 }
 
 /// A comment in a discussion thread
@@ -268,6 +341,20 @@ impl FeedItem {
         }
     }
 
+    /// Link to the item's comment thread, when the source has one
+    pub fn discussion_url(&self) -> Option<String> {
+        if let Some(hn_id) = self.metadata.hn_id {
+            return Some(format!("https://news.ycombinator.com/item?id={}", hn_id));
+        }
+        match (&self.metadata.subreddit, &self.metadata.reddit_id) {
+            (Some(subreddit), Some(post_id)) => Some(format!(
+                "https://www.reddit.com/r/{}/comments/{}/",
+                subreddit, post_id
+            )),
+            _ => None,
+        }
+    }
+
     /// Get display string with score if available
     pub fn score_display(&self) -> Option<String> {
         self.metadata.score.map(|s| format!("▲{}", s))
@@ -279,6 +366,54 @@ impl FeedItem {
     }
 }
 
+/// Fixture builder shared by unit tests across the crate
+#[cfg(test)]
+impl FeedItem {
+    /// A "Story {id}" from `provider`, which doubles as its source, published now
+    pub(crate) fn test(id: &str, provider: &str) -> Self {
+        Self::new(
+            id.to_string(),
+            provider.to_string(),
+            format!("Story {}", id),
+            provider.to_string(),
+            Utc::now(),
+        )
+    }
+
+    pub(crate) fn with_title(mut self, title: &str) -> Self {
+        self.title = title.to_string();
+        self
+    }
+
+    /// Move the publication time back by `age`
+    pub(crate) fn with_age(mut self, age: chrono::Duration) -> Self {
+        self.published_at = Utc::now() - age;
+        self
+    }
+
+    pub(crate) fn with_score(mut self, score: i32) -> Self {
+        self.metadata.score = Some(score);
+        self
+    }
+
+    pub(crate) fn with_comments(mut self, comments: i32) -> Self {
+        self.metadata.comments = Some(comments);
+        self
+    }
+
+    pub(crate) fn with_tags(mut self, tags: &[&str]) -> Self {
+        self.metadata.tags = tags.iter().map(|t| t.to_string()).collect();
+        self
+    }
+
+    /// Mark the item as matched by `rules`, notifying when there are any
+    pub(crate) fn with_rules(mut self, rules: &[&str]) -> Self {
+        self.metadata.matched_rules = rules.iter().map(|r| r.to_string()).collect();
+        self.metadata.notify = !rules.is_empty();
+        self
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2,9 +2,13 @@ pub mod cache;
 pub mod feed_item;
 pub mod hn;
 pub mod news;
+pub mod sort;
 
 pub use cache::*;
-pub use feed_item::{Comment, FeedItem, FeedItemMetadata, LinkPreview, Sentiment, SentimentLabel};
+pub use feed_item::{
+    Comment, FeedItem, FeedItemMetadata, LinkPreview, PostType, Sentiment, SentimentLabel,
+};
 pub use hn::*;
+pub use sort::{GroupBy, SortMode};
 // Note: news module has its own Sentiment - use feed_item version for new code
 pub use news::NewsArticle;
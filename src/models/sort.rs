@@ -0,0 +1,249 @@
+use crate::models::FeedItem;
+use chrono::{DateTime, Local, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+/// HN ranking exponent: higher values make items fall off faster
+const GRAVITY: f64 = 1.8;
+
+/// Feed list ordering
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortMode {
+    /// Order the provider returned (e.g. HN front-page rank)
+    #[default]
+    Feed,
+    Newest,
+    Score,
+    Comments,
+    /// HN-style ranking: score decayed by age
+    Gravity,
+    /// Alphabetical by source, newest first within a source
+    Source,
+}
+
+impl SortMode {
+    /// Next mode in the cycle
+    pub fn next(self) -> Self {
+        match self {
+            SortMode::Feed => SortMode::Newest,
+            SortMode::Newest => SortMode::Score,
+            SortMode::Score => SortMode::Comments,
+            SortMode::Comments => SortMode::Gravity,
+            SortMode::Gravity => SortMode::Source,
+            SortMode::Source => SortMode::Feed,
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            SortMode::Feed => "Feed order",
+            SortMode::Newest => "Newest",
+            SortMode::Score => "Score",
+            SortMode::Comments => "Comments",
+            SortMode::Gravity => "Ranked",
+            SortMode::Source => "Source",
+        }
+    }
+
+    /// Sort items in place; pinned items always stay on top
+    pub fn sort(self, items: &mut [FeedItem]) {
+        let now = Utc::now();
+        match self {
+            SortMode::Feed => {}
+            SortMode::Newest => items.sort_by_key(|i| Reverse(i.published_at)),
+            SortMode::Score => {
+                items.sort_by_key(|i| (Reverse(i.metadata.score), Reverse(i.published_at)))
+            }
+            SortMode::Comments => {
+                items.sort_by_key(|i| (Reverse(i.metadata.comments), Reverse(i.published_at)))
+            }
+            SortMode::Gravity => items.sort_by(|a, b| {
+                gravity_score(b, now)
+                    .total_cmp(&gravity_score(a, now))
+                    .then(b.published_at.cmp(&a.published_at))
+            }),
+            SortMode::Source => items.sort_by(|a, b| {
+                a.source
+                    .to_lowercase()
+                    .cmp(&b.source.to_lowercase())
+                    .then(b.published_at.cmp(&a.published_at))
+            }),
+        }
+        items.sort_by_key(|i| !i.metadata.pinned);
+    }
+}
+
+/// HN front-page formula: (points - 1) / (age_hours + 2)^1.8
+pub fn gravity_score(item: &FeedItem, now: DateTime<Utc>) -> f64 {
+    let points = item.metadata.score.unwrap_or(0).max(1) as f64;
+    let age_hours = (now - item.published_at).num_minutes().max(0) as f64 / 60.0;
+    (points - 1.0) / (age_hours + 2.0).powf(GRAVITY)
+}
+
+/// Optional section headers in the feed list
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GroupBy {
+    #[default]
+    None,
+    Provider,
+    Day,
+}
+
+impl GroupBy {
+    /// Next grouping in the cycle
+    pub fn next(self) -> Self {
+        match self {
+            GroupBy::None => GroupBy::Provider,
+            GroupBy::Provider => GroupBy::Day,
+            GroupBy::Day => GroupBy::None,
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            GroupBy::None => "None",
+            GroupBy::Provider => "Provider",
+            GroupBy::Day => "Day",
+        }
+    }
+
+    /// Header text for the group an item belongs to
+    pub fn label(&self, item: &FeedItem) -> Option<String> {
+        match self {
+            GroupBy::None => None,
+            GroupBy::Provider => Some(item.provider_id.clone()),
+            GroupBy::Day => Some(day_label(local_day(item), Local::now().date_naive())),
+        }
+    }
+
+    /// Make groups contiguous, keeping the existing order inside each group
+    ///
+    /// Providers appear in the order they first occur; days run newest first.
+    pub fn group(&self, items: &mut [FeedItem]) {
+        match self {
+            GroupBy::None => {}
+            GroupBy::Provider => {
+                let mut first_seen: HashMap<String, usize> = HashMap::new();
+                for item in items.iter() {
+                    let next = first_seen.len();
+                    first_seen.entry(item.provider_id.clone()).or_insert(next);
+                }
+                items.sort_by_key(|i| first_seen[&i.provider_id]);
+            }
+            GroupBy::Day => items.sort_by_key(|i| Reverse(local_day(i))),
+        }
+    }
+}
+
+fn local_day(item: &FeedItem) -> NaiveDate {
+    item.published_at.with_timezone(&Local).date_naive()
+}
+
+fn day_label(day: NaiveDate, today: NaiveDate) -> String {
+    match (today - day).num_days() {
+        0 => "Today".to_string(),
+        1 => "Yesterday".to_string(),
+        _ => day.format("%a %d %b %Y").to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn ids(items: &[FeedItem]) -> Vec<&str> {
+        items.iter().map(|i| i.id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_sort_modes() {
+        let mut items = vec![
+            FeedItem::test("old-popular", "hackernews")
+                .with_age(Duration::hours(48))
+                .with_score(900)
+                .with_comments(10),
+            FeedItem::test("fresh", "reddit")
+                .with_age(Duration::hours(1))
+                .with_score(40)
+                .with_comments(300),
+            FeedItem::test("middle", "finnhub")
+                .with_age(Duration::hours(6))
+                .with_score(200)
+                .with_comments(50),
+        ];
+
+        SortMode::Newest.sort(&mut items);
+        assert_eq!(ids(&items), vec!["fresh", "middle", "old-popular"]);
+
+        SortMode::Score.sort(&mut items);
+        assert_eq!(ids(&items), vec!["old-popular", "middle", "fresh"]);
+
+        SortMode::Comments.sort(&mut items);
+        assert_eq!(ids(&items), vec!["fresh", "middle", "old-popular"]);
+
+        // 900 points two days old ranks below fresher, smaller stories
+        SortMode::Gravity.sort(&mut items);
+        assert_eq!(ids(&items), vec!["fresh", "middle", "old-popular"]);
+
+        SortMode::Source.sort(&mut items);
+        assert_eq!(ids(&items), vec!["middle", "old-popular", "fresh"]);
+    }
+
+    #[test]
+    fn test_pinned_stay_on_top() {
+        let mut items = vec![
+            FeedItem::test("a", "hackernews")
+                .with_age(Duration::hours(1))
+                .with_score(10)
+                .with_comments(0),
+            FeedItem::test("b", "reddit")
+                .with_age(Duration::hours(5))
+                .with_score(1)
+                .with_comments(0),
+        ];
+        items[1].metadata.pinned = true;
+
+        SortMode::Score.sort(&mut items);
+        assert_eq!(ids(&items), vec!["b", "a"]);
+    }
+
+    #[test]
+    fn test_group_by_provider_keeps_order() {
+        let mut items = vec![
+            FeedItem::test("hn1", "hackernews")
+                .with_age(Duration::hours(1))
+                .with_score(0)
+                .with_comments(0),
+            FeedItem::test("r1", "reddit")
+                .with_age(Duration::hours(2))
+                .with_score(0)
+                .with_comments(0),
+            FeedItem::test("hn2", "hackernews")
+                .with_age(Duration::hours(3))
+                .with_score(0)
+                .with_comments(0),
+        ];
+
+        GroupBy::Provider.group(&mut items);
+        assert_eq!(ids(&items), vec!["hn1", "hn2", "r1"]);
+        assert_eq!(
+            GroupBy::Provider.label(&items[2]).as_deref(),
+            Some("reddit")
+        );
+    }
+
+    #[test]
+    fn test_day_labels() {
+        let today = NaiveDate::from_ymd_opt(2026, 3, 10).unwrap();
+        assert_eq!(day_label(today, today), "Today");
+        assert_eq!(day_label(today.pred_opt().unwrap(), today), "Yesterday");
+        assert_eq!(
+            day_label(NaiveDate::from_ymd_opt(2026, 3, 2).unwrap(), today),
+            "Mon 02 Mar 2026"
+        );
+    }
+}
@@ -0,0 +1,360 @@
+//! Terminal notifications
+//!
+//! Raises a desktop notification (OSC 9 or OSC 777 escape) or rings the bell
+//! when a refresh brings in items flagged by `notify` rules, matching a
+//! configured keyword, or scoring above a threshold. Items that fired are
+//! remembered in the cache database, so a restart does not announce them
+//! again, and kept in a log for the notification view.
+
+use crate::cache::CacheManager;
+use crate::config::NotificationsConfig;
+use crate::models::FeedItem;
+use crate::utils::terminal;
+use chrono::{DateTime, Duration, Utc};
+use regex::{Regex, RegexBuilder};
+use std::collections::{HashSet, VecDeque};
+use std::str::FromStr;
+use thiserror::Error;
+
+/// Notifications kept for the log view
+const LOG_CAPACITY: usize = 200;
+
+/// Longest notification body sent to the terminal
+const MAX_BODY_CHARS: usize = 200;
+
+/// Tree in the cache database holding notified `provider\x1fid` keys
+const SEEN_TREE: &str = "notifications:seen";
+
+/// How long notified items are remembered across sessions
+const SEEN_RETENTION_DAYS: i64 = 30;
+
+#[derive(Error, Debug)]
+pub enum NotificationError {
+    #[error("unknown notification method '{0}' (use osc9, osc777, bell or none)")]
+    UnknownMethod(String),
+
+    #[error("invalid notification keyword '{pattern}': {source}")]
+    Pattern {
+        pattern: String,
+        source: regex::Error,
+    },
+}
+
+/// How a notification reaches the user outside finterm
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotifyMethod {
+    /// `ESC ] 9 ; body BEL` (iTerm2, kitty, WezTerm, Windows Terminal)
+    Osc9,
+    /// `ESC ] 777 ; notify ; title ; body BEL` (foot, urxvt, some VTE builds)
+    Osc777,
+    /// Plain terminal bell
+    Bell,
+    /// Log and status line only
+    None,
+}
+
+impl FromStr for NotifyMethod {
+    type Err = NotificationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "osc9" => Ok(Self::Osc9),
+            "osc777" => Ok(Self::Osc777),
+            "bell" => Ok(Self::Bell),
+            "none" | "off" => Ok(Self::None),
+            other => Err(NotificationError::UnknownMethod(other.to_string())),
+        }
+    }
+}
+
+/// An item that fired a notification
+#[derive(Debug, Clone)]
+pub struct Notification {
+    pub at: DateTime<Utc>,
+    pub item: FeedItem,
+    /// What matched: rule names, a keyword or the score threshold
+    pub reason: String,
+}
+
+/// Decides which fetched items notify and remembers what already has
+#[derive(Debug)]
+pub struct Notifier {
+    method: NotifyMethod,
+    bell: bool,
+    keywords: Vec<Regex>,
+    min_score: Option<i32>,
+    /// Inside tmux, escapes are wrapped for passthrough
+    tmux: bool,
+    seen: HashSet<(String, String)>,
+    /// Notified items from earlier sessions, when the cache is available
+    store: Option<sled::Tree>,
+    log: VecDeque<Notification>,
+}
+
+impl Notifier {
+    /// Build from config; keywords are case-insensitive regexes
+    pub fn from_config(config: &NotificationsConfig) -> Result<Self, NotificationError> {
+        let keywords = config
+            .keywords
+            .iter()
+            .map(|pattern| {
+                RegexBuilder::new(pattern)
+                    .case_insensitive(true)
+                    .build()
+                    .map_err(|source| NotificationError::Pattern {
+                        pattern: pattern.clone(),
+                        source,
+                    })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            method: config.method.parse()?,
+            bell: config.bell,
+            keywords,
+            min_score: config.min_score,
+            tmux: terminal::in_tmux(),
+            seen: HashSet::new(),
+            store: None,
+            log: VecDeque::new(),
+        })
+    }
+
+    /// Builder method: remember notified items in the cache so they don't
+    /// fire again next session. Without it only this session is remembered.
+    pub fn with_cache(mut self, cache: &CacheManager) -> Self {
+        match cache.open_tree(SEEN_TREE) {
+            Ok(tree) => {
+                prune(&tree, Utc::now() - Duration::days(SEEN_RETENTION_DAYS));
+                self.store = Some(tree);
+            }
+            Err(e) => tracing::warn!("Notified items will not be remembered: {}", e),
+        }
+        self
+    }
+
+    /// Mark an item notified; false if it already was, here or in an earlier session
+    fn mark_seen(&mut self, item: &FeedItem) -> bool {
+        if !self
+            .seen
+            .insert((item.provider_id.clone(), item.id.clone()))
+        {
+            return false;
+        }
+        let Some(store) = &self.store else {
+            return true;
+        };
+
+        let key = format!("{}\u{1f}{}", item.provider_id, item.id);
+        let now = Utc::now().timestamp().to_be_bytes();
+        match store.compare_and_swap(key.as_bytes(), None as Option<&[u8]>, Some(&now[..])) {
+            Ok(Ok(())) => true,
+            Ok(Err(_)) => false,
+            Err(e) => {
+                tracing::warn!("Notification store unavailable: {}", e);
+                true
+            }
+        }
+    }
+
+    /// Why an item should notify, if it should
+    fn reason(&self, item: &FeedItem) -> Option<String> {
+        if item.metadata.notify {
+            return Some(item.metadata.matched_rules.join(", "));
+        }
+        if let Some(re) = self.keywords.iter().find(|re| re.is_match(&item.title)) {
+            return Some(format!("keyword {}", re.as_str()));
+        }
+        match (self.min_score, item.metadata.score) {
+            (Some(min), Some(score)) if score >= min => Some(format!("score ≥ {}", min)),
+            _ => None,
+        }
+    }
+
+    /// Notifications for items not seen before, newest in the log first
+    pub fn check(&mut self, items: &[FeedItem]) -> Vec<Notification> {
+        let now = Utc::now();
+        let mut fired = Vec::new();
+
+        for item in items {
+            let Some(reason) = self.reason(item) else {
+                continue;
+            };
+            if !self.mark_seen(item) {
+                continue;
+            }
+            fired.push(Notification {
+                at: now,
+                item: item.clone(),
+                reason,
+            });
+        }
+
+        for notification in &fired {
+            self.log.push_front(notification.clone());
+        }
+        self.log.truncate(LOG_CAPACITY);
+        fired
+    }
+
+    /// Escape sequence announcing a batch, or `None` when nothing should be written
+    pub fn escape_sequence(&self, fired: &[Notification]) -> Option<String> {
+        let first = fired.first()?;
+        let mut body = first.item.title.clone();
+        if fired.len() > 1 {
+            body.push_str(&format!(" (+{} more)", fired.len() - 1));
+        }
+        let body = sanitize(&body);
+
+        let mut out = match self.method {
+            NotifyMethod::Osc9 => self.wrap(&format!("\x1b]9;{}\x07", body)),
+            NotifyMethod::Osc777 => self.wrap(&format!(
+                "\x1b]777;notify;finterm: {};{}\x07",
+                sanitize(&first.reason),
+                body
+            )),
+            NotifyMethod::Bell => "\x07".to_string(),
+            NotifyMethod::None => String::new(),
+        };
+        if self.bell && self.method != NotifyMethod::Bell {
+            out.push('\x07');
+        }
+
+        (!out.is_empty()).then_some(out)
+    }
+
+    /// Wrap an OSC sequence in a tmux DCS passthrough when running under tmux
+    fn wrap(&self, sequence: &str) -> String {
+        if self.tmux {
+            terminal::tmux_passthrough(sequence)
+        } else {
+            sequence.to_string()
+        }
+    }
+
+    /// Everything that fired, most recent first
+    pub fn log(&self) -> &VecDeque<Notification> {
+        &self.log
+    }
+}
+
+/// Drop notified keys recorded before `cutoff`
+fn prune(tree: &sled::Tree, cutoff: DateTime<Utc>) {
+    let cutoff = cutoff.timestamp();
+    for (key, value) in tree.iter().flatten() {
+        let notified_at = value
+            .as_ref()
+            .try_into()
+            .map(i64::from_be_bytes)
+            .unwrap_or(0);
+        if notified_at < cutoff {
+            let _ = tree.remove(key);
+        }
+    }
+}
+
+/// Text safe inside an OSC string: no control characters or field separators
+fn sanitize(text: &str) -> String {
+    text.chars()
+        .filter(|c| !c.is_control())
+        .map(|c| if c == ';' { ',' } else { c })
+        .take(MAX_BODY_CHARS)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_and_escape() {
+        let config = NotificationsConfig {
+            method: "osc777".to_string(),
+            bell: true,
+            keywords: vec![r"\bfed\b".to_string()],
+            min_score: Some(500),
+        };
+        let mut notifier = Notifier::from_config(&config).unwrap();
+        notifier.tmux = false;
+
+        let mut ruled = FeedItem::test("1", "finnhub")
+            .with_title("Quiet story")
+            .with_score(10);
+        ruled.metadata.notify = true;
+        ruled.metadata.matched_rules = vec!["watchlist".to_string()];
+        let items = vec![
+            ruled,
+            FeedItem::test("2", "finnhub")
+                .with_title("Fed holds rates; markets rally")
+                .with_score(3),
+            FeedItem::test("3", "finnhub")
+                .with_title("Popular")
+                .with_score(900),
+            FeedItem::test("4", "finnhub")
+                .with_title("Federal filing")
+                .with_score(10),
+        ];
+
+        let fired = notifier.check(&items);
+        let reasons: Vec<&str> = fired.iter().map(|n| n.reason.as_str()).collect();
+        assert_eq!(
+            reasons,
+            vec!["watchlist", r"keyword \bfed\b", "score ≥ 500"]
+        );
+        assert_eq!(notifier.log().front().unwrap().item.id, "3");
+
+        let escape = notifier.escape_sequence(&fired).unwrap();
+        assert_eq!(
+            escape,
+            "\x1b]777;notify;finterm: watchlist;Quiet story (+2 more)\x07\x07"
+        );
+
+        // Already seen: nothing new fires
+        assert!(notifier.check(&items).is_empty());
+        assert!(notifier.escape_sequence(&[]).is_none());
+
+        notifier.method = NotifyMethod::Osc9;
+        notifier.tmux = true;
+        let escape = notifier.escape_sequence(&fired[1..2]).unwrap();
+        assert_eq!(
+            escape,
+            "\x1bPtmux;\x1b\x1b]9;Fed holds rates, markets rally\x07\x1b\\\x07"
+        );
+
+        assert!("beep".parse::<NotifyMethod>().is_err());
+    }
+
+    #[test]
+    fn test_seen_items_persist_across_sessions() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = CacheManager::new(dir.path().to_path_buf(), 10).unwrap();
+        let config = NotificationsConfig {
+            method: "none".to_string(),
+            bell: false,
+            keywords: vec!["fed".to_string()],
+            min_score: None,
+        };
+        let items = vec![FeedItem::test("1", "finnhub")
+            .with_title("Fed holds rates")
+            .with_score(3)];
+
+        let mut first = Notifier::from_config(&config).unwrap().with_cache(&cache);
+        assert_eq!(first.check(&items).len(), 1);
+
+        // A new session on the same cache stays quiet about the same item
+        let mut second = Notifier::from_config(&config).unwrap().with_cache(&cache);
+        assert!(second.check(&items).is_empty());
+        assert_eq!(
+            second
+                .check(&[FeedItem::test("2", "finnhub")
+                    .with_title("Fed cuts")
+                    .with_score(3)])
+                .len(),
+            1
+        );
+
+        // Without the cache only the session itself is remembered
+        let mut forgetful = Notifier::from_config(&config).unwrap();
+        assert_eq!(forgetful.check(&items).len(), 1);
+    }
+}
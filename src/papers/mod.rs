@@ -0,0 +1,550 @@
+//! Papers
+//!
+//! Actions for arXiv items: downloading the PDF under the papers directory,
+//! BibTeX entries built from the feed metadata, and a reading queue kept in
+//! its own database, apart from bookmarks.
+
+use crate::models::FeedItem;
+use chrono::{DateTime, Datelike, Utc};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum PaperError {
+    #[error("Database error: {0}")]
+    Database(#[from] sled::Error),
+
+    #[error("Serialization error: {0}")]
+    Serialization(String),
+
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Download failed: {0}")]
+    Download(String),
+
+    #[error("Not an arXiv paper")]
+    NotAPaper,
+}
+
+pub type Result<T> = std::result::Result<T, PaperError>;
+
+/// Longest title kept in a PDF filename
+const MAX_TITLE_CHARS: usize = 80;
+
+/// Words skipped when picking the title word of a citation key
+const KEY_STOPWORDS: &[&str] = &[
+    "a", "an", "the", "on", "of", "for", "in", "to", "and", "with", "from", "towards", "toward",
+];
+
+/// arXiv identifier of a paper, without version
+pub fn arxiv_id(item: &FeedItem) -> Option<&str> {
+    item.metadata.arxiv_id.as_deref()
+}
+
+/// Where a paper is in the reading queue
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ReadingState {
+    ToRead,
+    Reading,
+    Done,
+}
+
+impl ReadingState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::ToRead => "to read",
+            Self::Reading => "reading",
+            Self::Done => "done",
+        }
+    }
+
+    /// Next state when cycling; done papers go back to the pile
+    pub fn next(&self) -> Self {
+        match self {
+            Self::ToRead => Self::Reading,
+            Self::Reading => Self::Done,
+            Self::Done => Self::ToRead,
+        }
+    }
+}
+
+/// A queued paper
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueueEntry {
+    pub item: FeedItem,
+    pub state: ReadingState,
+    pub added_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Persistent reading queue of arXiv papers, keyed by arXiv id
+pub struct ReadingQueue {
+    db: sled::Db,
+}
+
+impl ReadingQueue {
+    /// Open (or create) the reading queue database in `dir`
+    pub fn open(dir: &Path) -> Result<Self> {
+        Ok(Self {
+            db: sled::open(dir.join("reading-queue"))?,
+        })
+    }
+
+    /// Queue a paper as to-read, or move a queued one on to its next state;
+    /// returns the state it is now in
+    pub fn advance(&self, item: &FeedItem) -> Result<ReadingState> {
+        let key = key(item)?;
+        let now = Utc::now();
+        let entry = match self.get(item)? {
+            Some(mut entry) => {
+                entry.state = entry.state.next();
+                entry.updated_at = now;
+                entry
+            }
+            None => QueueEntry {
+                item: item.clone(),
+                state: ReadingState::ToRead,
+                added_at: now,
+                updated_at: now,
+            },
+        };
+
+        let bytes =
+            serde_json::to_vec(&entry).map_err(|e| PaperError::Serialization(e.to_string()))?;
+        self.db.insert(key, bytes)?;
+        self.db.flush()?;
+        Ok(entry.state)
+    }
+
+    /// Take a paper off the queue; returns whether it was queued
+    pub fn remove(&self, item: &FeedItem) -> Result<bool> {
+        let removed = self.db.remove(key(item)?)?.is_some();
+        self.db.flush()?;
+        Ok(removed)
+    }
+
+    /// The queue entry for a paper, if queued
+    pub fn get(&self, item: &FeedItem) -> Result<Option<QueueEntry>> {
+        match self.db.get(key(item)?)? {
+            Some(bytes) => serde_json::from_slice(&bytes)
+                .map(Some)
+                .map_err(|e| PaperError::Serialization(e.to_string())),
+            None => Ok(None),
+        }
+    }
+
+    /// All queued papers: to read (oldest first), then reading, then done
+    pub fn list(&self) -> Result<Vec<QueueEntry>> {
+        let mut entries = self
+            .db
+            .iter()
+            .values()
+            .map(|bytes| {
+                serde_json::from_slice::<QueueEntry>(&bytes?)
+                    .map_err(|e| PaperError::Serialization(e.to_string()))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        entries.sort_by_key(|e| (e.state, e.added_at));
+        Ok(entries)
+    }
+
+    pub fn len(&self) -> usize {
+        self.db.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.db.is_empty()
+    }
+}
+
+/// Papers are keyed by arXiv id so search results and listings share entries
+fn key(item: &FeedItem) -> Result<Vec<u8>> {
+    arxiv_id(item)
+        .map(|id| format!("arxiv:{}", id).into_bytes())
+        .ok_or(PaperError::NotAPaper)
+}
+
+/// Citation key in the usual `surnameYEARword` form, e.g. `lovelace2024order`
+pub fn bibtex_key(item: &FeedItem) -> Option<String> {
+    arxiv_id(item)?;
+
+    let surname: String = item
+        .metadata
+        .authors
+        .first()
+        .map(|name| surname(name))
+        .unwrap_or_default()
+        .chars()
+        .filter(char::is_ascii_alphanumeric)
+        .collect::<String>()
+        .to_lowercase();
+    let word = item
+        .title
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .map(str::to_lowercase)
+        .find(|w| w.len() > 2 && !KEY_STOPWORDS.contains(&w.as_str()))
+        .unwrap_or_default();
+
+    let surname = if surname.is_empty() {
+        "anon".to_string()
+    } else {
+        surname
+    };
+    Some(format!("{}{}{}", surname, item.published_at.year(), word))
+}
+
+/// BibTeX `@misc` entry for a paper, as arXiv recommends for preprints
+pub fn bibtex(item: &FeedItem) -> Option<String> {
+    bibtex_with_key(item, &bibtex_key(item)?)
+}
+
+fn bibtex_with_key(item: &FeedItem, key: &str) -> Option<String> {
+    let id = arxiv_id(item)?;
+
+    let authors = item
+        .metadata
+        .authors
+        .iter()
+        .map(|name| bibtex_name(name))
+        .collect::<Vec<_>>()
+        .join(" and ");
+    let primary = item
+        .metadata
+        .tags
+        .iter()
+        .find(|t| t.contains('.') || t.contains('-'))
+        .cloned();
+
+    let mut fields = vec![("title", escape(&item.title))];
+    if !authors.is_empty() {
+        fields.push(("author", escape(&authors)));
+    }
+    fields.push(("year", item.published_at.year().to_string()));
+    fields.push(("eprint", id.to_string()));
+    fields.push(("archivePrefix", "arXiv".to_string()));
+    if let Some(primary) = primary {
+        fields.push(("primaryClass", primary));
+    }
+    fields.push(("url", format!("https://arxiv.org/abs/{}", id)));
+
+    let body = fields
+        .iter()
+        .map(|(name, value)| format!("  {} = {{{}}}", name, value))
+        .collect::<Vec<_>>()
+        .join(",\n");
+    Some(format!("@misc{{{},\n{}\n}}\n", key, body))
+}
+
+/// Append a paper's entry to a `.bib` file, creating it if needed. Returns
+/// the citation key used, or `None` when the paper is already in the file.
+/// A key taken by another paper gets a suffix (`lovelace2024order_b`).
+pub fn append_bibtex(path: &Path, item: &FeedItem) -> Result<Option<String>> {
+    let (Some(id), Some(key)) = (arxiv_id(item), bibtex_key(item)) else {
+        return Err(PaperError::NotAPaper);
+    };
+
+    let existing = match std::fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(e.into()),
+    };
+    if has_eprint(&existing, id) {
+        return Ok(None);
+    }
+
+    let taken = |key: &str| existing.contains(&format!("{{{},", key));
+    let key = std::iter::once(key.clone())
+        .chain(('b'..='z').map(|suffix| format!("{}_{}", key, suffix)))
+        .find(|candidate| !taken(candidate))
+        .ok_or_else(|| PaperError::Serialization(format!("no free citation key for {}", key)))?;
+    let entry = bibtex_with_key(item, &key).ok_or(PaperError::NotAPaper)?;
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let separator = match existing.as_str() {
+        "" => "",
+        text if text.ends_with("\n\n") => "",
+        text if text.ends_with('\n') => "\n",
+        _ => "\n\n",
+    };
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+    file.write_all(format!("{}{}", separator, entry).as_bytes())?;
+    Ok(Some(key))
+}
+
+/// Whether a `.bib` text already cites an arXiv id, however the field is spaced or quoted
+fn has_eprint(bib: &str, id: &str) -> bool {
+    bib.lines().any(|line| {
+        let field: String = line.chars().filter(|c| !c.is_whitespace()).collect();
+        let Some(value) = field
+            .to_lowercase()
+            .strip_prefix("eprint=")
+            .map(str::to_string)
+        else {
+            return false;
+        };
+        value.trim_end_matches(',').trim_matches(['{', '}', '"']) == id.to_lowercase()
+    })
+}
+
+/// Filename for a paper's PDF, e.g.
+/// `Lovelace et al. 2024 - Order Flow and Price Impact (2401.00001v2).pdf`
+pub fn pdf_filename(item: &FeedItem) -> Option<String> {
+    let id = arxiv_id(item)?.replace('/', "_");
+    let version = item
+        .metadata
+        .arxiv_version
+        .map(|v| format!("v{}", v))
+        .unwrap_or_default();
+
+    let authors = &item.metadata.authors;
+    let mut name = match authors.first() {
+        Some(first) if authors.len() > 1 => format!("{} et al. ", surname(first)),
+        Some(first) => format!("{} ", surname(first)),
+        None => String::new(),
+    };
+    name.push_str(&format!("{} - ", item.published_at.year()));
+
+    let title: String = item.title.chars().take(MAX_TITLE_CHARS).collect();
+    name.push_str(title.trim_end());
+    name.push_str(&format!(" ({}{})", id, version));
+
+    let clean = name
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => ' ',
+            c if c.is_control() => ' ',
+            c => c,
+        })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    Some(format!("{}.pdf", clean))
+}
+
+/// Download a paper's PDF into `dir`; an existing copy is kept as is
+pub async fn download_pdf(item: &FeedItem, dir: &Path) -> Result<PathBuf> {
+    let filename = pdf_filename(item).ok_or(PaperError::NotAPaper)?;
+    let path = dir.join(filename);
+    if path.exists() {
+        return Ok(path);
+    }
+
+    let url = match &item.metadata.pdf_url {
+        Some(url) => url.clone(),
+        None => format!(
+            "https://arxiv.org/pdf/{}",
+            arxiv_id(item).unwrap_or_default()
+        ),
+    };
+    let client = Client::builder()
+        .connect_timeout(Duration::from_secs(10))
+        .timeout(Duration::from_secs(300))
+        .user_agent("FinTerm/0.3.0 (https://github.com/kj114022/finterm)")
+        .build()
+        .map_err(|e| PaperError::Download(e.to_string()))?;
+    let mut response = client
+        .get(&url)
+        .send()
+        .await
+        .and_then(|r| r.error_for_status())
+        .map_err(|e| PaperError::Download(e.to_string()))?;
+
+    std::fs::create_dir_all(dir)?;
+    // Written under a temporary name so an interrupted download is not kept
+    let partial = path.with_extension("pdf.part");
+    let mut file = std::fs::File::create(&partial)?;
+    let mut first = true;
+    loop {
+        let chunk = match response.chunk().await {
+            Ok(Some(chunk)) => chunk,
+            Ok(None) => break,
+            Err(e) => {
+                let _ = std::fs::remove_file(&partial);
+                return Err(PaperError::Download(e.to_string()));
+            }
+        };
+        // arXiv answers with an HTML page while a PDF is still being built
+        if first && !chunk.starts_with(b"%PDF") {
+            let _ = std::fs::remove_file(&partial);
+            return Err(PaperError::Download(format!(
+                "{} did not return a PDF",
+                url
+            )));
+        }
+        first = false;
+        file.write_all(&chunk)?;
+    }
+    file.flush()?;
+    drop(file);
+    std::fs::rename(&partial, &path)?;
+
+    Ok(path)
+}
+
+/// Family name from a "Given Family" name (or one already in "Family, Given" form)
+fn surname(name: &str) -> &str {
+    match name.split_once(',') {
+        Some((family, _)) => family.trim(),
+        None => name.split_whitespace().last().unwrap_or(name),
+    }
+}
+
+/// "Ada Lovelace" as BibTeX's "Lovelace, Ada"
+fn bibtex_name(name: &str) -> String {
+    let name = name.trim();
+    if name.contains(',') {
+        return name.to_string();
+    }
+    match name.rsplit_once(char::is_whitespace) {
+        Some((given, family)) => format!("{}, {}", family, given.trim()),
+        None => name.to_string(),
+    }
+}
+
+/// Escape characters that are special to BibTeX/LaTeX
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '&' | '%' | '$' | '#' | '_') {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use tempfile::tempdir;
+
+    fn paper(id: &str, title: &str) -> FeedItem {
+        let mut item = FeedItem::new(
+            id.to_string(),
+            "arxiv".to_string(),
+            title.to_string(),
+            "arXiv:Trading and Market Microstructure".to_string(),
+            Utc.with_ymd_and_hms(2024, 1, 2, 0, 0, 0).unwrap(),
+        );
+        item.metadata.arxiv_id = Some(id.to_string());
+        item.metadata.arxiv_version = Some(2);
+        item.metadata.authors = vec!["Ada Lovelace".to_string(), "Charles Babbage".to_string()];
+        item.metadata.tags = vec!["q-fin.TR".to_string(), "paper".to_string()];
+        item
+    }
+
+    #[test]
+    fn test_queue_states() {
+        let dir = tempdir().unwrap();
+        let queue = ReadingQueue::open(dir.path()).unwrap();
+        let first = paper("2401.00001", "First");
+        let second = paper("2401.00002", "Second");
+
+        assert_eq!(queue.advance(&first).unwrap(), ReadingState::ToRead);
+        assert_eq!(queue.advance(&second).unwrap(), ReadingState::ToRead);
+        assert_eq!(queue.advance(&first).unwrap(), ReadingState::Reading);
+
+        let listed: Vec<_> = queue
+            .list()
+            .unwrap()
+            .into_iter()
+            .map(|e| (e.item.id, e.state))
+            .collect();
+        assert_eq!(
+            listed,
+            vec![
+                ("2401.00002".to_string(), ReadingState::ToRead),
+                ("2401.00001".to_string(), ReadingState::Reading),
+            ]
+        );
+
+        assert_eq!(queue.advance(&first).unwrap(), ReadingState::Done);
+        assert!(queue.remove(&first).unwrap());
+        assert!(queue.get(&first).unwrap().is_none());
+        assert_eq!(queue.len(), 1);
+
+        // Only papers can be queued
+        let mut story = paper("1", "Story");
+        story.metadata.arxiv_id = None;
+        assert!(matches!(queue.advance(&story), Err(PaperError::NotAPaper)));
+    }
+
+    #[test]
+    fn test_bibtex_and_filename() {
+        let item = paper(
+            "2401.00001",
+            "On Order Flow & Price Impact: 50% of the story",
+        );
+
+        assert_eq!(
+            bibtex(&item).unwrap(),
+            "@misc{lovelace2024order,\n  \
+             title = {On Order Flow \\& Price Impact: 50\\% of the story},\n  \
+             author = {Lovelace, Ada and Babbage, Charles},\n  \
+             year = {2024},\n  \
+             eprint = {2401.00001},\n  \
+             archivePrefix = {arXiv},\n  \
+             primaryClass = {q-fin.TR},\n  \
+             url = {https://arxiv.org/abs/2401.00001}\n}\n"
+        );
+        assert_eq!(
+            pdf_filename(&item).unwrap(),
+            "Lovelace et al. 2024 - On Order Flow & Price Impact 50% of the story (2401.00001v2).pdf"
+        );
+
+        let dir = tempdir().unwrap();
+        let bib = dir.path().join("refs").join("papers.bib");
+        assert_eq!(
+            append_bibtex(&bib, &item).unwrap().as_deref(),
+            Some("lovelace2024order")
+        );
+        assert_eq!(append_bibtex(&bib, &item).unwrap(), None);
+        assert_eq!(
+            append_bibtex(&bib, &paper("2401.00002", "Market Making"))
+                .unwrap()
+                .as_deref(),
+            Some("lovelace2024market")
+        );
+        let text = std::fs::read_to_string(&bib).unwrap();
+        assert_eq!(text.matches("@misc{").count(), 2);
+        assert!(text.contains("}\n\n@misc{lovelace2024market,"));
+    }
+
+    #[test]
+    fn test_append_bibtex_key_collision() {
+        let dir = tempdir().unwrap();
+        let bib = dir.path().join("papers.bib");
+        let flow = paper("2401.00001", "Order Flow and Price Impact");
+        let books = paper("2401.00003", "Order Books under Stress");
+        assert_eq!(bibtex_key(&flow), bibtex_key(&books));
+
+        assert_eq!(
+            append_bibtex(&bib, &flow).unwrap().as_deref(),
+            Some("lovelace2024order")
+        );
+        assert_eq!(
+            append_bibtex(&bib, &books).unwrap().as_deref(),
+            Some("lovelace2024order_b")
+        );
+        // Each paper is recognised by its eprint, not the shared key
+        assert_eq!(append_bibtex(&bib, &books).unwrap(), None);
+        assert_eq!(append_bibtex(&bib, &flow).unwrap(), None);
+
+        let text = std::fs::read_to_string(&bib).unwrap();
+        assert!(text.contains("@misc{lovelace2024order_b,\n  title = {Order Books under Stress}"));
+        assert!(text.contains("eprint = {2401.00003}"));
+    }
+}
@@ -1,92 +1,311 @@
 //! arXiv provider
 //!
-//! Fetches latest papers from arXiv RSS feeds
+//! Fetches latest papers from arXiv RSS feeds, and searches through the
+//! Atom export API
 
+use crate::config::ArxivConfig;
 use crate::models::{FeedItem, FeedItemMetadata};
 use crate::providers::{FeedProvider, ProviderError, ProviderStatus, Result};
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
-use quick_xml::events::Event;
+use quick_xml::events::{BytesStart, Event};
 use quick_xml::reader::Reader;
 use reqwest::Client;
 use std::time::Duration;
+use thiserror::Error;
 
 const ARXIV_RSS_BASE: &str = "https://rss.arxiv.org/rss";
+const ARXIV_API_BASE: &str = "https://export.arxiv.org/api/query";
 
-/// arXiv category for papers
-#[derive(Debug, Clone, Default)]
-pub enum ArxivCategory {
-    #[default]
-    CS, // Computer Science (all)
-    CSAI,    // Artificial Intelligence
-    CSLG,    // Machine Learning
-    CSCL,    // Computation & Language (NLP)
-    CSCV,    // Computer Vision
-    CSNE,    // Neural & Evolutionary Computing
-    Math,    // Mathematics
-    Physics, // Physics
-    Stat,    // Statistics
+/// Authors named in `FeedItem::author` before "et al."
+const MAX_LISTED_AUTHORS: usize = 3;
+
+/// An arXiv category such as `cs.AI` or `q-fin.TR`, or a whole archive such as `math`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArxivCategory(String);
+
+impl Default for ArxivCategory {
+    fn default() -> Self {
+        Self("cs.AI".to_string())
+    }
 }
 
 impl ArxivCategory {
-    pub fn as_rss_path(&self) -> &str {
+    /// Parse a category code or a short alias such as "ml" or "nlp".
+    /// Codes are case-normalised, so `q-fin.tr` becomes `q-fin.TR`.
+    pub fn parse(s: &str) -> Self {
+        let code = match s.trim().to_lowercase().as_str() {
+            "ai" => "cs.AI",
+            "lg" | "ml" | "machine learning" => "cs.LG",
+            "cl" | "nlp" => "cs.CL",
+            "cv" | "vision" => "cs.CV",
+            "ne" | "neural" => "cs.NE",
+            "mathematics" => "math",
+            "phys" => "physics",
+            "statistics" => "stat",
+            "finance" => "q-fin",
+            _ => return Self(normalize_code(s.trim())),
+        };
+        Self(code.to_string())
+    }
+
+    /// Code as used in feed URLs and queries
+    pub fn code(&self) -> &str {
+        &self.0
+    }
+
+    /// Friendly name for well-known categories, the code otherwise
+    pub fn display_name(&self) -> &str {
+        match self.0.as_str() {
+            "cs" => "Computer Science",
+            "cs.AI" => "AI",
+            "cs.LG" => "Machine Learning",
+            "cs.CL" => "NLP",
+            "cs.CV" => "Computer Vision",
+            "cs.NE" => "Neural Computing",
+            "math" => "Mathematics",
+            "physics" => "Physics",
+            "stat" => "Statistics",
+            code => code,
+        }
+    }
+}
+
+/// Archives are lower case; two-letter subject classes (`cs.AI`, `q-fin.TR`)
+/// are upper case and longer ones (`physics.comp-ph`) lower case
+fn normalize_code(code: &str) -> String {
+    match code.split_once('.') {
+        Some((archive, subject))
+            if subject.len() == 2 && subject.chars().all(|c| c.is_ascii_alphabetic()) =>
+        {
+            format!("{}.{}", archive.to_lowercase(), subject.to_uppercase())
+        }
+        _ => code.to_lowercase(),
+    }
+}
+
+/// Why a paper is in a day's RSS listing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnnounceType {
+    /// First announcement in its primary category
+    New,
+    /// Filed elsewhere and cross-listed to a followed category
+    Cross,
+    /// A new version of an earlier paper
+    Replace,
+    /// A new version of a cross-listed paper
+    ReplaceCross,
+}
+
+impl AnnounceType {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim() {
+            "new" => Some(AnnounceType::New),
+            "cross" => Some(AnnounceType::Cross),
+            "replace" => Some(AnnounceType::Replace),
+            "replace-cross" => Some(AnnounceType::ReplaceCross),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ArxivSearchError {
+    #[error("Nothing to search for")]
+    Empty,
+
+    #[error("Unknown sort '{0}' (use relevance, date or updated)")]
+    UnknownSort(String),
+}
+
+/// Field of a paper a search term is matched against
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArxivField {
+    All,
+    Author,
+    Title,
+    Abstract,
+    Category,
+}
+
+impl ArxivField {
+    /// Prefix the export API uses for the field
+    pub fn prefix(&self) -> &str {
         match self {
-            ArxivCategory::CS => "cs",
-            ArxivCategory::CSAI => "cs.AI",
-            ArxivCategory::CSLG => "cs.LG",
-            ArxivCategory::CSCL => "cs.CL",
-            ArxivCategory::CSCV => "cs.CV",
-            ArxivCategory::CSNE => "cs.NE",
-            ArxivCategory::Math => "math",
-            ArxivCategory::Physics => "physics",
-            ArxivCategory::Stat => "stat",
+            ArxivField::All => "all",
+            ArxivField::Author => "au",
+            ArxivField::Title => "ti",
+            ArxivField::Abstract => "abs",
+            ArxivField::Category => "cat",
         }
     }
 
-    pub fn display_name(&self) -> &str {
+    fn parse(key: &str) -> Option<Self> {
+        match key.to_lowercase().as_str() {
+            "all" => Some(ArxivField::All),
+            "au" | "author" | "by" => Some(ArxivField::Author),
+            "ti" | "title" => Some(ArxivField::Title),
+            "abs" | "abstract" => Some(ArxivField::Abstract),
+            "cat" | "category" => Some(ArxivField::Category),
+            _ => None,
+        }
+    }
+}
+
+/// Order of search results
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ArxivSort {
+    #[default]
+    Relevance,
+    /// Newest submissions first
+    Submitted,
+    /// Most recently revised first
+    Updated,
+}
+
+impl ArxivSort {
+    fn as_param(&self) -> &str {
         match self {
-            ArxivCategory::CS => "Computer Science",
-            ArxivCategory::CSAI => "AI",
-            ArxivCategory::CSLG => "Machine Learning",
-            ArxivCategory::CSCL => "NLP",
-            ArxivCategory::CSCV => "Computer Vision",
-            ArxivCategory::CSNE => "Neural Computing",
-            ArxivCategory::Math => "Mathematics",
-            ArxivCategory::Physics => "Physics",
-            ArxivCategory::Stat => "Statistics",
+            ArxivSort::Relevance => "relevance",
+            ArxivSort::Submitted => "submittedDate",
+            ArxivSort::Updated => "lastUpdatedDate",
         }
     }
+}
 
-    pub fn parse(s: &str) -> Self {
-        match s.to_lowercase().as_str() {
-            "cs.ai" | "ai" => ArxivCategory::CSAI,
-            "cs.lg" | "lg" | "ml" | "machine learning" => ArxivCategory::CSLG,
-            "cs.cl" | "cl" | "nlp" => ArxivCategory::CSCL,
-            "cs.cv" | "cv" | "vision" => ArxivCategory::CSCV,
-            "cs.ne" | "ne" | "neural" => ArxivCategory::CSNE,
-            "math" | "mathematics" => ArxivCategory::Math,
-            "physics" | "phys" => ArxivCategory::Physics,
-            "stat" | "statistics" => ArxivCategory::Stat,
-            _ => ArxivCategory::CS,
+/// An export API search, parsed from e.g.
+/// `au:hinton ti:"capsule networks" cat:cs.LG sort:date`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ArxivSearch {
+    /// Terms that must all match; quoted values are phrases
+    pub terms: Vec<(ArxivField, String)>,
+    pub sort: ArxivSort,
+}
+
+impl ArxivSearch {
+    /// Parse a query; words without a known `field:` prefix match anywhere
+    pub fn parse(input: &str) -> std::result::Result<Self, ArxivSearchError> {
+        let mut search = ArxivSearch::default();
+
+        for token in tokenize(input) {
+            let Some((key, value)) = token.split_once(':').filter(|(_, v)| !v.is_empty()) else {
+                search.terms.push((ArxivField::All, token));
+                continue;
+            };
+            if key.eq_ignore_ascii_case("sort") {
+                search.sort = match value.to_lowercase().as_str() {
+                    "date" | "new" | "newest" | "submitted" => ArxivSort::Submitted,
+                    "updated" => ArxivSort::Updated,
+                    "relevance" => ArxivSort::Relevance,
+                    other => return Err(ArxivSearchError::UnknownSort(other.to_string())),
+                };
+                continue;
+            }
+            match ArxivField::parse(key) {
+                Some(field) => search.terms.push((field, value.to_string())),
+                // Unknown keys (e.g. "C++:" or a URL) are just text
+                None => search.terms.push((ArxivField::All, token)),
+            }
+        }
+
+        if search.terms.is_empty() {
+            return Err(ArxivSearchError::Empty);
         }
+        Ok(search)
+    }
+
+    /// The API's `search_query`: every term AND'd
+    pub fn query(&self) -> String {
+        self.terms
+            .iter()
+            .map(|(field, value)| format!("{}:{}", field.prefix(), value))
+            .collect::<Vec<_>>()
+            .join(" AND ")
+    }
+
+    /// Query parameters for `max_results` results from `start`
+    fn params(&self, start: usize, max_results: usize) -> Vec<(&str, String)> {
+        vec![
+            ("search_query", self.query()),
+            ("start", start.to_string()),
+            ("max_results", max_results.to_string()),
+            ("sortBy", self.sort.as_param().to_string()),
+            ("sortOrder", "descending".to_string()),
+        ]
     }
 }
 
+/// Split on whitespace, keeping double-quoted phrases (quotes included) together
+fn tokenize(input: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+
+    for c in input.chars() {
+        match c {
+            '"' => {
+                quoted = !quoted;
+                current.push(c);
+            }
+            c if c.is_whitespace() && !quoted => {
+                if !current.is_empty() {
+                    tokens.push(std::mem::take(&mut current));
+                }
+            }
+            c => current.push(c),
+        }
+    }
+    if !current.is_empty() {
+        if quoted {
+            current.push('"');
+        }
+        tokens.push(current);
+    }
+    tokens
+}
+
 /// arXiv paper entry from RSS
 #[derive(Debug, Default)]
 struct ArxivEntry {
     title: String,
     link: String,
     description: String,
+    /// Comma-separated author list
     creator: String,
     pub_date: String,
+    /// `oai:arXiv.org:` followed by the versioned id
+    guid: String,
+    /// Primary category first
+    categories: Vec<String>,
+    announce_type: String,
+}
+
+/// arXiv paper entry from the Atom export API
+#[derive(Debug, Default)]
+struct ApiEntry {
+    /// Abstract page URL, ending in the versioned id
+    id: String,
+    title: String,
+    summary: String,
+    published: String,
+    authors: Vec<String>,
+    /// Primary category first
+    categories: Vec<String>,
+    abs_url: Option<String>,
+    pdf_url: Option<String>,
 }
 
 /// arXiv provider
 pub struct ArxivProvider {
     client: Client,
-    category: ArxivCategory,
+    /// Followed categories, read as one combined feed
+    categories: Vec<ArxivCategory>,
+    include_cross_lists: bool,
+    include_replacements: bool,
     enabled: bool,
+    api_base: String,
+    /// When set, the feed shows these export API results instead of a category
+    search: Option<ArxivSearch>,
 }
 
 impl ArxivProvider {
@@ -101,21 +320,56 @@ impl ArxivProvider {
 
         Ok(Self {
             client,
-            category: category
+            categories: vec![category
                 .map(|c| ArxivCategory::parse(&c))
-                .unwrap_or_default(),
+                .unwrap_or_default()],
+            include_cross_lists: true,
+            include_replacements: false,
             enabled: true,
+            api_base: ARXIV_API_BASE.to_string(),
+            search: None,
         })
     }
 
-    /// Set category
-    pub fn set_category(&mut self, category: ArxivCategory) {
-        self.category = category;
+    /// Create a provider following the configured categories
+    pub fn from_config(config: &ArxivConfig) -> Result<Self> {
+        let mut provider = Self::new(None)?;
+        let categories: Vec<ArxivCategory> = config
+            .categories
+            .iter()
+            .filter(|c| !c.trim().is_empty())
+            .map(|c| ArxivCategory::parse(c))
+            .collect();
+        if !categories.is_empty() {
+            provider.categories = categories;
+        }
+        provider.include_cross_lists = config.include_cross_lists;
+        provider.include_replacements = config.include_replacements;
+        provider.enabled = config.enabled;
+        Ok(provider)
     }
 
-    /// Fetch RSS feed
+    /// Show the results of an export API search as the feed
+    pub fn with_search(mut self, search: ArxivSearch) -> Self {
+        self.search = Some(search);
+        self
+    }
+
+    /// Use a different export API endpoint (for tests)
+    pub fn with_api_base_url(mut self, base_url: &str) -> Self {
+        self.api_base = base_url.to_string();
+        self
+    }
+
+    /// Set the followed categories
+    pub fn set_categories(&mut self, categories: Vec<ArxivCategory>) {
+        self.categories = categories;
+    }
+
+    /// Fetch the combined RSS feed of every followed category
     async fn fetch_feed(&self) -> Result<String> {
-        let url = format!("{}/{}", ARXIV_RSS_BASE, self.category.as_rss_path());
+        let codes: Vec<&str> = self.categories.iter().map(ArxivCategory::code).collect();
+        let url = format!("{}/{}", ARXIV_RSS_BASE, codes.join("+"));
 
         let response = self
             .client
@@ -161,18 +415,19 @@ impl ArxivProvider {
                         in_item = false;
                     }
                 }
-                Ok(Event::Text(e)) => {
-                    if in_item {
-                        if let Some(ref mut entry) = current_entry {
-                            let text = e.unescape().unwrap_or_default().to_string();
-                            match current_tag.as_str() {
-                                "title" => entry.title = text,
-                                "link" => entry.link = text,
-                                "description" => entry.description = text,
-                                "dc:creator" => entry.creator = text,
-                                "pubDate" => entry.pub_date = text,
-                                _ => {}
-                            }
+                Ok(Event::Text(e)) if in_item => {
+                    if let Some(ref mut entry) = current_entry {
+                        let text = e.unescape().unwrap_or_default().to_string();
+                        match current_tag.as_str() {
+                            "title" => entry.title = text,
+                            "link" => entry.link = text,
+                            "description" => entry.description = text,
+                            "dc:creator" => entry.creator = text,
+                            "pubDate" => entry.pub_date = text,
+                            "guid" => entry.guid = text,
+                            "category" => entry.categories.push(text),
+                            "arxiv:announce_type" => entry.announce_type = text,
+                            _ => {}
                         }
                     }
                 }
@@ -187,50 +442,162 @@ impl ArxivProvider {
         Ok(entries)
     }
 
+    /// Search results `start..start + max_results`
+    pub async fn search_papers(
+        &self,
+        search: &ArxivSearch,
+        start: usize,
+        max_results: usize,
+    ) -> Result<Vec<FeedItem>> {
+        let response = self
+            .client
+            .get(&self.api_base)
+            .query(&search.params(start, max_results))
+            .send()
+            .await?;
+        let status = response.status();
+        let xml = response
+            .text()
+            .await
+            .map_err(|e| ProviderError::Network(e.to_string()))?;
+
+        // Bad queries come back as a feed with a single error entry
+        let entries = parse_api_feed(&xml)?;
+        if !status.is_success() && entries.is_empty() {
+            return Err(ProviderError::Network(format!("arXiv returned {}", status)));
+        }
+
+        Ok(entries
+            .into_iter()
+            .map(|e| self.convert_api_entry(e))
+            .collect())
+    }
+
+    /// Convert an export API entry, keeping every author and category
+    fn convert_api_entry(&self, entry: ApiEntry) -> FeedItem {
+        let published_at = DateTime::parse_from_rfc3339(&entry.published)
+            .map(|dt| dt.with_timezone(&Utc))
+            .unwrap_or_else(|_| Utc::now());
+        let (arxiv_id, version) = split_version(&entry.id);
+        let primary = entry.categories.first().cloned().unwrap_or_default();
+
+        let mut tags = entry.categories;
+        tags.push("paper".to_string());
+
+        let metadata = FeedItemMetadata {
+            tags,
+            authors: entry.authors.clone(),
+            arxiv_id: Some(arxiv_id.clone()),
+            arxiv_version: version,
+            pdf_url: entry.pdf_url,
+            ..Default::default()
+        };
+
+        let mut item = FeedItem::new(
+            arxiv_id,
+            self.id().to_string(),
+            collapse_whitespace(&entry.title),
+            format!("arXiv:{}", ArxivCategory::parse(&primary).display_name()),
+            published_at,
+        )
+        .with_metadata(metadata)
+        .with_url(entry.abs_url.unwrap_or(entry.id));
+
+        if let Some(author) = author_line(&entry.authors) {
+            item = item.with_author(author);
+        }
+        let summary = collapse_whitespace(&entry.summary);
+        if !summary.is_empty() {
+            item = item.with_summary(summary);
+        }
+
+        item
+    }
+
+    /// Whether an RSS entry passes the cross-list and replacement settings
+    fn wants(&self, entry: &ArxivEntry) -> bool {
+        match AnnounceType::parse(&entry.announce_type) {
+            Some(AnnounceType::Cross) => self.include_cross_lists,
+            Some(AnnounceType::Replace) => self.include_replacements,
+            Some(AnnounceType::ReplaceCross) => {
+                self.include_replacements && self.include_cross_lists
+            }
+            Some(AnnounceType::New) | None => true,
+        }
+    }
+
+    /// Parse an RSS feed into items, dropping unwanted announcements
+    fn papers_from_feed(&self, xml: &str, limit: usize) -> Result<Vec<FeedItem>> {
+        Ok(self
+            .parse_feed(xml)?
+            .into_iter()
+            .filter(|e| self.wants(e))
+            .take(limit)
+            .map(|e| self.convert_to_feed_item(e))
+            .collect())
+    }
+
     /// Convert entry to FeedItem
     fn convert_to_feed_item(&self, entry: ArxivEntry) -> FeedItem {
         let published_at = DateTime::parse_from_rfc2822(&entry.pub_date)
             .map(|dt| dt.with_timezone(&Utc))
             .unwrap_or_else(|_| Utc::now());
 
-        // Clean title (remove newlines/extra spaces)
-        let title = entry
-            .title
-            .lines()
-            .map(|l| l.trim())
-            .collect::<Vec<_>>()
-            .join(" ");
+        let title = collapse_whitespace(&entry.title);
 
-        // Extract arXiv ID from link
-        let arxiv_id = entry
-            .link
-            .split('/')
-            .next_back()
-            .unwrap_or("unknown")
-            .to_string();
+        // The guid carries the version; older feeds only have the link
+        let (arxiv_id, version) = if entry.guid.is_empty() {
+            split_version(&entry.link)
+        } else {
+            split_version(&entry.guid)
+        };
+        let pdf_url = match version {
+            Some(version) => format!("https://arxiv.org/pdf/{}v{}", arxiv_id, version),
+            None => format!("https://arxiv.org/pdf/{}", arxiv_id),
+        };
+
+        let authors: Vec<String> = entry
+            .creator
+            .split(',')
+            .map(|a| a.trim().trim_start_matches("and ").trim().to_string())
+            .filter(|a| !a.is_empty())
+            .collect();
+
+        let primary = entry
+            .categories
+            .first()
+            .map(|c| ArxivCategory::parse(c))
+            .unwrap_or_else(|| self.categories.first().cloned().unwrap_or_default());
+        let mut tags = entry.categories;
+        tags.push("paper".to_string());
 
         let metadata = FeedItemMetadata {
-            tags: vec![
-                self.category.display_name().to_string(),
-                "paper".to_string(),
-            ],
+            tags,
+            authors: authors.clone(),
+            arxiv_id: Some(arxiv_id.clone()),
+            arxiv_version: version,
+            pdf_url: Some(pdf_url),
             ..Default::default()
         };
 
-        let source = format!("arXiv:{}", self.category.display_name());
+        let source = format!("arXiv:{}", primary.display_name());
 
-        // Clean description (HTML to text)
-        let summary = html2text::from_read(entry.description.as_bytes(), 200);
+        // The description leads with "arXiv:<id> Announce Type: new Abstract:"
+        let description = html2text::from_read(entry.description.as_bytes(), 200);
+        let summary = description
+            .split_once("Abstract:")
+            .map_or(description.as_str(), |(_, abstract_text)| abstract_text);
+        let summary = collapse_whitespace(summary);
 
         let mut item = FeedItem::new(arxiv_id, self.id().to_string(), title, source, published_at)
             .with_metadata(metadata)
             .with_url(entry.link);
 
-        if !entry.creator.is_empty() {
-            item = item.with_author(entry.creator);
+        if let Some(author) = author_line(&authors) {
+            item = item.with_author(author);
         }
 
-        if !summary.trim().is_empty() {
+        if !summary.is_empty() {
             item = item.with_summary(summary);
         }
 
@@ -265,29 +632,172 @@ impl FeedProvider for ArxivProvider {
     }
 
     fn categories(&self) -> Vec<&str> {
-        vec![
-            "cs", "cs.ai", "cs.lg", "cs.cl", "cs.cv", "math", "physics", "stat",
-        ]
+        self.categories.iter().map(ArxivCategory::code).collect()
     }
 
     async fn fetch_items(&self, limit: usize) -> Result<Vec<FeedItem>> {
+        if let Some(search) = &self.search {
+            return self.search_papers(search, 0, limit).await;
+        }
+
         let xml = self.fetch_feed().await?;
-        let entries = self.parse_feed(&xml)?;
+        self.papers_from_feed(&xml, limit)
+    }
 
-        let items: Vec<FeedItem> = entries
-            .into_iter()
-            .take(limit)
-            .map(|e| self.convert_to_feed_item(e))
-            .collect();
+    fn supports_search(&self) -> bool {
+        true // Export API
+    }
 
-        Ok(items)
+    async fn search(&self, query: &str, limit: usize) -> Result<Vec<FeedItem>> {
+        let search = ArxivSearch::parse(query).map_err(|e| ProviderError::Other(e.to_string()))?;
+        self.search_papers(&search, 0, limit).await
     }
 
-    fn supports_search(&self) -> bool {
-        false
+    fn supports_offset(&self) -> bool {
+        // RSS feeds are a single page
+        self.search.is_some()
+    }
+
+    async fn fetch_items_with_offset(&self, offset: usize, limit: usize) -> Result<Vec<FeedItem>> {
+        match &self.search {
+            Some(search) => self.search_papers(search, offset, limit).await,
+            None => Err(ProviderError::Other("Offset not supported".to_string())),
+        }
     }
 }
 
+/// Parse an export API Atom feed
+fn parse_api_feed(xml: &str) -> Result<Vec<ApiEntry>> {
+    let mut reader = Reader::from_str(xml);
+    reader.trim_text(true);
+
+    let mut entries = Vec::new();
+    let mut entry: Option<ApiEntry> = None;
+    let mut current_tag = String::new();
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(ref e)) => {
+                current_tag = String::from_utf8_lossy(e.name().as_ref()).to_string();
+                match (current_tag.as_str(), entry.as_mut()) {
+                    ("entry", _) => entry = Some(ApiEntry::default()),
+                    (_, Some(entry)) => read_entry_attributes(entry, e),
+                    _ => {}
+                }
+            }
+            Ok(Event::Empty(ref e)) => {
+                if let Some(entry) = entry.as_mut() {
+                    read_entry_attributes(entry, e);
+                }
+            }
+            Ok(Event::End(ref e)) => {
+                if e.name().as_ref() == b"entry" {
+                    if let Some(done) = entry.take() {
+                        if done.id.contains("/api/errors") {
+                            return Err(ProviderError::Other(format!(
+                                "arXiv: {}",
+                                collapse_whitespace(&done.summary)
+                            )));
+                        }
+                        entries.push(done);
+                    }
+                }
+                current_tag.clear();
+            }
+            Ok(Event::Text(e)) => {
+                if let Some(entry) = entry.as_mut() {
+                    let text = e.unescape().unwrap_or_default().to_string();
+                    match current_tag.as_str() {
+                        "id" => entry.id = text,
+                        "title" => entry.title = text,
+                        "summary" => entry.summary = text,
+                        "published" => entry.published = text,
+                        "name" => entry.authors.push(text),
+                        _ => {}
+                    }
+                }
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => {
+                return Err(ProviderError::Parse(format!("XML error: {}", e)));
+            }
+            _ => {}
+        }
+    }
+
+    Ok(entries)
+}
+
+/// Links and categories, which carry their data in attributes
+fn read_entry_attributes(entry: &mut ApiEntry, element: &BytesStart) {
+    let attr = |name: &str| {
+        element
+            .try_get_attribute(name)
+            .ok()
+            .flatten()
+            .and_then(|a| a.unescape_value().ok())
+            .map(|v| v.to_string())
+    };
+
+    match element.name().as_ref() {
+        b"link" => {
+            let href = attr("href");
+            if attr("title").as_deref() == Some("pdf") {
+                entry.pdf_url = href;
+            } else if attr("rel").as_deref() == Some("alternate") {
+                entry.abs_url = href;
+            }
+        }
+        b"arxiv:primary_category" => {
+            if let Some(term) = attr("term") {
+                entry.categories.retain(|c| *c != term);
+                entry.categories.insert(0, term);
+            }
+        }
+        b"category" => {
+            if let Some(term) = attr("term") {
+                if !entry.categories.contains(&term) {
+                    entry.categories.push(term);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Split `http://arxiv.org/abs/2401.00001v2` or `oai:arXiv.org:2401.00001v2`
+/// into `2401.00001` and version 2. Old-style ids keep their archive, e.g.
+/// `hep-th/9901001`.
+fn split_version(id: &str) -> (String, Option<u32>) {
+    let id = id
+        .split_once("/abs/")
+        .or_else(|| id.split_once("arXiv.org:"))
+        .map_or(id, |(_, rest)| rest);
+    match id.rsplit_once('v') {
+        Some((base, version)) if !base.is_empty() => match version.parse() {
+            Ok(version) => (base.to_string(), Some(version)),
+            Err(_) => (id.to_string(), None),
+        },
+        _ => (id.to_string(), None),
+    }
+}
+
+/// "A, B, C et al." for the list view
+fn author_line(authors: &[String]) -> Option<String> {
+    match authors.len() {
+        0 => None,
+        n if n <= MAX_LISTED_AUTHORS => Some(authors.join(", ")),
+        _ => Some(format!(
+            "{} et al.",
+            authors[..MAX_LISTED_AUTHORS].join(", ")
+        )),
+    }
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -302,9 +812,190 @@ mod tests {
 
     #[test]
     fn test_category_parsing() {
-        assert!(matches!(ArxivCategory::parse("cs.ai"), ArxivCategory::CSAI));
-        assert!(matches!(ArxivCategory::parse("ml"), ArxivCategory::CSLG));
-        assert!(matches!(ArxivCategory::parse("nlp"), ArxivCategory::CSCL));
+        assert_eq!(ArxivCategory::parse("cs.ai").code(), "cs.AI");
+        assert_eq!(ArxivCategory::parse("ml").code(), "cs.LG");
+        assert_eq!(ArxivCategory::parse("nlp").code(), "cs.CL");
+        assert_eq!(ArxivCategory::parse("Q-FIN.tr").code(), "q-fin.TR");
+        assert_eq!(
+            ArxivCategory::parse("physics.Comp-Ph").code(),
+            "physics.comp-ph"
+        );
+        assert_eq!(ArxivCategory::parse("q-fin.ST").display_name(), "q-fin.ST");
+    }
+
+    #[test]
+    fn test_search_parse() {
+        let search =
+            ArxivSearch::parse(r#"au:hinton ti:"capsule networks" cat:cs.LG routing sort:date"#)
+                .unwrap();
+        assert_eq!(search.sort, ArxivSort::Submitted);
+        assert_eq!(
+            search.query(),
+            r#"au:hinton AND ti:"capsule networks" AND cat:cs.LG AND all:routing"#
+        );
+        assert_eq!(
+            ArxivSearch::parse("sort:oldest"),
+            Err(ArxivSearchError::UnknownSort("oldest".to_string()))
+        );
+        assert_eq!(
+            ArxivSearch::parse("sort:date"),
+            Err(ArxivSearchError::Empty)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_search_papers() {
+        let mut server = mockito::Server::new_async().await;
+        let feed = r#"<?xml version="1.0" encoding="UTF-8"?>
+<feed xmlns="http://www.w3.org/2005/Atom" xmlns:arxiv="http://arxiv.org/schemas/atom">
+  <title>arXiv Query</title>
+  <entry>
+    <id>http://arxiv.org/abs/2401.00001v2</id>
+    <published>2024-01-01T18:00:00Z</published>
+    <title>Order Flow and
+      Price Impact</title>
+    <summary>  We study how order flow
+      moves prices.  </summary>
+    <author><name>Ada Lovelace</name></author>
+    <author><name>Alan Turing</name></author>
+    <author><name>Grace Hopper</name></author>
+    <author><name>Claude Shannon</name></author>
+    <link href="http://arxiv.org/abs/2401.00001v2" rel="alternate" type="text/html"/>
+    <link title="pdf" href="http://arxiv.org/pdf/2401.00001v2" rel="related" type="application/pdf"/>
+    <arxiv:primary_category term="q-fin.TR" scheme="http://arxiv.org/schemas/atom"/>
+    <category term="q-fin.ST" scheme="http://arxiv.org/schemas/atom"/>
+    <category term="q-fin.TR" scheme="http://arxiv.org/schemas/atom"/>
+  </entry>
+</feed>"#;
+        let _page = server
+            .mock("GET", "/api/query")
+            .match_query(mockito::Matcher::AllOf(vec![
+                mockito::Matcher::UrlEncoded(
+                    "search_query".into(),
+                    "cat:q-fin.TR AND all:impact".into(),
+                ),
+                mockito::Matcher::UrlEncoded("start".into(), "20".into()),
+                mockito::Matcher::UrlEncoded("max_results".into(), "10".into()),
+                mockito::Matcher::UrlEncoded("sortBy".into(), "submittedDate".into()),
+            ]))
+            .with_body(feed)
+            .create_async()
+            .await;
+
+        let search = ArxivSearch::parse("cat:q-fin.TR impact sort:date").unwrap();
+        let provider = ArxivProvider::new(None)
+            .unwrap()
+            .with_api_base_url(&format!("{}/api/query", server.url()))
+            .with_search(search);
+        assert!(provider.supports_offset());
+
+        let items = provider.fetch_items_with_offset(20, 10).await.unwrap();
+        assert_eq!(items.len(), 1);
+        let paper = &items[0];
+        assert_eq!(paper.id, "2401.00001");
+        assert_eq!(paper.title, "Order Flow and Price Impact");
+        assert_eq!(paper.source, "arXiv:q-fin.TR");
+        assert_eq!(
+            paper.author.as_deref(),
+            Some("Ada Lovelace, Alan Turing, Grace Hopper et al.")
+        );
+        assert_eq!(
+            paper.summary.as_deref(),
+            Some("We study how order flow moves prices.")
+        );
+        assert_eq!(paper.metadata.authors.len(), 4);
+        assert_eq!(paper.metadata.arxiv_version, Some(2));
+        assert_eq!(paper.metadata.tags, ["q-fin.TR", "q-fin.ST", "paper"]);
+        assert_eq!(
+            paper.metadata.pdf_url.as_deref(),
+            Some("http://arxiv.org/pdf/2401.00001v2")
+        );
+        assert_eq!(
+            paper.url.as_deref(),
+            Some("http://arxiv.org/abs/2401.00001v2")
+        );
+    }
+
+    #[test]
+    fn test_rss_announce_types_and_metadata() {
+        let rss = r#"<?xml version="1.0" encoding="UTF-8"?>
+<rss xmlns:arxiv="http://arxiv.org/schemas/atom" xmlns:dc="http://purl.org/dc/elements/1.1/" version="2.0">
+  <channel>
+    <title>q-fin.TR+q-fin.ST updates on arXiv.org</title>
+    <item>
+      <title>Order Flow and Price Impact</title>
+      <link>https://arxiv.org/abs/2401.00001</link>
+      <description>arXiv:2401.00001v1 Announce Type: new
+Abstract: We study how order flow moves prices.</description>
+      <guid isPermaLink="false">oai:arXiv.org:2401.00001v1</guid>
+      <category>q-fin.TR</category>
+      <category>q-fin.ST</category>
+      <pubDate>Mon, 01 Jan 2024 00:00:00 -0500</pubDate>
+      <arxiv:announce_type>new</arxiv:announce_type>
+      <dc:creator>Ada Lovelace, Alan Turing, Grace Hopper, Claude Shannon</dc:creator>
+    </item>
+    <item>
+      <title>A Cross-listed Paper</title>
+      <link>https://arxiv.org/abs/2401.00002</link>
+      <description>arXiv:2401.00002v1 Announce Type: cross
+Abstract: From elsewhere.</description>
+      <guid isPermaLink="false">oai:arXiv.org:2401.00002v1</guid>
+      <category>cs.LG</category>
+      <category>q-fin.ST</category>
+      <pubDate>Mon, 01 Jan 2024 00:00:00 -0500</pubDate>
+      <arxiv:announce_type>cross</arxiv:announce_type>
+      <dc:creator>Alan Turing</dc:creator>
+    </item>
+    <item>
+      <title>A Revised Paper</title>
+      <link>https://arxiv.org/abs/2312.00003</link>
+      <description>arXiv:2312.00003v3 Announce Type: replace
+Abstract: Now with more data.</description>
+      <guid isPermaLink="false">oai:arXiv.org:2312.00003v3</guid>
+      <category>q-fin.TR</category>
+      <pubDate>Mon, 01 Jan 2024 00:00:00 -0500</pubDate>
+      <arxiv:announce_type>replace</arxiv:announce_type>
+      <dc:creator>Grace Hopper</dc:creator>
+    </item>
+  </channel>
+</rss>"#;
+
+        let config = ArxivConfig {
+            categories: vec!["q-fin.tr".to_string(), "q-fin.ST".to_string()],
+            ..Default::default()
+        };
+        let mut provider = ArxivProvider::from_config(&config).unwrap();
+        assert_eq!(provider.categories(), ["q-fin.TR", "q-fin.ST"]);
+
+        // Cross-lists are in by default, replacements out
+        let items = provider.papers_from_feed(rss, 10).unwrap();
+        let ids: Vec<&str> = items.iter().map(|i| i.id.as_str()).collect();
+        assert_eq!(ids, ["2401.00001", "2401.00002"]);
+
+        let paper = &items[0];
+        assert_eq!(paper.source, "arXiv:q-fin.TR");
+        assert_eq!(paper.metadata.arxiv_version, Some(1));
+        assert_eq!(paper.metadata.authors.len(), 4);
+        assert_eq!(
+            paper.author.as_deref(),
+            Some("Ada Lovelace, Alan Turing, Grace Hopper et al.")
+        );
+        assert_eq!(
+            paper.summary.as_deref(),
+            Some("We study how order flow moves prices.")
+        );
+        assert_eq!(paper.metadata.tags, ["q-fin.TR", "q-fin.ST", "paper"]);
+        assert_eq!(
+            paper.metadata.pdf_url.as_deref(),
+            Some("https://arxiv.org/pdf/2401.00001v1")
+        );
+
+        provider.include_cross_lists = false;
+        provider.include_replacements = true;
+        let items = provider.papers_from_feed(rss, 10).unwrap();
+        let ids: Vec<&str> = items.iter().map(|i| i.id.as_str()).collect();
+        assert_eq!(ids, ["2401.00001", "2312.00003"]);
+        assert_eq!(items[1].metadata.arxiv_version, Some(3));
     }
 
     #[tokio::test]
@@ -312,8 +1003,7 @@ mod tests {
         let provider = ArxivProvider::new(Some("cs.ai".to_string())).unwrap();
         let result = provider.fetch_items(5).await;
         // Note: This test requires network access
-        if result.is_ok() {
-            let items = result.unwrap();
+        if let Ok(items) = result {
             println!("Fetched {} arXiv papers", items.len());
         }
     }
@@ -0,0 +1,525 @@
+//! Live Hacker News updates
+//!
+//! Firebase streams any path as server-sent events when asked for
+//! `text/event-stream`: a `put` with the whole value first, then `put` and
+//! `patch` events for the parts that change. [`HnLive`] follows the
+//! category's story list and `updates.json`, fetching the stories that enter
+//! the watched ranks or change while in them.
+
+use super::HackerNewsProvider;
+use crate::models::FeedItem;
+use crate::providers::{ProviderError, Result};
+use reqwest::header::ACCEPT;
+use reqwest::{Client, Response};
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::HashSet;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+/// Firebase sends a keep-alive every 30 seconds; silence past this is a dead stream
+const IDLE_TIMEOUT: Duration = Duration::from_secs(90);
+
+/// Wait before the first reconnect; doubles on every further attempt
+const BASE_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+/// Story lists hold at most 500 ids; ranks past this are ignored
+const MAX_RANKS: usize = 500;
+
+/// A change to the followed story list
+#[derive(Debug, Clone)]
+pub enum HnUpdate {
+    /// New order of the watched ranks, by story id
+    Ranking(Vec<u64>),
+    /// Stories that entered the watched ranks, or changed score or comment
+    /// count while in them
+    Items(Vec<FeedItem>),
+}
+
+/// A running subscription; dropping it closes the streams
+pub struct HnLive {
+    updates: mpsc::UnboundedReceiver<HnUpdate>,
+    task: JoinHandle<()>,
+}
+
+impl HnLive {
+    /// The next pending update, without waiting
+    pub fn try_next(&mut self) -> Option<HnUpdate> {
+        self.updates.try_recv().ok()
+    }
+
+    /// Wait for the next update
+    pub async fn next(&mut self) -> Option<HnUpdate> {
+        self.updates.recv().await
+    }
+}
+
+impl Drop for HnLive {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+impl HackerNewsProvider {
+    /// Follow the top `watch` stories of the current category as they change.
+    /// `known` are stories already shown; they are only fetched again when
+    /// they change.
+    pub fn subscribe(self, known: &[u64], watch: usize) -> HnLive {
+        let (tx, updates) = mpsc::unbounded_channel();
+        let known = known.iter().copied().collect();
+        let task = tokio::spawn(async move { self.follow(known, watch, tx).await });
+        HnLive { updates, task }
+    }
+
+    /// Keep both streams open, reconnecting with backoff until unsubscribed
+    async fn follow(
+        self,
+        mut known: HashSet<u64>,
+        watch: usize,
+        tx: mpsc::UnboundedSender<HnUpdate>,
+    ) {
+        // The feed client's timeout covers the whole body, which never ends here
+        let client = Client::builder()
+            .connect_timeout(Duration::from_secs(15))
+            .build()
+            .unwrap_or_default();
+        let mut ranking = Vec::new();
+        let mut backoff = BASE_BACKOFF;
+
+        while !tx.is_closed() {
+            match self
+                .stream(&client, &mut ranking, &mut known, watch, &tx)
+                .await
+            {
+                Ok(0) => {}
+                // A stream that applied changes closed normally: reconnect at once
+                Ok(_) => {
+                    backoff = BASE_BACKOFF;
+                    continue;
+                }
+                Err(e) => tracing::debug!("Hacker News live stream: {}", e),
+            }
+            // Nothing changed (keep-alives at most), so back off before trying again
+            tokio::time::sleep(backoff).await;
+            backoff = (backoff * 2).min(MAX_BACKOFF);
+        }
+    }
+
+    /// Read both streams until one closes; returns the number of changes
+    /// applied. Keep-alives don't count, and `cancel` or `auth_revoked` end
+    /// the stream with an error.
+    async fn stream(
+        &self,
+        client: &Client,
+        ranking: &mut Vec<u64>,
+        known: &mut HashSet<u64>,
+        watch: usize,
+        tx: &mpsc::UnboundedSender<HnUpdate>,
+    ) -> Result<usize> {
+        let list_url = format!("{}/{}.json", self.api_base, self.category.endpoint());
+        let mut list = EventStream::open(client, &list_url).await?;
+        let mut updates =
+            EventStream::open(client, &format!("{}/updates.json", self.api_base)).await?;
+        let mut seen = 0;
+
+        loop {
+            tokio::select! {
+                event = list.next() => {
+                    let Some(event) = event? else { return Ok(seen) };
+                    check_revoked(&event)?;
+
+                    let before = top(ranking, watch);
+                    if !apply_list_event(ranking, &event) {
+                        continue;
+                    }
+                    seen += 1;
+                    let after = top(ranking, watch);
+                    if after == before {
+                        continue;
+                    }
+
+                    // Send new stories first so the ranking can place them
+                    let entered: Vec<u64> =
+                        after.iter().copied().filter(|id| known.insert(*id)).collect();
+                    if !entered.is_empty() {
+                        let items = self.fetch_items_by_ids(&entered).await?;
+                        if tx.send(HnUpdate::Items(items)).is_err() {
+                            return Ok(seen);
+                        }
+                    }
+                    if tx.send(HnUpdate::Ranking(after)).is_err() {
+                        return Ok(seen);
+                    }
+                }
+                event = updates.next() => {
+                    let Some(event) = event? else { return Ok(seen) };
+                    check_revoked(&event)?;
+                    let changed = changed_items(&event);
+                    if !changed.is_empty() {
+                        seen += 1;
+                    }
+
+                    let watched = top(ranking, watch);
+                    let changed: Vec<u64> = changed
+                        .into_iter()
+                        .filter(|id| watched.contains(id))
+                        .collect();
+                    if changed.is_empty() {
+                        continue;
+                    }
+                    let items = self.fetch_items_by_ids(&changed).await?;
+                    if tx.send(HnUpdate::Items(items)).is_err() {
+                        return Ok(seen);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// One server-sent event
+#[derive(Debug, Default)]
+struct Event {
+    name: String,
+    data: String,
+}
+
+/// Body of Firebase `put` and `patch` events
+#[derive(Debug, Deserialize)]
+struct Change {
+    path: String,
+    data: Value,
+}
+
+/// Reads server-sent events off a streaming response
+struct EventStream {
+    response: Response,
+    buf: Vec<u8>,
+}
+
+impl EventStream {
+    async fn open(client: &Client, url: &str) -> Result<Self> {
+        let response = client
+            .get(url)
+            .header(ACCEPT, "text/event-stream")
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            return Err(ProviderError::Network(format!(
+                "{} returned {}",
+                url,
+                response.status()
+            )));
+        }
+        Ok(Self {
+            response,
+            buf: Vec::new(),
+        })
+    }
+
+    /// The next complete event, or `None` once the server closes the stream.
+    /// Partial events stay buffered, so this is safe to cancel.
+    async fn next(&mut self) -> Result<Option<Event>> {
+        loop {
+            if let Some(event) = take_event(&mut self.buf) {
+                return Ok(Some(event));
+            }
+            let chunk = tokio::time::timeout(IDLE_TIMEOUT, self.response.chunk())
+                .await
+                .map_err(|_| ProviderError::Network("stream went quiet".to_string()))??;
+            match chunk {
+                // Carriage returns only ever end lines; dropping them leaves `\n`
+                Some(bytes) => self.buf.extend(bytes.iter().filter(|&&b| b != b'\r')),
+                None => return Ok(None),
+            }
+        }
+    }
+}
+
+/// Firebase sends `cancel` or `auth_revoked` when it stops serving a path
+fn check_revoked(event: &Event) -> Result<()> {
+    match event.name.as_str() {
+        "cancel" | "auth_revoked" => Err(ProviderError::Network(format!(
+            "stream ended with {}",
+            event.name
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// Split the first blank-line terminated event off the buffer
+fn take_event(buf: &mut Vec<u8>) -> Option<Event> {
+    let end = buf.windows(2).position(|w| w == b"\n\n")?;
+    let block: Vec<u8> = buf.drain(..end + 2).collect();
+
+    let mut event = Event::default();
+    let mut data = Vec::new();
+    for line in String::from_utf8_lossy(&block).lines() {
+        let (field, value) = line.split_once(':').unwrap_or((line, ""));
+        let value = value.strip_prefix(' ').unwrap_or(value);
+        match field {
+            "event" => event.name = value.to_string(),
+            "data" => data.push(value.to_string()),
+            _ => {}
+        }
+    }
+    event.data = data.join("\n");
+    Some(event)
+}
+
+/// Apply a `put` or `patch` to a story list. Removed ranks are kept as 0 so
+/// later indices still line up. False for keep-alives and other events.
+fn apply_list_event(ranking: &mut Vec<u64>, event: &Event) -> bool {
+    let Ok(change) = serde_json::from_str::<Change>(&event.data) else {
+        return false;
+    };
+    let path = change.path.trim_matches('/');
+
+    match (event.name.as_str(), path) {
+        ("put", "") => {
+            ranking.clear();
+            match &change.data {
+                Value::Array(ids) => ranking.extend(ids.iter().map(|id| id.as_u64().unwrap_or(0))),
+                Value::Object(ranks) => {
+                    for (index, id) in ranks {
+                        set_rank(ranking, index, id);
+                    }
+                }
+                _ => {}
+            }
+        }
+        ("put", index) => set_rank(ranking, index, &change.data),
+        ("patch", _) => {
+            let Some(ranks) = change.data.as_object() else {
+                return false;
+            };
+            for (index, id) in ranks {
+                let index = if path.is_empty() {
+                    index.clone()
+                } else {
+                    format!("{}/{}", path, index)
+                };
+                set_rank(ranking, &index, id);
+            }
+        }
+        _ => return false,
+    }
+    true
+}
+
+fn set_rank(ranking: &mut Vec<u64>, index: &str, id: &Value) {
+    let Ok(index) = index.parse::<usize>() else {
+        return;
+    };
+    if index >= MAX_RANKS {
+        return;
+    }
+    if index >= ranking.len() {
+        ranking.resize(index + 1, 0);
+    }
+    ranking[index] = id.as_u64().unwrap_or(0);
+}
+
+/// The first `watch` stories of a list
+fn top(ranking: &[u64], watch: usize) -> Vec<u64> {
+    ranking
+        .iter()
+        .copied()
+        .filter(|&id| id != 0)
+        .take(watch)
+        .collect()
+}
+
+/// Item ids listed by an `updates.json` event
+fn changed_items(event: &Event) -> Vec<u64> {
+    if !matches!(event.name.as_str(), "put" | "patch") {
+        return Vec::new();
+    }
+    let Ok(change) = serde_json::from_str::<Change>(&event.data) else {
+        return Vec::new();
+    };
+
+    let items = match change.path.trim_matches('/') {
+        "" => change.data.get("items").cloned().unwrap_or_default(),
+        "items" => change.data,
+        path if path.starts_with("items/") => return change.data.as_u64().into_iter().collect(),
+        _ => return Vec::new(),
+    };
+    match items {
+        Value::Array(ids) => ids.iter().filter_map(Value::as_u64).collect(),
+        Value::Object(ids) => ids.values().filter_map(Value::as_u64).collect(),
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// Stand-in for Firebase: streams the events for a path in two halves
+    /// each and then holds the connection open; other paths get a JSON body
+    async fn stand_in(
+        streams: Vec<(&'static str, Vec<String>)>,
+        items: Vec<(String, String)>,
+    ) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        let routes = Arc::new((streams, items));
+
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let routes = routes.clone();
+                tokio::spawn(async move {
+                    let mut request = vec![0; 4096];
+                    let n = socket.read(&mut request).await.unwrap_or(0);
+                    let request = String::from_utf8_lossy(&request[..n]).to_string();
+                    let path = request.split_whitespace().nth(1).unwrap_or_default();
+                    let (streams, items) = &*routes;
+
+                    if let Some((_, events)) = streams.iter().find(|(p, _)| *p == path) {
+                        let head = "HTTP/1.1 200 OK\r\ncontent-type: text/event-stream\r\n\r\n";
+                        socket.write_all(head.as_bytes()).await.unwrap();
+                        for event in events {
+                            let (first, second) = event.split_at(event.len() / 2);
+                            for half in [first, second] {
+                                socket.write_all(half.as_bytes()).await.unwrap();
+                                tokio::time::sleep(Duration::from_millis(10)).await;
+                            }
+                        }
+                        std::future::pending::<()>().await;
+                    }
+
+                    let body = items
+                        .iter()
+                        .find(|(p, _)| p == path)
+                        .map_or("null", |(_, body)| body.as_str());
+                    let response = format!(
+                        "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\n\
+                         content-length: {}\r\nconnection: close\r\n\r\n{}",
+                        body.len(),
+                        body
+                    );
+                    let _ = socket.write_all(response.as_bytes()).await;
+                });
+            }
+        });
+
+        base
+    }
+
+    #[test]
+    fn test_list_events() {
+        let event = |name: &str, data: &str| Event {
+            name: name.to_string(),
+            data: data.to_string(),
+        };
+        let mut ranking = Vec::new();
+
+        assert!(!apply_list_event(
+            &mut ranking,
+            &event("keep-alive", "null")
+        ));
+        assert!(apply_list_event(
+            &mut ranking,
+            &event("put", r#"{"path": "/", "data": [3, 1, 2]}"#)
+        ));
+        assert!(apply_list_event(
+            &mut ranking,
+            &event("patch", r#"{"path": "/", "data": {"1": 9}}"#)
+        ));
+        assert_eq!(ranking, vec![3, 9, 2]);
+
+        // Absurd ranks are dropped instead of growing the list
+        assert!(apply_list_event(
+            &mut ranking,
+            &event("put", r#"{"path": "/999999999", "data": 5}"#)
+        ));
+        assert_eq!(ranking.len(), 3);
+        assert!(check_revoked(&event("cancel", "null")).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_follows_ranks_and_updates() {
+        let event = |name: &str, data: &str| format!("event: {}\ndata: {}\n\n", name, data);
+        let story = |id: u64, score: i32| {
+            (
+                format!("/item/{}.json", id),
+                format!(
+                    r#"{{"id": {id}, "type": "story", "by": "pg", "time": 1767225600,
+                        "title": "Story {id}", "score": {score}, "descendants": 4}}"#
+                ),
+            )
+        };
+
+        let base = stand_in(
+            vec![
+                (
+                    "/topstories.json",
+                    vec![
+                        event("put", r#"{"path": "/", "data": [1, 2, 3, 4]}"#),
+                        event("keep-alive", "null"),
+                        event("patch", r#"{"path": "/", "data": {"0": 3, "2": 1}}"#),
+                    ],
+                ),
+                (
+                    "/updates.json",
+                    // Keep-alives first so the list is known before the update
+                    vec![
+                        event("keep-alive", "null"),
+                        event("keep-alive", "null"),
+                        event("keep-alive", "null"),
+                        event(
+                            "put",
+                            r#"{"path": "/", "data": {"items": [2, 99], "profiles": ["pg"]}}"#,
+                        ),
+                    ],
+                ),
+            ],
+            vec![story(1, 10), story(2, 55), story(3, 7)],
+        )
+        .await;
+
+        let provider = HackerNewsProvider::new(None)
+            .unwrap()
+            .with_api_base_url(&base);
+        let mut live = provider.subscribe(&[1, 2], 3);
+
+        let mut updates = Vec::new();
+        while updates.len() < 4 {
+            let update = tokio::time::timeout(Duration::from_secs(5), live.next())
+                .await
+                .expect("no update from the stand-in")
+                .unwrap();
+            updates.push(update);
+        }
+
+        let rankings: Vec<Vec<u64>> = updates
+            .iter()
+            .filter_map(|u| match u {
+                HnUpdate::Ranking(ids) => Some(ids.clone()),
+                HnUpdate::Items(_) => None,
+            })
+            .collect();
+        assert_eq!(rankings, vec![vec![1, 2, 3], vec![3, 2, 1]]);
+
+        // Story 3 is new to the list; story 2 changed; 99 is not watched
+        let mut fetched: Vec<(String, Option<i32>)> = updates
+            .iter()
+            .flat_map(|u| match u {
+                HnUpdate::Items(items) => items.clone(),
+                HnUpdate::Ranking(_) => Vec::new(),
+            })
+            .map(|item| (item.id, item.metadata.score))
+            .collect();
+        fetched.sort();
+        assert_eq!(
+            fetched,
+            vec![("2".to_string(), Some(55)), ("3".to_string(), Some(7))]
+        );
+    }
+}
@@ -0,0 +1,1151 @@
+//! Hacker News provider
+
+mod live;
+
+pub use live::{HnLive, HnUpdate};
+
+use crate::models::{Comment, FeedItem, FeedItemMetadata};
+use crate::providers::{FeedProvider, ProviderError, ProviderStatus, Result};
+use crate::utils::{normalize_url, parse_since};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::future::join_all;
+use reqwest::{Client, StatusCode};
+use serde::Deserialize;
+use std::time::Duration;
+use thiserror::Error;
+
+const HN_API_BASE: &str = "https://hacker-news.firebaseio.com/v0";
+const ALGOLIA_API_BASE: &str = "https://hn.algolia.com/api/v1";
+const HN_ITEM_URL: &str = "https://news.ycombinator.com/item?id=";
+
+/// Hacker News story category
+#[derive(Debug, Clone, Copy, Default)]
+pub enum HnCategory {
+    #[default]
+    Top,
+    New,
+    Best,
+    Ask,
+    Show,
+    Job,
+}
+
+impl HnCategory {
+    pub fn endpoint(&self) -> &str {
+        match self {
+            HnCategory::Top => "topstories",
+            HnCategory::New => "newstories",
+            HnCategory::Best => "beststories",
+            HnCategory::Ask => "askstories",
+            HnCategory::Show => "showstories",
+            HnCategory::Job => "jobstories",
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            HnCategory::Top => "Top",
+            HnCategory::New => "New",
+            HnCategory::Best => "Best",
+            HnCategory::Ask => "Ask HN",
+            HnCategory::Show => "Show HN",
+            HnCategory::Job => "Jobs",
+        }
+    }
+
+    pub fn parse(s: &str) -> Self {
+        match s.to_lowercase().as_str() {
+            "new" => HnCategory::New,
+            "best" => HnCategory::Best,
+            "ask" => HnCategory::Ask,
+            "show" => HnCategory::Show,
+            "job" | "jobs" => HnCategory::Job,
+            _ => HnCategory::Top,
+        }
+    }
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum HnSearchError {
+    #[error("Invalid date '{0}' (use e.g. 24h, 7d or 2026-01-31)")]
+    InvalidDate(String),
+
+    #[error("Invalid number in '{0}'")]
+    InvalidNumber(String),
+
+    #[error("Unknown type '{0}' (use story, comment, ask, show, poll, job or all)")]
+    UnknownType(String),
+
+    #[error("Unknown sort '{0}' (use relevance or date)")]
+    UnknownSort(String),
+}
+
+/// Kind of item an Algolia search is restricted to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HnSearchType {
+    Story,
+    Comment,
+    Ask,
+    Show,
+    Poll,
+    Job,
+}
+
+impl HnSearchType {
+    /// Algolia tag for this kind of item
+    pub fn tag(&self) -> &str {
+        match self {
+            HnSearchType::Story => "story",
+            HnSearchType::Comment => "comment",
+            HnSearchType::Ask => "ask_hn",
+            HnSearchType::Show => "show_hn",
+            HnSearchType::Poll => "poll",
+            HnSearchType::Job => "job",
+        }
+    }
+}
+
+/// An Algolia search with filters, parsed from e.g.
+/// `rust by:pg type:comment points:100 comments:10 since:7d sort:date`
+#[derive(Debug, Clone, PartialEq)]
+pub struct HnSearch {
+    pub query: String,
+    /// Newest first (`search_by_date`) instead of by relevance
+    pub by_date: bool,
+    /// Kinds of item to match; any of them
+    pub types: Vec<HnSearchType>,
+    pub author: Option<String>,
+    pub min_points: Option<u32>,
+    pub min_comments: Option<u32>,
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+}
+
+impl Default for HnSearch {
+    fn default() -> Self {
+        Self {
+            query: String::new(),
+            by_date: false,
+            types: vec![HnSearchType::Story],
+            author: None,
+            min_points: None,
+            min_comments: None,
+            since: None,
+            until: None,
+        }
+    }
+}
+
+impl HnSearch {
+    /// Parse a query; words without a known `key:` prefix are search text
+    pub fn parse(input: &str) -> std::result::Result<Self, HnSearchError> {
+        let now = Utc::now();
+        let mut search = HnSearch {
+            types: Vec::new(),
+            ..Default::default()
+        };
+        let mut words = Vec::new();
+
+        for token in input.split_whitespace() {
+            let Some((key, value)) = token.split_once(':').filter(|(_, v)| !v.is_empty()) else {
+                words.push(token);
+                continue;
+            };
+            let number = || {
+                value
+                    .trim_start_matches(">=")
+                    .trim_start_matches('>')
+                    .parse::<u32>()
+                    .map_err(|_| HnSearchError::InvalidNumber(token.to_string()))
+            };
+            let date = || {
+                parse_since(value, now).ok_or_else(|| HnSearchError::InvalidDate(value.to_string()))
+            };
+
+            match key.to_lowercase().as_str() {
+                "by" | "author" => search.author = Some(value.to_string()),
+                "points" => search.min_points = Some(number()?),
+                "comments" => search.min_comments = Some(number()?),
+                "since" => search.since = Some(date()?),
+                "until" | "before" => search.until = Some(date()?),
+                "sort" => {
+                    search.by_date = match value.to_lowercase().as_str() {
+                        "date" | "new" | "newest" => true,
+                        "relevance" | "popular" => false,
+                        other => return Err(HnSearchError::UnknownSort(other.to_string())),
+                    }
+                }
+                "type" => {
+                    for kind in value.split(',') {
+                        match kind.to_lowercase().as_str() {
+                            "story" | "stories" => search.types.push(HnSearchType::Story),
+                            "comment" | "comments" => search.types.push(HnSearchType::Comment),
+                            "ask" => search.types.push(HnSearchType::Ask),
+                            "show" => search.types.push(HnSearchType::Show),
+                            "poll" => search.types.push(HnSearchType::Poll),
+                            "job" | "jobs" => search.types.push(HnSearchType::Job),
+                            "all" => search.types.extend([
+                                HnSearchType::Story,
+                                HnSearchType::Comment,
+                                HnSearchType::Poll,
+                                HnSearchType::Job,
+                            ]),
+                            other => return Err(HnSearchError::UnknownType(other.to_string())),
+                        }
+                    }
+                }
+                // Unknown keys (e.g. "10:30" or a URL) are just text
+                _ => words.push(token),
+            }
+        }
+
+        if search.types.is_empty() {
+            search.types.push(HnSearchType::Story);
+        }
+        search.types.dedup();
+        search.query = words.join(" ");
+        Ok(search)
+    }
+
+    fn endpoint(&self) -> &str {
+        if self.by_date {
+            "search_by_date"
+        } else {
+            "search"
+        }
+    }
+
+    /// Algolia `tags`: types are OR'd, the author is AND'd
+    fn tags(&self) -> String {
+        let types: Vec<&str> = self.types.iter().map(HnSearchType::tag).collect();
+        let mut tags = match types.as_slice() {
+            [single] => single.to_string(),
+            many => format!("({})", many.join(",")),
+        };
+        if let Some(author) = &self.author {
+            tags.push_str(&format!(",author_{}", author));
+        }
+        tags
+    }
+
+    /// Algolia `numericFilters` for points, comments and the date range
+    fn numeric_filters(&self) -> Vec<String> {
+        let mut filters = Vec::new();
+        if let Some(points) = self.min_points {
+            filters.push(format!("points>={}", points));
+        }
+        if let Some(comments) = self.min_comments {
+            filters.push(format!("num_comments>={}", comments));
+        }
+        if let Some(since) = self.since {
+            filters.push(format!("created_at_i>={}", since.timestamp()));
+        }
+        if let Some(until) = self.until {
+            filters.push(format!("created_at_i<{}", until.timestamp()));
+        }
+        filters
+    }
+
+    /// Query parameters for one page of results
+    fn params(&self, page: usize, hits_per_page: usize) -> Vec<(&'static str, String)> {
+        let mut params = vec![
+            ("query", self.query.clone()),
+            ("tags", self.tags()),
+            ("page", page.to_string()),
+            ("hitsPerPage", hits_per_page.to_string()),
+        ];
+        let numeric = self.numeric_filters();
+        if !numeric.is_empty() {
+            params.push(("numericFilters", numeric.join(",")));
+        }
+        params
+    }
+}
+
+/// A page of Algolia results
+#[derive(Debug, Deserialize)]
+struct AlgoliaResponse {
+    hits: Vec<AlgoliaHit>,
+    #[serde(rename = "nbPages", default)]
+    pages: usize,
+}
+
+/// A story or comment from Algolia
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct AlgoliaHit {
+    #[serde(rename = "objectID")]
+    object_id: String,
+    #[serde(rename = "_tags")]
+    tags: Vec<String>,
+    title: Option<String>,
+    url: Option<String>,
+    author: Option<String>,
+    points: Option<i32>,
+    num_comments: Option<i32>,
+    created_at_i: Option<i64>,
+    story_text: Option<String>,
+    comment_text: Option<String>,
+    story_id: Option<u64>,
+    story_title: Option<String>,
+}
+
+/// Hacker News item from API
+#[derive(Debug, Clone, Deserialize)]
+pub struct HnItem {
+    pub id: u64,
+    #[serde(rename = "type")]
+    pub item_type: String,
+    pub by: Option<String>,
+    pub time: i64,
+    pub text: Option<String>,
+    pub dead: Option<bool>,
+    pub deleted: Option<bool>,
+    pub parent: Option<u64>,
+    pub kids: Option<Vec<u64>>,
+    pub url: Option<String>,
+    pub score: Option<i32>,
+    pub title: Option<String>,
+    pub descendants: Option<i32>,
+}
+
+/// Hacker News user from API
+#[derive(Debug, Clone, Deserialize)]
+pub struct HnUser {
+    pub id: String,
+    /// Unix timestamp of account creation
+    pub created: i64,
+    pub karma: i32,
+    /// Self-description (HTML)
+    pub about: Option<String>,
+    /// Stories, comments and polls, newest first
+    #[serde(default)]
+    pub submitted: Vec<u64>,
+}
+
+/// A user with their recent stories and comments
+#[derive(Debug, Clone)]
+pub struct HnProfile {
+    pub user: HnUser,
+    pub submissions: Vec<FeedItem>,
+    /// Each comment on its own, without replies
+    pub comments: Vec<Comment>,
+}
+
+/// Hacker News provider
+pub struct HackerNewsProvider {
+    client: Client,
+    pub category: HnCategory,
+    enabled: bool,
+    // Cache story IDs for infinite scroll
+    cached_ids: std::sync::Mutex<Vec<u64>>,
+    api_base: String,
+    search_base: String,
+    /// When set, the feed shows these Algolia results instead of a category
+    search: Option<HnSearch>,
+}
+
+impl HackerNewsProvider {
+    /// Create a new Hacker News provider
+    pub fn new(category: Option<String>) -> Result<Self> {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(30))
+            .build()
+            .map_err(|e| ProviderError::Other(e.to_string()))?;
+
+        Ok(Self {
+            client,
+            category: category.map(|c| HnCategory::parse(&c)).unwrap_or_default(),
+            enabled: true,
+            cached_ids: std::sync::Mutex::new(Vec::new()),
+            api_base: HN_API_BASE.to_string(),
+            search_base: ALGOLIA_API_BASE.to_string(),
+            search: None,
+        })
+    }
+
+    /// Show the results of an Algolia search as the feed
+    pub fn with_search(mut self, search: HnSearch) -> Self {
+        self.search = Some(search);
+        self
+    }
+
+    /// Use a different Firebase API root (for tests)
+    pub fn with_api_base_url(mut self, base_url: &str) -> Self {
+        self.api_base = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Use a different Algolia API root (for tests)
+    pub fn with_search_base_url(mut self, base_url: &str) -> Self {
+        self.search_base = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Set the current category
+    pub fn set_category(&mut self, category: HnCategory) {
+        self.category = category;
+        // Clear cached IDs when category changes
+        if let Ok(mut ids) = self.cached_ids.lock() {
+            ids.clear();
+        }
+    }
+
+    /// Fetch story IDs for current category
+    async fn fetch_story_ids(&self, limit: usize) -> Result<Vec<u64>> {
+        let url = format!("{}/{}.json", self.api_base, self.category.endpoint());
+
+        let ids: Vec<u64> = self
+            .client
+            .get(&url)
+            .send()
+            .await?
+            .json()
+            .await
+            .map_err(|e| ProviderError::Parse(e.to_string()))?;
+
+        // Cache the IDs for infinite scroll
+        if let Ok(mut cached) = self.cached_ids.lock() {
+            *cached = ids.clone();
+        }
+
+        Ok(ids.into_iter().take(limit).collect())
+    }
+
+    /// Fetch items with offset for infinite scroll
+    pub async fn fetch_offset_items(&self, offset: usize, limit: usize) -> Result<Vec<FeedItem>> {
+        // Fetch all story IDs
+        let ids = self.fetch_all_story_ids().await?;
+
+        // Get slice with offset
+        let slice: Vec<u64> = ids.into_iter().skip(offset).take(limit).collect();
+
+        if slice.is_empty() {
+            return Ok(vec![]);
+        }
+
+        self.fetch_items_by_ids(&slice).await
+    }
+
+    /// Fetch all story IDs without limit (for caching)
+    async fn fetch_all_story_ids(&self) -> Result<Vec<u64>> {
+        let url = format!("{}/{}.json", self.api_base, self.category.endpoint());
+
+        let ids: Vec<u64> = self
+            .client
+            .get(&url)
+            .send()
+            .await?
+            .json()
+            .await
+            .map_err(|e| ProviderError::Parse(e.to_string()))?;
+
+        // Cache the IDs
+        if let Ok(mut cached) = self.cached_ids.lock() {
+            *cached = ids.clone();
+        }
+
+        Ok(ids)
+    }
+
+    /// Fetch items by IDs (parallel)
+    async fn fetch_items_by_ids(&self, ids: &[u64]) -> Result<Vec<FeedItem>> {
+        let batch_size = 25; // Increased from 10 for faster fetching
+        let mut all_items = Vec::with_capacity(ids.len());
+
+        for chunk in ids.chunks(batch_size) {
+            let futures: Vec<_> = chunk.iter().map(|&id| self.fetch_item(id)).collect();
+
+            let results = join_all(futures).await;
+
+            for item in results.into_iter().flatten() {
+                if item.deleted.unwrap_or(false) || item.dead.unwrap_or(false) {
+                    continue;
+                }
+                all_items.push(self.convert_to_feed_item(item));
+            }
+        }
+
+        Ok(all_items)
+    }
+
+    /// Fetch a single item by ID
+    async fn fetch_item(&self, id: u64) -> Result<HnItem> {
+        let url = format!("{}/item/{}.json", self.api_base, id);
+
+        self.client
+            .get(&url)
+            .send()
+            .await?
+            .json::<Option<HnItem>>()
+            .await
+            .map_err(|e| ProviderError::Parse(e.to_string()))?
+            .ok_or(ProviderError::Other(format!("Item {} not found", id)))
+    }
+
+    /// Convert HnItem to FeedItem
+    fn convert_to_feed_item(&self, item: HnItem) -> FeedItem {
+        let published_at = DateTime::from_timestamp(item.time, 0).unwrap_or_else(Utc::now);
+
+        let source = match self.category {
+            HnCategory::Ask => "Ask HN",
+            HnCategory::Show => "Show HN",
+            HnCategory::Job => "HN Jobs",
+            _ => "Hacker News",
+        };
+
+        let metadata = FeedItemMetadata {
+            score: item.score,
+            comments: item.descendants,
+            hn_id: Some(item.id), // Store HN ID for fetching comments
+            ..Default::default()
+        };
+
+        let mut feed_item = FeedItem::new(
+            item.id.to_string(),
+            self.id().to_string(),
+            item.title.unwrap_or_else(|| "(no title)".to_string()),
+            source.to_string(),
+            published_at,
+        )
+        .with_metadata(metadata);
+
+        if let Some(author) = item.by {
+            feed_item = feed_item.with_author(author);
+        }
+
+        if let Some(url) = item.url {
+            feed_item = feed_item.with_url(url);
+        }
+
+        if let Some(text) = item.text {
+            // Convert HTML to plain text
+            let plain_text = html2text::from_read(text.as_bytes(), 80);
+            feed_item = feed_item.with_summary(plain_text);
+        }
+
+        feed_item
+    }
+
+    /// Search results `offset..offset + limit`
+    ///
+    /// Algolia pages by number, so this reads the one or two pages of size
+    /// `limit` that cover the range.
+    async fn search_items(
+        &self,
+        search: &HnSearch,
+        offset: usize,
+        limit: usize,
+    ) -> Result<Vec<FeedItem>> {
+        if limit == 0 {
+            return Ok(Vec::new());
+        }
+        // Algolia caps hitsPerPage at 1000
+        let per_page = limit.min(1000);
+        let first = offset / per_page;
+        let last = (offset + limit - 1) / per_page;
+
+        let mut hits = Vec::new();
+        for page in first..=last {
+            let response = self.fetch_search_page(search, page, per_page).await?;
+            let done = page + 1 >= response.pages;
+            hits.extend(response.hits);
+            if done {
+                break;
+            }
+        }
+
+        Ok(hits
+            .into_iter()
+            .skip(offset - first * per_page)
+            .take(limit)
+            .filter_map(|hit| self.convert_hit(hit))
+            .collect())
+    }
+
+    async fn fetch_search_page(
+        &self,
+        search: &HnSearch,
+        page: usize,
+        per_page: usize,
+    ) -> Result<AlgoliaResponse> {
+        self.fetch_algolia(search.endpoint(), &search.params(page, per_page))
+            .await
+    }
+
+    /// Stories submitted with `url`, matched after normalizing both links
+    pub async fn find_by_url(&self, url: &str) -> Result<Vec<FeedItem>> {
+        let params = [
+            ("query", url.to_string()),
+            ("restrictSearchableAttributes", "url".to_string()),
+            ("tags", "story".to_string()),
+            ("hitsPerPage", "50".to_string()),
+        ];
+        let response = self.fetch_algolia("search", &params).await?;
+
+        // Algolia matches words of the URL, not the whole link
+        let wanted = normalize_url(url);
+        Ok(response
+            .hits
+            .into_iter()
+            .filter(|hit| wanted.is_none() || hit.url.as_deref().and_then(normalize_url) == wanted)
+            .filter_map(|hit| self.convert_hit(hit))
+            .collect())
+    }
+
+    async fn fetch_algolia(
+        &self,
+        endpoint: &str,
+        params: &[(&str, String)],
+    ) -> Result<AlgoliaResponse> {
+        let url = format!("{}/{}", self.search_base, endpoint);
+        let response = self.client.get(&url).query(params).send().await?;
+        if response.status() == StatusCode::TOO_MANY_REQUESTS {
+            return Err(ProviderError::RateLimit);
+        }
+
+        response
+            .error_for_status()?
+            .json()
+            .await
+            .map_err(|e| ProviderError::Parse(e.to_string()))
+    }
+
+    /// Convert an Algolia hit; comments link into their story's thread
+    fn convert_hit(&self, hit: AlgoliaHit) -> Option<FeedItem> {
+        let published_at = hit
+            .created_at_i
+            .and_then(|ts| DateTime::from_timestamp(ts, 0))
+            .unwrap_or_else(Utc::now);
+        let has_tag = |tag: &str| hit.tags.iter().any(|t| t == tag);
+
+        if has_tag("comment") {
+            let story_id = hit.story_id?;
+            let text = html2text::from_read(hit.comment_text.unwrap_or_default().as_bytes(), 80);
+            let title = format!(
+                "{} on: {}",
+                hit.author.as_deref().unwrap_or("[deleted]"),
+                hit.story_title.as_deref().unwrap_or("(story)")
+            );
+            let metadata = FeedItemMetadata {
+                tags: vec!["hackernews".to_string(), "comment".to_string()],
+                hn_id: Some(story_id),
+                ..Default::default()
+            };
+
+            let mut item = FeedItem::new(
+                hit.object_id.clone(),
+                self.id().to_string(),
+                title,
+                "HN comment".to_string(),
+                published_at,
+            )
+            .with_url(format!("{}{}#{}", HN_ITEM_URL, story_id, hit.object_id))
+            .with_summary(text.trim().to_string())
+            .with_metadata(metadata);
+            if let Some(author) = hit.author {
+                item = item.with_author(author);
+            }
+            return Some(item);
+        }
+
+        let title = hit.title?;
+        let source = if has_tag("ask_hn") {
+            "Ask HN"
+        } else if has_tag("show_hn") {
+            "Show HN"
+        } else if has_tag("job") {
+            "HN Jobs"
+        } else {
+            "Hacker News"
+        };
+        let metadata = FeedItemMetadata {
+            score: hit.points,
+            comments: hit.num_comments,
+            tags: vec!["hackernews".to_string()],
+            hn_id: hit.object_id.parse().ok(),
+            ..Default::default()
+        };
+
+        let mut item = FeedItem::new(
+            hit.object_id.clone(),
+            self.id().to_string(),
+            title,
+            source.to_string(),
+            published_at,
+        )
+        .with_metadata(metadata);
+
+        match hit.url.filter(|url| !url.is_empty()) {
+            Some(url) => item = item.with_url(url),
+            None => item = item.with_url(format!("{}{}", HN_ITEM_URL, hit.object_id)),
+        }
+        if let Some(author) = hit.author {
+            item = item.with_author(author);
+        }
+        if let Some(text) = hit.story_text.filter(|t| !t.is_empty()) {
+            item = item.with_summary(html2text::from_read(text.as_bytes(), 80));
+        }
+
+        Some(item)
+    }
+
+    /// Fetch a user and their `limit` most recent submissions
+    pub async fn fetch_profile(&self, username: &str, limit: usize) -> Result<HnProfile> {
+        let url = format!("{}/user/{}.json", self.api_base, username);
+        let user = self
+            .client
+            .get(&url)
+            .send()
+            .await?
+            .json::<Option<HnUser>>()
+            .await
+            .map_err(|e| ProviderError::Parse(e.to_string()))?
+            .ok_or_else(|| ProviderError::Other(format!("No HN user '{}'", username)))?;
+
+        let futures: Vec<_> = user
+            .submitted
+            .iter()
+            .take(limit)
+            .map(|&id| self.fetch_item(id))
+            .collect();
+        let mut submissions = Vec::new();
+        let mut comments = Vec::new();
+        for item in join_all(futures).await.into_iter().flatten() {
+            if item.deleted.unwrap_or(false) || item.dead.unwrap_or(false) {
+                continue;
+            }
+            if item.item_type == "comment" {
+                let created_at = DateTime::from_timestamp(item.time, 0).unwrap_or_else(Utc::now);
+                let text = item.text.unwrap_or_default();
+                let mut comment = Comment::new(
+                    item.id.to_string(),
+                    user.id.clone(),
+                    text.clone(),
+                    created_at,
+                );
+                comment.text_plain = Some(html2text::from_read(text.as_bytes(), 80));
+                comments.push(comment);
+            } else if item.title.is_some() {
+                submissions.push(self.convert_to_feed_item(item));
+            }
+        }
+
+        Ok(HnProfile {
+            user,
+            submissions,
+            comments,
+        })
+    }
+
+    /// The story a comment belongs to, found by walking up its parents
+    pub async fn fetch_story_for(&self, comment_id: u64) -> Result<FeedItem> {
+        let mut id = comment_id;
+        // Threads deeper than this are vanishingly rare
+        for _ in 0..64 {
+            let item = self.fetch_item(id).await?;
+            match item.parent {
+                Some(parent) if item.item_type == "comment" => id = parent,
+                _ => return Ok(self.convert_to_feed_item(item)),
+            }
+        }
+        Err(ProviderError::Other(format!(
+            "No story found above comment {}",
+            comment_id
+        )))
+    }
+
+    /// Fetch comments for a story by ID
+    pub async fn fetch_comments(&self, story_id: u64, max_depth: u32) -> Result<Vec<Comment>> {
+        let item = self.fetch_item(story_id).await?;
+
+        let comment_ids = item.kids.unwrap_or_default();
+        if comment_ids.is_empty() {
+            return Ok(vec![]);
+        }
+
+        // Fetch top-level comments in parallel
+        let futures: Vec<_> = comment_ids
+            .into_iter()
+            .take(20) // Limit to 20 top-level comments
+            .map(|id| self.fetch_comment_tree(id, 0, max_depth))
+            .collect();
+
+        let results = join_all(futures).await;
+
+        let comments: Vec<Comment> = results.into_iter().flatten().collect();
+
+        Ok(comments)
+    }
+
+    /// Recursively fetch a comment and its replies
+    async fn fetch_comment_tree(
+        &self,
+        comment_id: u64,
+        depth: u32,
+        max_depth: u32,
+    ) -> Option<Comment> {
+        if depth > max_depth {
+            return None;
+        }
+
+        let item = self.fetch_item(comment_id).await.ok()?;
+
+        // Skip deleted/dead comments
+        if item.deleted.unwrap_or(false) || item.dead.unwrap_or(false) {
+            return None;
+        }
+
+        let author = item.by.unwrap_or_else(|| "[deleted]".to_string());
+        let text = item.text.unwrap_or_default();
+        let text_plain = html2text::from_read(text.as_bytes(), 80);
+
+        let created_at = DateTime::from_timestamp(item.time, 0).unwrap_or_else(Utc::now);
+
+        let mut comment = Comment::new(comment_id.to_string(), author, text.clone(), created_at);
+        comment.text_plain = Some(text_plain);
+        comment.depth = depth;
+
+        // Fetch replies (limit depth for performance)
+        if depth < max_depth {
+            if let Some(kid_ids) = item.kids {
+                let futures: Vec<_> = kid_ids
+                    .into_iter()
+                    .take(10) // Limit replies per comment
+                    .map(|id| self.fetch_comment_tree(id, depth + 1, max_depth))
+                    .collect();
+
+                let replies = join_all(futures).await;
+                comment.replies = replies.into_iter().flatten().collect();
+            }
+        }
+
+        Some(comment)
+    }
+}
+
+#[async_trait]
+impl FeedProvider for HackerNewsProvider {
+    fn id(&self) -> &str {
+        "hackernews"
+    }
+
+    fn name(&self) -> &str {
+        "Hacker News"
+    }
+
+    fn description(&self) -> &str {
+        "Tech news and discussions from Y Combinator"
+    }
+
+    fn icon(&self) -> &str {
+        "[HN]"
+    }
+
+    fn status(&self) -> ProviderStatus {
+        if self.enabled {
+            ProviderStatus::Ready
+        } else {
+            ProviderStatus::Disabled
+        }
+    }
+
+    fn categories(&self) -> Vec<&str> {
+        vec!["top", "new", "best", "ask", "show", "job"]
+    }
+
+    async fn fetch_items(&self, limit: usize) -> Result<Vec<FeedItem>> {
+        if let Some(search) = &self.search {
+            return self.search_items(search, 0, limit).await;
+        }
+
+        let ids = self.fetch_story_ids(limit).await?;
+
+        // Parallel fetch with batching (10 concurrent requests)
+        let batch_size = 10;
+        let mut all_items = Vec::with_capacity(ids.len());
+
+        for chunk in ids.chunks(batch_size) {
+            let futures: Vec<_> = chunk.iter().map(|&id| self.fetch_item(id)).collect();
+
+            let results = join_all(futures).await;
+
+            for item in results.into_iter().flatten() {
+                // Skip deleted/dead items
+                if item.deleted.unwrap_or(false) || item.dead.unwrap_or(false) {
+                    continue;
+                }
+                all_items.push(self.convert_to_feed_item(item));
+            }
+        }
+
+        Ok(all_items)
+    }
+
+    fn supports_search(&self) -> bool {
+        true // HN search via Algolia
+    }
+
+    async fn search(&self, query: &str, limit: usize) -> Result<Vec<FeedItem>> {
+        let search = HnSearch::parse(query).map_err(|e| ProviderError::Other(e.to_string()))?;
+        self.search_items(&search, 0, limit).await
+    }
+
+    fn supports_offset(&self) -> bool {
+        true // HN supports infinite scroll
+    }
+
+    async fn fetch_items_with_offset(&self, offset: usize, limit: usize) -> Result<Vec<FeedItem>> {
+        match &self.search {
+            Some(search) => self.search_items(search, offset, limit).await,
+            None => self.fetch_offset_items(offset, limit).await,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_provider_ready() {
+        let provider = HackerNewsProvider::new(None).unwrap();
+        assert_eq!(provider.status(), ProviderStatus::Ready);
+    }
+
+    #[test]
+    fn test_category_parsing() {
+        assert!(matches!(HnCategory::parse("top"), HnCategory::Top));
+        assert!(matches!(HnCategory::parse("show"), HnCategory::Show));
+        assert!(matches!(HnCategory::parse("unknown"), HnCategory::Top));
+    }
+
+    #[test]
+    fn test_search_parse() {
+        let search =
+            HnSearch::parse("rust async by:pg type:comment,ask points:100 comments:>10 sort:date")
+                .unwrap();
+        assert_eq!(search.query, "rust async");
+        assert!(search.by_date);
+        assert_eq!(search.endpoint(), "search_by_date");
+        assert_eq!(search.tags(), "(comment,ask_hn),author_pg");
+        assert_eq!(
+            search.numeric_filters(),
+            vec!["points>=100", "num_comments>=10"]
+        );
+
+        let plain = HnSearch::parse("10:30 standup since:7d").unwrap();
+        assert_eq!(plain.query, "10:30 standup");
+        assert_eq!(plain.tags(), "story");
+        assert!(plain.since.is_some());
+
+        assert_eq!(
+            HnSearch::parse("type:tweet"),
+            Err(HnSearchError::UnknownType("tweet".to_string()))
+        );
+        assert!(HnSearch::parse("points:many").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_search_paging_and_comments() {
+        let mut server = mockito::Server::new_async().await;
+        let hit = |id: u32| {
+            format!(
+                r#"{{"objectID": "{id}", "_tags": ["story", "author_pg", "story_{id}"],
+                    "title": "Story {id}", "url": "https://example.com/{id}", "author": "pg",
+                    "points": 120, "num_comments": 30, "created_at_i": 1767225600}}"#
+            )
+        };
+        let comment = r#"{"objectID": "900", "_tags": ["comment", "author_dang", "story_3"],
+            "author": "dang", "comment_text": "<p>Please keep it <i>civil</i>.</p>",
+            "story_id": 3, "story_title": "Story 3", "created_at_i": 1767225600}"#;
+        let mut page = |page: usize, hits: &[String]| {
+            server
+                .mock("GET", "/search_by_date")
+                .match_query(mockito::Matcher::AllOf(vec![
+                    mockito::Matcher::UrlEncoded("query".into(), "rust".into()),
+                    mockito::Matcher::UrlEncoded("tags".into(), "(story,comment)".into()),
+                    mockito::Matcher::UrlEncoded("page".into(), page.to_string()),
+                    mockito::Matcher::UrlEncoded("hitsPerPage".into(), "2".into()),
+                    mockito::Matcher::UrlEncoded("numericFilters".into(), "points>=50".into()),
+                ]))
+                .with_body(format!(
+                    r#"{{"hits": [{}], "nbPages": 3, "page": {page}}}"#,
+                    hits.join(",")
+                ))
+                .create()
+        };
+        let _first = page(0, &[hit(1), hit(2)]);
+        let _second = page(1, &[hit(3), comment.to_string()]);
+
+        let search = HnSearch::parse("rust type:story,comment points:50 sort:date").unwrap();
+        let provider = HackerNewsProvider::new(None)
+            .unwrap()
+            .with_search_base_url(&server.url())
+            .with_search(search);
+
+        let items = provider.fetch_items(2).await.unwrap();
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].metadata.hn_id, Some(1));
+        assert_eq!(items[0].metadata.score, Some(120));
+
+        // Offset 1 spans pages 0 and 1
+        let more = provider.fetch_items_with_offset(1, 2).await.unwrap();
+        let ids: Vec<&str> = more.iter().map(|i| i.id.as_str()).collect();
+        assert_eq!(ids, vec!["2", "3"]);
+
+        let page_two = provider.fetch_items_with_offset(2, 2).await.unwrap();
+        let reply = &page_two[1];
+        assert_eq!(reply.title, "dang on: Story 3");
+        assert_eq!(reply.source, "HN comment");
+        assert_eq!(reply.metadata.hn_id, Some(3));
+        assert_eq!(
+            reply.url.as_deref(),
+            Some("https://news.ycombinator.com/item?id=3#900")
+        );
+        assert_eq!(reply.summary.as_deref(), Some("Please keep it *civil*."));
+    }
+
+    #[tokio::test]
+    async fn test_find_by_url() {
+        let mut server = mockito::Server::new_async().await;
+        let _mock = server
+            .mock("GET", "/search")
+            .match_query(mockito::Matcher::AllOf(vec![
+                mockito::Matcher::UrlEncoded(
+                    "query".into(),
+                    "https://example.com/post?utm_source=x".into(),
+                ),
+                mockito::Matcher::UrlEncoded("restrictSearchableAttributes".into(), "url".into()),
+            ]))
+            .with_body(
+                r#"{"hits": [
+                    {"objectID": "7", "_tags": ["story"], "title": "Same link",
+                     "url": "http://www.example.com/post/", "num_comments": 12},
+                    {"objectID": "8", "_tags": ["story"], "title": "Other post",
+                     "url": "https://example.com/post-2"}
+                ], "nbPages": 1}"#,
+            )
+            .create_async()
+            .await;
+
+        let provider = HackerNewsProvider::new(None)
+            .unwrap()
+            .with_search_base_url(&server.url());
+        let found = provider
+            .find_by_url("https://example.com/post?utm_source=x")
+            .await
+            .unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(
+            found[0].discussion_url().as_deref(),
+            Some("https://news.ycombinator.com/item?id=7")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fetch_profile() {
+        let mut server = mockito::Server::new_async().await;
+        let mut mocks = vec![
+            server
+                .mock("GET", "/user/pg.json")
+                .with_body(
+                    r#"{"id": "pg", "created": 1160418092, "karma": 155000,
+                    "about": "Bug fixer.", "submitted": [5, 4, 3, 2, 1]}"#,
+                )
+                .create_async()
+                .await,
+        ];
+        let items = [
+            (
+                5,
+                r#"{"id": 5, "type": "comment", "by": "pg", "time": 1700000500, "parent": 1, "text": "Agreed, <i>mostly</i>."}"#,
+            ),
+            (
+                4,
+                r#"{"id": 4, "type": "comment", "by": "pg", "time": 1700000400, "deleted": true}"#,
+            ),
+            (
+                3,
+                r#"{"id": 3, "type": "story", "by": "pg", "time": 1700000300, "title": "Flagged", "dead": true}"#,
+            ),
+            (
+                2,
+                r#"{"id": 2, "type": "story", "by": "pg", "time": 1700000200, "title": "Hackers and painters", "score": 120, "descendants": 40}"#,
+            ),
+        ];
+        for (id, body) in items {
+            mocks.push(
+                server
+                    .mock("GET", format!("/item/{}.json", id).as_str())
+                    .with_body(body)
+                    .create_async()
+                    .await,
+            );
+        }
+
+        let provider = HackerNewsProvider::new(None)
+            .unwrap()
+            .with_api_base_url(&server.url());
+        // Only the four newest submissions are fetched; item 1 is never requested
+        let profile = provider.fetch_profile("pg", 4).await.unwrap();
+        assert_eq!(profile.user.karma, 155000);
+        assert_eq!(profile.submissions.len(), 1);
+        assert_eq!(profile.submissions[0].title, "Hackers and painters");
+        assert_eq!(profile.comments.len(), 1);
+        assert_eq!(profile.comments[0].id, "5");
+        assert_eq!(profile.comments[0].author, "pg");
+        for mock in mocks {
+            mock.assert_async().await;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fetch_story_for_walks_parents() {
+        let mut server = mockito::Server::new_async().await;
+        let items = [
+            (
+                30,
+                r#"{"id": 30, "type": "comment", "time": 1700000300, "parent": 20, "text": "Reply"}"#,
+            ),
+            (
+                20,
+                r#"{"id": 20, "type": "comment", "time": 1700000200, "parent": 10, "text": "Top comment"}"#,
+            ),
+            (
+                10,
+                r#"{"id": 10, "type": "story", "by": "dang", "time": 1700000100, "title": "Launch HN: Finterm", "kids": [20]}"#,
+            ),
+        ];
+        let mut mocks = Vec::new();
+        for (id, body) in items {
+            mocks.push(
+                server
+                    .mock("GET", format!("/item/{}.json", id).as_str())
+                    .with_body(body)
+                    .expect(1)
+                    .create_async()
+                    .await,
+            );
+        }
+
+        let provider = HackerNewsProvider::new(None)
+            .unwrap()
+            .with_api_base_url(&server.url());
+        let story = provider.fetch_story_for(30).await.unwrap();
+        assert_eq!(story.id, "10");
+        assert_eq!(story.title, "Launch HN: Finterm");
+        for mock in mocks {
+            mock.assert_async().await;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fetch_story_ids() {
+        let provider = HackerNewsProvider::new(None).unwrap();
+        let ids = provider.fetch_story_ids(5).await;
+        assert!(ids.is_ok());
+        assert!(ids.unwrap().len() <= 5);
+    }
+}
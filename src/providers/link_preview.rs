@@ -4,7 +4,15 @@
 
 use crate::models::LinkPreview;
 use reqwest::Client;
+use scraper::{ElementRef, Html, Selector};
 use std::time::Duration;
+use url::Url;
+
+/// Average reading speed used for reading time estimates
+const WORDS_PER_MINUTE: usize = 200;
+
+/// Elements whose text is never shown to a reader
+const HIDDEN_TAGS: &[&str] = &["script", "style", "noscript", "template", "head"];
 
 /// Fetch link preview data from a URL
 pub async fn fetch_link_preview(client: &Client, url: &str) -> Option<LinkPreview> {
@@ -16,29 +24,49 @@ pub async fn fetch_link_preview(client: &Client, url: &str) -> Option<LinkPrevie
         .await
         .ok()?;
 
+    // Resolve relative URLs against where we actually ended up after redirects
+    let final_url = response.url().clone();
     let html = response.text().await.ok()?;
-    parse_open_graph(&html)
+    parse_open_graph(&html, Some(&final_url))
+}
+
+/// Whether a URL points at an external page worth extracting
+///
+/// Discussion permalinks (Reddit self-posts, HN items) are rendered by
+/// JavaScript or carry no article body, so they are skipped.
+pub fn is_extractable_url(url: &str) -> bool {
+    let lower = url.to_lowercase();
+    (lower.starts_with("http://") || lower.starts_with("https://"))
+        && !lower.contains("reddit.com/")
+        && !lower.contains("news.ycombinator.com/")
+        && !lower.ends_with(".pdf")
 }
 
 /// Parse Open Graph metadata from HTML
-fn parse_open_graph(html: &str) -> Option<LinkPreview> {
-    let title =
-        extract_meta_content(html, "og:title").or_else(|| extract_tag_content(html, "title"));
-    let description = extract_meta_content(html, "og:description")
-        .or_else(|| extract_meta_content(html, "description"));
-    let image_url = extract_meta_content(html, "og:image");
-    let site_name = extract_meta_content(html, "og:site_name");
-    let content_type = extract_meta_content(html, "og:type");
-
-    // Estimate reading time from content length
-    let word_count = html.split_whitespace().count();
+fn parse_open_graph(html: &str, page_url: Option<&Url>) -> Option<LinkPreview> {
+    let document = Html::parse_document(html);
+    let base_url = base_href(&document, page_url);
+
+    let title = extract_meta_content(&document, "og:title")
+        .or_else(|| extract_tag_content(&document, "title"));
+    let description = extract_meta_content(&document, "og:description")
+        .or_else(|| extract_meta_content(&document, "description"));
+    let image_url = extract_meta_content(&document, "og:image")
+        .or_else(|| extract_meta_content(&document, "twitter:image"))
+        .and_then(|src| resolve_url(base_url.as_ref(), &src));
+    let site_name = extract_meta_content(&document, "og:site_name");
+    let content_type = extract_meta_content(&document, "og:type");
+    let favicon_url = extract_favicon(&document, base_url.as_ref());
+
+    // Estimate reading time from the text a reader would actually see
+    let word_count = visible_word_count(&document);
     let reading_time = if word_count > 100 {
-        Some((word_count / 200).max(1) as u32)
+        Some(word_count.div_ceil(WORDS_PER_MINUTE) as u32)
     } else {
         None
     };
 
-    let content_snippet = extract_first_paragraph(html);
+    let content_snippet = extract_first_paragraph(&document);
 
     if title.is_some() || description.is_some() {
         Some(LinkPreview {
@@ -49,81 +77,211 @@ fn parse_open_graph(html: &str) -> Option<LinkPreview> {
             content_type,
             reading_time,
             content_snippet,
-            favicon_url: None,
+            favicon_url,
         })
     } else {
         None
     }
 }
 
-/// Extract meta tag content by property
-fn extract_meta_content(html: &str, property: &str) -> Option<String> {
-    // Look for <meta property="og:..." content="...">
-    let pattern1 = format!(r#"property="{}""#, property);
-    let pattern2 = format!(r#"name="{}""#, property);
-
-    for pattern in [pattern1, pattern2] {
-        if let Some(pos) = html.find(&pattern) {
-            let chunk = &html[pos..];
-            if let Some(content_start) = chunk.find("content=\"") {
-                let content_chunk = &chunk[content_start + 9..];
-                if let Some(end) = content_chunk.find('"') {
-                    let content = &content_chunk[..end];
-                    if !content.is_empty() {
-                        return Some(decode_html_entities(content));
-                    }
-                }
-            }
-        }
+/// Extract meta tag content by property or name
+fn extract_meta_content(document: &Html, property: &str) -> Option<String> {
+    let selector = Selector::parse("meta").expect("valid selector");
+
+    document
+        .select(&selector)
+        .filter(|meta| {
+            let el = meta.value();
+            el.attr("property")
+                .or_else(|| el.attr("name"))
+                .map(|p| p.eq_ignore_ascii_case(property))
+                .unwrap_or(false)
+        })
+        .filter_map(|meta| meta.value().attr("content"))
+        .map(|content| content.trim())
+        .find(|content| !content.is_empty())
+        .map(|content| content.to_string())
+}
+
+/// Extract text content from the first matching tag like <title>...</title>
+fn extract_tag_content(document: &Html, tag: &str) -> Option<String> {
+    let selector = Selector::parse(tag).ok()?;
+    let text = collapse_whitespace(
+        &document
+            .select(&selector)
+            .next()?
+            .text()
+            .collect::<String>(),
+    );
+
+    if text.is_empty() {
+        None
+    } else {
+        Some(text)
     }
-    None
 }
 
-/// Extract content from a tag like <title>...</title>
-fn extract_tag_content(html: &str, tag: &str) -> Option<String> {
-    let start_tag = format!("<{}", tag);
-    let end_tag = format!("</{}>", tag);
-
-    if let Some(start) = html.find(&start_tag) {
-        let after_start = &html[start..];
-        if let Some(close_bracket) = after_start.find('>') {
-            let content_start = &after_start[close_bracket + 1..];
-            if let Some(end) = content_start.find(&end_tag) {
-                let content = content_start[..end].trim();
-                if !content.is_empty() {
-                    return Some(decode_html_entities(content));
-                }
-            }
+/// Extract first meaningful paragraph of content
+fn extract_first_paragraph(document: &Html) -> Option<String> {
+    let selector = Selector::parse("p").expect("valid selector");
+
+    document
+        .select(&selector)
+        .map(|p| collapse_whitespace(&p.text().collect::<String>()))
+        .find(|text| text.len() > 20)
+        .map(|text| text.chars().take(300).collect())
+}
+
+/// Find the page favicon, falling back to /favicon.ico on the page's host
+fn extract_favicon(document: &Html, base_url: Option<&Url>) -> Option<String> {
+    let selector = Selector::parse("link[rel][href]").expect("valid selector");
+
+    let declared = document.select(&selector).find_map(|link| {
+        let el = link.value();
+        let rel = el.attr("rel")?.to_lowercase();
+        let is_icon = rel
+            .split_whitespace()
+            .any(|r| r == "icon" || r == "apple-touch-icon");
+        if is_icon {
+            el.attr("href")
+        } else {
+            None
         }
+    });
+
+    match declared {
+        Some(href) => resolve_url(base_url, href),
+        None => base_url
+            .and_then(|base| base.join("/favicon.ico").ok())
+            .map(|u| u.to_string()),
     }
-    None
 }
 
-/// Extract first paragraph of content
-fn extract_first_paragraph(html: &str) -> Option<String> {
-    if let Some(start) = html.find("<p") {
-        let after_p = &html[start..];
-        if let Some(close) = after_p.find('>') {
-            let content = &after_p[close + 1..];
-            if let Some(end) = content.find("</p>") {
-                let text = &content[..end];
-                let plain = html2text::from_read(text.as_bytes(), 200);
-                let trimmed = plain.trim();
-                if !trimmed.is_empty() && trimmed.len() > 20 {
-                    return Some(trimmed.chars().take(300).collect());
-                }
-            }
-        }
+/// Base URL for relative links, honoring a <base href> in the document
+fn base_href(document: &Html, page_url: Option<&Url>) -> Option<Url> {
+    let selector = Selector::parse("base[href]").expect("valid selector");
+    let declared = document
+        .select(&selector)
+        .next()
+        .and_then(|base| base.value().attr("href"));
+
+    match (declared, page_url) {
+        (Some(href), Some(page)) => page.join(href).ok().or_else(|| Some(page.clone())),
+        (Some(href), None) => Url::parse(href).ok(),
+        (None, page) => page.cloned(),
     }
-    None
 }
 
-/// Decode basic HTML entities
-fn decode_html_entities(s: &str) -> String {
-    s.replace("&amp;", "&")
-        .replace("&lt;", "<")
-        .replace("&gt;", ">")
-        .replace("&quot;", "\"")
-        .replace("&#39;", "'")
-        .replace("&apos;", "'")
+/// Resolve a possibly relative URL, keeping absolute URLs untouched
+fn resolve_url(base_url: Option<&Url>, href: &str) -> Option<String> {
+    let href = href.trim();
+    if href.is_empty() || href.starts_with("data:") {
+        return None;
+    }
+
+    match Url::parse(href) {
+        Ok(url) => Some(url.to_string()),
+        Err(_) => base_url?.join(href).ok().map(|u| u.to_string()),
+    }
+}
+
+/// Count words in text that is rendered to the reader
+fn visible_word_count(document: &Html) -> usize {
+    document
+        .root_element()
+        .descendants()
+        .filter_map(|node| node.value().as_text().map(|text| (node, text)))
+        .filter(|(node, _)| {
+            !node
+                .ancestors()
+                .filter_map(ElementRef::wrap)
+                .any(|el| HIDDEN_TAGS.contains(&el.value().name()))
+        })
+        .map(|(_, text)| text.split_whitespace().count())
+        .sum()
+}
+
+/// Collapse runs of whitespace into single spaces
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn page(body_words: usize) -> String {
+        format!(
+            r#"<html><head>
+            <title>Fallback title</title>
+            <meta content="Quarterly results beat estimates" property="og:title">
+            <meta name="description" content="Revenue rose 12% &amp; margins widened">
+            <meta property="og:site_name" content="Market Wire">
+            <meta property="og:type" content="article">
+            <meta property="og:image" content="/img/cover.png">
+            <link rel="shortcut icon" href="static/favicon.png">
+            <script>var tracking = "{}";</script>
+            </head><body><p>{}</p></body></html>"#,
+            "noise ".repeat(5000),
+            "word ".repeat(body_words)
+        )
+    }
+
+    #[test]
+    fn test_parse_open_graph_fields() {
+        let base = Url::parse("https://news.example.com/markets/2026/story.html").unwrap();
+        let preview = parse_open_graph(&page(450), Some(&base)).unwrap();
+
+        assert_eq!(
+            preview.title.as_deref(),
+            Some("Quarterly results beat estimates")
+        );
+        assert_eq!(
+            preview.description.as_deref(),
+            Some("Revenue rose 12% & margins widened")
+        );
+        assert_eq!(preview.site_name.as_deref(), Some("Market Wire"));
+        assert_eq!(preview.content_type.as_deref(), Some("article"));
+    }
+
+    #[test]
+    fn test_reading_time_ignores_scripts() {
+        let preview = parse_open_graph(&page(450), None).unwrap();
+        assert_eq!(preview.reading_time, Some(3));
+
+        let short = parse_open_graph(&page(20), None).unwrap();
+        assert_eq!(short.reading_time, None);
+    }
+
+    #[test]
+    fn test_resolves_relative_urls() {
+        let base = Url::parse("https://news.example.com/markets/2026/story.html").unwrap();
+        let preview = parse_open_graph(&page(10), Some(&base)).unwrap();
+
+        assert_eq!(
+            preview.image_url.as_deref(),
+            Some("https://news.example.com/img/cover.png")
+        );
+        assert_eq!(
+            preview.favicon_url.as_deref(),
+            Some("https://news.example.com/markets/2026/static/favicon.png")
+        );
+    }
+
+    #[test]
+    fn test_favicon_fallback_and_base_href() {
+        let html = r#"<html><head><base href="https://cdn.example.org/assets/">
+            <title>Hello</title><meta property="og:image" content="hero.jpg"></head></html>"#;
+        let base = Url::parse("https://example.org/post").unwrap();
+        let preview = parse_open_graph(html, Some(&base)).unwrap();
+
+        assert_eq!(
+            preview.image_url.as_deref(),
+            Some("https://cdn.example.org/assets/hero.jpg")
+        );
+        assert_eq!(
+            preview.favicon_url.as_deref(),
+            Some("https://cdn.example.org/favicon.ico")
+        );
+    }
 }
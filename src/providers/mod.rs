@@ -8,11 +8,13 @@ pub mod cratesio;
 pub mod finnhub;
 pub mod hackernews;
 pub mod link_preview;
+pub mod readability;
 pub mod reddit;
 pub mod registry;
 
-use crate::models::FeedItem;
+use crate::models::{Comment, FeedItem};
 use async_trait::async_trait;
+use serde::Serialize;
 use std::fmt;
 use thiserror::Error;
 
@@ -47,7 +49,8 @@ impl From<reqwest::Error> for ProviderError {
 pub type Result<T> = std::result::Result<T, ProviderError>;
 
 /// Provider status for UI display
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "state", content = "message", rename_all = "snake_case")]
 pub enum ProviderStatus {
     /// Provider is configured and ready
     Ready,
@@ -126,10 +129,67 @@ pub trait FeedProvider: Send + Sync {
     }
 }
 
+/// Fetch the discussion thread for an item from the site it came from
+pub async fn fetch_comments(item: &FeedItem, max_depth: u32) -> Result<Vec<Comment>> {
+    match item.provider_id.as_str() {
+        "hackernews" => {
+            let hn_id = item
+                .metadata
+                .hn_id
+                .ok_or_else(|| ProviderError::Other("Missing HN item id".to_string()))?;
+            HackerNewsProvider::new(None)?
+                .fetch_comments(hn_id, max_depth)
+                .await
+        }
+        "reddit" => match (&item.metadata.subreddit, &item.metadata.reddit_id) {
+            (Some(subreddit), Some(post_id)) => {
+                RedditProvider::new(vec![], None, true)?
+                    .fetch_comments(subreddit, post_id, max_depth)
+                    .await
+            }
+            _ => Err(ProviderError::Other("Missing Reddit post id".to_string())),
+        },
+        _ => Err(ProviderError::Other(
+            "Comments not available for this source".to_string(),
+        )),
+    }
+}
+
+/// Every HN story and Reddit submission of a link, most discussed first
+///
+/// Fails only when both lookups do; a single failure is logged.
+pub async fn find_discussions(url: &str) -> Result<Vec<FeedItem>> {
+    let hn = HackerNewsProvider::new(None)?;
+    let reddit = RedditProvider::new(vec![], None, true)?;
+    let (hn, reddit) = tokio::join!(hn.find_by_url(url), reddit.find_by_url(url));
+
+    let mut items = match (hn, reddit) {
+        (Err(e), Err(_)) => return Err(e),
+        (hn, reddit) => {
+            let mut items = Vec::new();
+            for (source, result) in [("Hacker News", hn), ("Reddit", reddit)] {
+                match result {
+                    Ok(found) => items.extend(found),
+                    Err(e) => tracing::warn!("{} discussion lookup failed: {}", source, e),
+                }
+            }
+            items
+        }
+    };
+
+    items.sort_by_key(|item| {
+        std::cmp::Reverse((
+            item.metadata.comments.unwrap_or(0),
+            item.metadata.score.unwrap_or(0),
+        ))
+    });
+    Ok(items)
+}
+
 // Re-export main types
-pub use arxiv::ArxivProvider;
+pub use arxiv::{ArxivProvider, ArxivSearch};
 pub use cratesio::CratesIoProvider;
 pub use finnhub::FinnhubProvider;
-pub use hackernews::HackerNewsProvider;
-pub use reddit::RedditProvider;
+pub use hackernews::{HackerNewsProvider, HnLive, HnProfile, HnSearch, HnUpdate};
+pub use reddit::{RedditApi, RedditProvider, RedditScope};
 pub use registry::ProviderRegistry;
@@ -0,0 +1,437 @@
+//! Readability-style article extractor
+//!
+//! Fetches a linked page and pulls out its main article body, keeping
+//! headings, lists, block quotes and code blocks as plain text.
+
+use reqwest::Client;
+use scraper::{ElementRef, Html, Node, Selector};
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Pages yielding fewer words than this are treated as having no article
+const MIN_ARTICLE_WORDS: usize = 50;
+
+/// Paragraphs shorter than this (in characters) do not contribute to scoring
+const MIN_PARAGRAPH_CHARS: usize = 25;
+
+/// Elements that never contain article text
+const SKIPPED_TAGS: &[&str] = &[
+    "script", "style", "noscript", "nav", "aside", "footer", "form", "button", "iframe", "svg",
+    "select", "textarea", "template", "head",
+];
+
+/// class/id fragments that mark boilerplate (comments, sidebars, share bars...)
+const NEGATIVE_HINTS: &[&str] = &[
+    "comment",
+    "sidebar",
+    "footer",
+    "footnote-nav",
+    "menu",
+    "nav",
+    "share",
+    "social",
+    "related",
+    "promo",
+    "sponsor",
+    "advert",
+    "cookie",
+    "newsletter",
+    "subscribe",
+    "widget",
+    "masthead",
+    "breadcrumb",
+];
+
+/// class/id fragments that mark likely article containers
+const POSITIVE_HINTS: &[&str] = &[
+    "article", "body", "content", "entry", "main", "post", "story", "text",
+];
+
+/// Fetch a page and extract its main article text
+pub async fn fetch_article_content(client: &Client, url: &str) -> Option<String> {
+    let response = client
+        .get(url)
+        .timeout(Duration::from_secs(10))
+        .header("User-Agent", "Mozilla/5.0 (compatible; FinTerm/0.3.0)")
+        .send()
+        .await
+        .ok()?;
+
+    // Skip PDFs, images and other non-HTML payloads
+    let is_html = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .map(|ct| ct.contains("html"))
+        .unwrap_or(true);
+    if !is_html {
+        return None;
+    }
+
+    let html = response.text().await.ok()?;
+    extract_article(&html)
+}
+
+/// Extract the main article body from an HTML document
+pub fn extract_article(html: &str) -> Option<String> {
+    let document = Html::parse_document(html);
+    let candidate = find_candidate(&document)?;
+
+    let mut renderer = Renderer::default();
+    renderer.render_children(candidate);
+    let text = renderer.finish();
+
+    if text.split_whitespace().count() < MIN_ARTICLE_WORDS {
+        return None;
+    }
+
+    Some(text)
+}
+
+/// Pick the element most likely to hold the article body
+fn find_candidate(document: &Html) -> Option<ElementRef<'_>> {
+    // Semantic markup wins when it holds a real amount of text
+    let semantic = Selector::parse(r#"[itemprop="articleBody"], article, [role="main"], main"#)
+        .expect("valid selector");
+    if let Some(el) = document
+        .select(&semantic)
+        .find(|el| paragraph_chars(*el) >= 500)
+    {
+        return Some(el);
+    }
+
+    // Otherwise score block containers by the paragraphs they hold
+    let paragraphs = Selector::parse("p, pre, td").expect("valid selector");
+    let mut scores = HashMap::new();
+
+    for p in document.select(&paragraphs) {
+        if is_unlikely(p) {
+            continue;
+        }
+        let text: String = p.text().collect();
+        let len = text.trim().chars().count();
+        if len < MIN_PARAGRAPH_CHARS {
+            continue;
+        }
+
+        let score = 1.0 + text.matches(',').count() as f64 + (len / 100).min(3) as f64;
+
+        let parent = p.parent().and_then(ElementRef::wrap);
+        if let Some(parent) = parent {
+            *scores
+                .entry(parent.id())
+                .or_insert_with(|| class_weight(parent)) += score;
+
+            if let Some(grandparent) = parent.parent().and_then(ElementRef::wrap) {
+                *scores
+                    .entry(grandparent.id())
+                    .or_insert_with(|| class_weight(grandparent)) += score / 2.0;
+            }
+        }
+    }
+
+    scores
+        .into_iter()
+        .filter_map(|(id, score)| {
+            let el = document.tree.get(id).and_then(ElementRef::wrap)?;
+            Some((el, score * (1.0 - link_density(el))))
+        })
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(el, _)| el)
+        .or_else(|| {
+            let body = Selector::parse("body").expect("valid selector");
+            document.select(&body).next()
+        })
+}
+
+/// Total length of paragraph text directly usable as article content
+fn paragraph_chars(el: ElementRef) -> usize {
+    let p = Selector::parse("p").expect("valid selector");
+    el.select(&p)
+        .map(|p| p.text().map(|t| t.trim().len()).sum::<usize>())
+        .sum()
+}
+
+/// Lowercased class and id attributes joined for hint matching
+fn class_and_id(el: ElementRef) -> String {
+    let value = el.value();
+    format!(
+        "{} {}",
+        value.attr("class").unwrap_or_default(),
+        value.id().unwrap_or_default()
+    )
+    .to_lowercase()
+}
+
+/// Starting score for a container based on its class/id
+fn class_weight(el: ElementRef) -> f64 {
+    let hints = class_and_id(el);
+    let mut weight = 0.0;
+    if POSITIVE_HINTS.iter().any(|h| hints.contains(h)) {
+        weight += 25.0;
+    }
+    if NEGATIVE_HINTS.iter().any(|h| hints.contains(h)) {
+        weight -= 25.0;
+    }
+    match el.value().name() {
+        "article" => weight + 10.0,
+        "div" | "section" | "main" => weight + 5.0,
+        _ => weight,
+    }
+}
+
+/// Whether the element or any ancestor looks like boilerplate
+fn is_unlikely(el: ElementRef) -> bool {
+    el.ancestors()
+        .filter_map(ElementRef::wrap)
+        .chain(std::iter::once(el))
+        .any(|e| {
+            SKIPPED_TAGS.contains(&e.value().name()) || {
+                let hints = class_and_id(e);
+                NEGATIVE_HINTS.iter().any(|h| hints.contains(h))
+                    && !POSITIVE_HINTS.iter().any(|h| hints.contains(h))
+            }
+        })
+}
+
+/// Share of an element's text that sits inside links
+fn link_density(el: ElementRef) -> f64 {
+    let total: usize = el.text().map(|t| t.trim().len()).sum();
+    if total == 0 {
+        return 1.0;
+    }
+    let a = Selector::parse("a").expect("valid selector");
+    let linked: usize = el
+        .select(&a)
+        .map(|a| a.text().map(|t| t.trim().len()).sum::<usize>())
+        .sum();
+    linked as f64 / total as f64
+}
+
+/// Converts an element subtree into readable blocks of plain text
+#[derive(Default)]
+struct Renderer {
+    blocks: Vec<String>,
+    inline: String,
+}
+
+impl Renderer {
+    fn render_children(&mut self, el: ElementRef) {
+        for child in el.children() {
+            match child.value() {
+                Node::Text(text) => self.push_text(text),
+                Node::Element(_) => {
+                    if let Some(child) = ElementRef::wrap(child) {
+                        self.render_element(child);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn render_element(&mut self, el: ElementRef) {
+        let name = el.value().name();
+        if SKIPPED_TAGS.contains(&name) {
+            return;
+        }
+        let hints = class_and_id(el);
+        if NEGATIVE_HINTS.iter().any(|h| hints.contains(h))
+            && !POSITIVE_HINTS.iter().any(|h| hints.contains(h))
+        {
+            return;
+        }
+
+        match name {
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                self.flush();
+                let level = name[1..].parse::<usize>().unwrap_or(1);
+                let text = collapse_whitespace(&el.text().collect::<String>());
+                if !text.is_empty() {
+                    self.blocks.push(format!("{} {}", "#".repeat(level), text));
+                }
+            }
+            "pre" => {
+                self.flush();
+                let code: String = el.text().collect();
+                let code = code.trim_end_matches(['\n', '\r', ' ']);
+                let code = code.trim_start_matches(['\n', '\r']);
+                if !code.trim().is_empty() {
+                    let indented: Vec<String> =
+                        code.lines().map(|l| format!("    {}", l)).collect();
+                    self.blocks.push(indented.join("\n"));
+                }
+            }
+            "blockquote" => {
+                self.flush();
+                let inner = self.render_nested(el);
+                if !inner.is_empty() {
+                    let quoted: Vec<String> = inner
+                        .lines()
+                        .map(|l| {
+                            if l.is_empty() {
+                                ">".to_string()
+                            } else {
+                                format!("> {}", l)
+                            }
+                        })
+                        .collect();
+                    self.blocks.push(quoted.join("\n"));
+                }
+            }
+            "ul" | "ol" => {
+                self.flush();
+                let ordered = name == "ol";
+                let mut lines = Vec::new();
+                let items = el
+                    .children()
+                    .filter_map(ElementRef::wrap)
+                    .filter(|c| c.value().name() == "li");
+                for (idx, li) in items.enumerate() {
+                    let marker = if ordered {
+                        format!("{}. ", idx + 1)
+                    } else {
+                        "- ".to_string()
+                    };
+                    let inner = self.render_nested(li);
+                    let pad = " ".repeat(marker.len());
+                    for (line_idx, line) in inner.lines().filter(|l| !l.is_empty()).enumerate() {
+                        if line_idx == 0 {
+                            lines.push(format!("{}{}", marker, line));
+                        } else {
+                            lines.push(format!("{}{}", pad, line));
+                        }
+                    }
+                }
+                if !lines.is_empty() {
+                    self.blocks.push(lines.join("\n"));
+                }
+            }
+            "tr" => {
+                self.flush();
+                let cells: Vec<String> = el
+                    .children()
+                    .filter_map(ElementRef::wrap)
+                    .filter(|c| matches!(c.value().name(), "td" | "th"))
+                    .map(|c| collapse_whitespace(&c.text().collect::<String>()))
+                    .filter(|c| !c.is_empty())
+                    .collect();
+                if !cells.is_empty() {
+                    self.blocks.push(cells.join(" | "));
+                }
+            }
+            "br" => self.inline.push('\n'),
+            "img" | "hr" => {}
+            "p" | "div" | "section" | "article" | "main" | "header" | "figure" | "figcaption"
+            | "table" | "tbody" | "thead" | "dl" | "dd" | "dt" | "li" => {
+                self.flush();
+                self.render_children(el);
+                self.flush();
+            }
+            _ => self.render_children(el),
+        }
+    }
+
+    /// Render an element's children on their own and return the joined text
+    fn render_nested(&self, el: ElementRef) -> String {
+        let mut nested = Renderer::default();
+        nested.render_children(el);
+        nested.finish_with("\n")
+    }
+
+    fn push_text(&mut self, text: &str) {
+        let starts_with_space = text.starts_with(char::is_whitespace);
+        let ends_with_space = text.ends_with(char::is_whitespace);
+        let collapsed = collapse_whitespace(text);
+
+        if collapsed.is_empty() {
+            if starts_with_space && !self.inline.is_empty() && !self.inline.ends_with(' ') {
+                self.inline.push(' ');
+            }
+            return;
+        }
+
+        if starts_with_space && !self.inline.is_empty() && !self.inline.ends_with([' ', '\n']) {
+            self.inline.push(' ');
+        }
+        self.inline.push_str(&collapsed);
+        if ends_with_space {
+            self.inline.push(' ');
+        }
+    }
+
+    /// Close the current paragraph, if any
+    fn flush(&mut self) {
+        let paragraph = self
+            .inline
+            .lines()
+            .map(|l| l.trim())
+            .collect::<Vec<_>>()
+            .join("\n");
+        let paragraph = paragraph.trim();
+        if !paragraph.is_empty() {
+            self.blocks.push(paragraph.to_string());
+        }
+        self.inline.clear();
+    }
+
+    fn finish(self) -> String {
+        self.finish_with("\n\n")
+    }
+
+    fn finish_with(mut self, separator: &str) -> String {
+        self.flush();
+        self.blocks.join(separator)
+    }
+}
+
+/// Collapse runs of whitespace into single spaces
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NEWS_ARTICLE: &str = include_str!("../../tests/fixtures/articles/news_article.html");
+    const BLOG_POST: &str = include_str!("../../tests/fixtures/articles/blog_post.html");
+    const LANDING_PAGE: &str = include_str!("../../tests/fixtures/articles/landing_page.html");
+
+    #[test]
+    fn test_extracts_article_body_without_boilerplate() {
+        let text = extract_article(NEWS_ARTICLE).expect("article should be extracted");
+
+        assert!(text.contains("## Why rates matter"));
+        assert!(text.contains("Treasury yields climbed for a third straight session"));
+        assert!(!text.contains("Subscribe to our newsletter"));
+        assert!(!text.contains("Most Popular"));
+        assert!(!text.contains("Great article, thanks"));
+        assert!(!text.contains("trackPageView"));
+    }
+
+    #[test]
+    fn test_keeps_lists_and_quotes() {
+        let text = extract_article(NEWS_ARTICLE).unwrap();
+
+        assert!(text.contains("- Core inflation rose 0.3% in September"));
+        assert!(text.contains("1. Watch the two-year yield"));
+        assert!(text.contains("2. Track the dollar index"));
+        assert!(text.contains("> We remain data dependent"));
+    }
+
+    #[test]
+    fn test_keeps_code_blocks_without_semantic_markup() {
+        let text = extract_article(BLOG_POST).expect("blog post should be extracted");
+
+        assert!(text.contains("# Parsing order books in Rust"));
+        assert!(text.contains("    fn best_bid(book: &OrderBook) -> Option<Price> {"));
+        assert!(text.contains("        book.bids.first().map(|level| level.price)"));
+        assert!(!text.contains("Related posts"));
+    }
+
+    #[test]
+    fn test_rejects_pages_without_article() {
+        assert!(extract_article(LANDING_PAGE).is_none());
+    }
+}
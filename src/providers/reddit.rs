@@ -1,19 +1,29 @@
-//! Reddit RSS provider
+//! Reddit provider
 //!
-//! Fetches posts from Reddit subreddits via their public RSS feeds.
+//! Fetches posts from subreddits via the public `.json` listings, which carry
+//! real scores, comment counts and post flags and page with `after` cursors.
+//! The RSS feeds remain available as a fallback (`api = "rss"`).
 
-use crate::models::{Comment, FeedItem, FeedItemMetadata};
+use crate::config::RedditConfig;
+use crate::models::{Comment, FeedItem, FeedItemMetadata, PostType};
 use crate::providers::{FeedProvider, ProviderError, ProviderStatus, Result};
+use crate::utils::markdown_to_text;
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use futures::future::join_all;
 use quick_xml::events::Event;
 use quick_xml::reader::Reader;
-use reqwest::Client;
+use reqwest::{Client, StatusCode};
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
 use std::time::Duration;
 
 const REDDIT_BASE_URL: &str = "https://www.reddit.com";
 
+/// Most posts Reddit returns per listing request
+const MAX_PAGE_SIZE: usize = 100;
+
 /// Reddit feed sort type
 #[derive(Debug, Clone, Copy, Default)]
 pub enum RedditSort {
@@ -34,6 +44,16 @@ impl RedditSort {
         }
     }
 
+    /// Listing name in `.json` URLs
+    pub fn as_slug(&self) -> &str {
+        match self {
+            RedditSort::Hot => "hot",
+            RedditSort::New => "new",
+            RedditSort::Top => "top",
+            RedditSort::Rising => "rising",
+        }
+    }
+
     pub fn as_str(&self) -> &str {
         match self {
             RedditSort::Hot => "Hot",
@@ -53,6 +73,304 @@ impl RedditSort {
     }
 }
 
+/// Time range for top listings (`?t=`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RedditTime {
+    Hour,
+    #[default]
+    Day,
+    Week,
+    Month,
+    Year,
+    All,
+}
+
+impl RedditTime {
+    pub fn as_str(&self) -> &str {
+        match self {
+            RedditTime::Hour => "hour",
+            RedditTime::Day => "day",
+            RedditTime::Week => "week",
+            RedditTime::Month => "month",
+            RedditTime::Year => "year",
+            RedditTime::All => "all",
+        }
+    }
+
+    pub fn parse(s: &str) -> Self {
+        match s.to_lowercase().as_str() {
+            "hour" => RedditTime::Hour,
+            "week" => RedditTime::Week,
+            "month" => RedditTime::Month,
+            "year" => RedditTime::Year,
+            "all" => RedditTime::All,
+            _ => RedditTime::Day,
+        }
+    }
+}
+
+/// What the Reddit feed shows: every configured subreddit, one, or a multireddit
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum RedditScope {
+    #[default]
+    All,
+    Subreddit(String),
+    Multi(String),
+}
+
+impl RedditScope {
+    /// Parse `all`, `r/name`, `name` or `m/group`
+    pub fn parse(s: &str) -> Option<Self> {
+        let s = s.trim().trim_start_matches('/');
+        if s.is_empty() || s.eq_ignore_ascii_case("all") {
+            return Some(RedditScope::All);
+        }
+        if let Some(multi) = s.strip_prefix("m/") {
+            return (!multi.is_empty()).then(|| RedditScope::Multi(multi.to_string()));
+        }
+        let name = s.strip_prefix("r/").unwrap_or(s);
+        is_valid_subreddit(name).then(|| RedditScope::Subreddit(name.to_string()))
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            RedditScope::All => "all".to_string(),
+            RedditScope::Subreddit(name) => format!("r/{}", name),
+            RedditScope::Multi(name) => format!("m/{}", name),
+        }
+    }
+
+    /// Name overrides are looked up under
+    fn key(&self) -> Option<&str> {
+        match self {
+            RedditScope::All => None,
+            RedditScope::Subreddit(name) | RedditScope::Multi(name) => Some(name),
+        }
+    }
+}
+
+/// Tabs for a config: all, then each multireddit, then each subreddit
+pub fn scopes(config: &RedditConfig) -> Vec<RedditScope> {
+    std::iter::once(RedditScope::All)
+        .chain(config.multireddits.keys().cloned().map(RedditScope::Multi))
+        .chain(
+            config
+                .subreddits
+                .iter()
+                .cloned()
+                .map(RedditScope::Subreddit),
+        )
+        .collect()
+}
+
+/// Subreddit names are 2-21 letters, digits or underscores
+pub fn is_valid_subreddit(name: &str) -> bool {
+    (2..=21).contains(&name.len()) && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Which Reddit endpoints posts are fetched from
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RedditApi {
+    /// `.json` listings: accurate metadata and paging
+    #[default]
+    Json,
+    /// RSS feeds: no scores, but tolerated where the JSON API is blocked
+    Rss,
+}
+
+impl RedditApi {
+    pub fn parse(s: &str) -> Self {
+        match s.to_lowercase().as_str() {
+            "rss" => RedditApi::Rss,
+            _ => RedditApi::Json,
+        }
+    }
+}
+
+/// A page of a `.json` listing
+#[derive(Debug, Deserialize)]
+struct Listing {
+    data: ListingData,
+}
+
+#[derive(Debug, Deserialize)]
+struct ListingData {
+    after: Option<String>,
+    children: Vec<ListingChild>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ListingChild {
+    data: RedditPost,
+}
+
+/// Post fields used from a listing entry
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct RedditPost {
+    id: String,
+    /// Fullname, e.g. "t3_abc123"
+    name: String,
+    title: String,
+    author: Option<String>,
+    subreddit: String,
+    score: i64,
+    num_comments: i64,
+    upvote_ratio: Option<f32>,
+    link_flair_text: Option<String>,
+    over_18: bool,
+    spoiler: bool,
+    is_self: bool,
+    is_video: bool,
+    is_gallery: bool,
+    post_hint: Option<String>,
+    crosspost_parent: Option<String>,
+    selftext: String,
+    url: Option<String>,
+    permalink: String,
+    thumbnail: Option<String>,
+    created_utc: f64,
+    gallery_data: Option<GalleryData>,
+    media_metadata: Option<HashMap<String, MediaMetadata>>,
+    secure_media: Option<Media>,
+    media: Option<Media>,
+    /// The original post, for crossposts
+    crosspost_parent_list: Vec<RedditPost>,
+}
+
+/// Order of the images in a gallery post
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct GalleryData {
+    items: Vec<GalleryItem>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct GalleryItem {
+    media_id: String,
+}
+
+/// A gallery image, keyed by media id
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct MediaMetadata {
+    /// Full-size source; `u` for stills, `gif` for animations
+    s: Option<MediaSource>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct MediaSource {
+    u: Option<String>,
+    gif: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct Media {
+    reddit_video: Option<RedditVideo>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct RedditVideo {
+    fallback_url: Option<String>,
+}
+
+impl RedditPost {
+    fn post_type(&self) -> PostType {
+        if self.crosspost_parent.is_some() {
+            PostType::Crosspost
+        } else if self.is_gallery {
+            PostType::Gallery
+        } else if self.is_video
+            || matches!(
+                self.post_hint.as_deref(),
+                Some("hosted:video" | "rich:video")
+            )
+        {
+            PostType::Video
+        } else if self.post_hint.as_deref() == Some("image") {
+            PostType::Image
+        } else if self.is_self {
+            PostType::Text
+        } else {
+            PostType::Link
+        }
+    }
+
+    /// The post carrying the content: the original for crossposts
+    fn origin(&self) -> &RedditPost {
+        self.crosspost_parent_list.first().unwrap_or(self)
+    }
+
+    /// Where the post points: the linked page, or the thread for self-posts
+    fn outbound_url(&self) -> String {
+        match &self.url {
+            Some(url) if !self.is_self && url.starts_with('/') => {
+                format!("{}{}", REDDIT_BASE_URL, url)
+            }
+            Some(url) if !self.is_self && !url.is_empty() => url.clone(),
+            _ => format!("{}{}", REDDIT_BASE_URL, self.permalink),
+        }
+    }
+
+    /// Image, gallery or video URLs in display order
+    fn media_urls(&self) -> Vec<String> {
+        match self.post_type() {
+            PostType::Gallery => {
+                let (Some(gallery), Some(metadata)) = (&self.gallery_data, &self.media_metadata)
+                else {
+                    return Vec::new();
+                };
+                gallery
+                    .items
+                    .iter()
+                    .filter_map(|item| metadata.get(&item.media_id)?.s.as_ref())
+                    .filter_map(|source| source.u.clone().or_else(|| source.gif.clone()))
+                    .collect()
+            }
+            PostType::Video => self
+                .secure_media
+                .iter()
+                .chain(&self.media)
+                .find_map(|media| media.reddit_video.as_ref()?.fallback_url.clone())
+                .or_else(|| self.url.clone().filter(|url| url.starts_with("http")))
+                .into_iter()
+                .collect(),
+            PostType::Image => self.url.iter().cloned().collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// Article text for a post: self-text followed by its media links
+///
+/// Link posts without text have none, so the linked page can be extracted.
+fn post_content(body: &str, post_type: PostType, media_urls: &[String]) -> Option<String> {
+    let media = match (post_type, media_urls) {
+        (_, []) => None,
+        (PostType::Gallery, urls) => Some(format!(
+            "Gallery ({} images):\n{}",
+            urls.len(),
+            urls.iter()
+                .enumerate()
+                .map(|(i, url)| format!("  {}. {}", i + 1, url))
+                .collect::<Vec<_>>()
+                .join("\n")
+        )),
+        (PostType::Video, [url, ..]) => Some(format!("Video: {}", url)),
+        (_, [url, ..]) => Some(format!("Image: {}", url)),
+    };
+
+    let parts: Vec<String> = [Some(body.to_string()).filter(|b| !b.is_empty()), media]
+        .into_iter()
+        .flatten()
+        .collect();
+    (!parts.is_empty()).then(|| parts.join("\n\n"))
+}
+
 /// Reddit RSS entry parsed from feed
 #[derive(Debug, Clone, Default)]
 struct RedditEntry {
@@ -64,13 +382,22 @@ struct RedditEntry {
     content: Option<String>,
 }
 
-/// Reddit RSS provider
+/// Reddit provider
 pub struct RedditProvider {
     client: Client,
+    base_url: String,
     subreddits: Vec<String>,
     sort: RedditSort,
+    time: RedditTime,
+    api: RedditApi,
     enabled: bool,
-    current_subreddit_index: usize,
+    multireddits: BTreeMap<String, Vec<String>>,
+    /// Sort and time range per subreddit or multireddit name
+    overrides: BTreeMap<String, (RedditSort, RedditTime)>,
+    scope: RedditScope,
+    /// `after` cursors keyed by how many posts precede them in the listing;
+    /// `None` marks the end
+    cursors: Mutex<BTreeMap<usize, Option<String>>>,
 }
 
 impl RedditProvider {
@@ -95,36 +422,289 @@ impl RedditProvider {
 
         Ok(Self {
             client,
+            base_url: REDDIT_BASE_URL.to_string(),
             subreddits: default_subreddits,
             sort: sort.map(|s| RedditSort::parse(&s)).unwrap_or_default(),
+            time: RedditTime::default(),
+            api: RedditApi::default(),
             enabled,
-            current_subreddit_index: 0,
+            multireddits: BTreeMap::new(),
+            overrides: BTreeMap::new(),
+            scope: RedditScope::default(),
+            cursors: Mutex::new(BTreeMap::new()),
         })
     }
 
-    /// Get current subreddit
-    pub fn current_subreddit(&self) -> &str {
-        self.subreddits
-            .get(self.current_subreddit_index)
-            .map(|s| s.as_str())
-            .unwrap_or("technology")
+    /// Create a provider from the `[reddit]` config section, showing `scope`
+    pub fn from_config(config: &RedditConfig, scope: RedditScope) -> Result<Self> {
+        let mut provider = Self::new(
+            config.subreddits.clone(),
+            Some(config.sort.clone()),
+            config.enabled,
+        )?
+        .with_api(RedditApi::parse(&config.api));
+
+        provider.time = RedditTime::parse(&config.time);
+        provider.multireddits = config.multireddits.clone();
+        provider.overrides = config
+            .overrides
+            .iter()
+            .map(|(name, o)| {
+                let sort = o
+                    .sort
+                    .as_deref()
+                    .map(RedditSort::parse)
+                    .unwrap_or(provider.sort);
+                let time = o
+                    .time
+                    .as_deref()
+                    .map(RedditTime::parse)
+                    .unwrap_or(provider.time);
+                (name.clone(), (sort, time))
+            })
+            .collect();
+        provider.scope = scope;
+        Ok(provider)
+    }
+
+    /// Builder method: choose the JSON listings or RSS feeds
+    pub fn with_api(mut self, api: RedditApi) -> Self {
+        self.api = api;
+        self
+    }
+
+    /// Builder method: point at another host (e.g. a local stand-in)
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    /// What the feed currently shows
+    pub fn scope(&self) -> &RedditScope {
+        &self.scope
     }
 
-    /// Set current subreddit by index
-    pub fn set_subreddit_index(&mut self, index: usize) {
-        if index < self.subreddits.len() {
-            self.current_subreddit_index = index;
+    /// Subreddits in the current scope
+    fn scope_subreddits(&self) -> Vec<String> {
+        match &self.scope {
+            RedditScope::All => self.subreddits.clone(),
+            RedditScope::Subreddit(name) => vec![name.clone()],
+            RedditScope::Multi(name) => self
+                .multireddits
+                .get(name)
+                .filter(|subs| !subs.is_empty())
+                .cloned()
+                .unwrap_or_else(|| self.subreddits.clone()),
         }
     }
 
+    /// Sort and time range for the current scope, after overrides
+    fn listing_order(&self) -> (RedditSort, RedditTime) {
+        self.scope
+            .key()
+            .and_then(|key| self.overrides.get(key))
+            .copied()
+            .unwrap_or((self.sort, self.time))
+    }
+
     /// Build RSS feed URL for a subreddit
     fn build_feed_url(&self, subreddit: &str) -> String {
-        format!(
-            "{}/r/{}{}.rss",
-            REDDIT_BASE_URL,
-            subreddit,
-            self.sort.as_path()
+        let (sort, time) = self.listing_order();
+        let mut url = format!("{}/r/{}{}.rss", self.base_url, subreddit, sort.as_path());
+        if matches!(sort, RedditSort::Top) {
+            url.push_str("?t=");
+            url.push_str(time.as_str());
+        }
+        url
+    }
+
+    /// Build the `.json` listing URL for the scope's subreddits combined (`r/a+b+c`)
+    fn build_listing_url(&self, after: Option<&str>, limit: usize) -> String {
+        let (sort, time) = self.listing_order();
+        let mut url = format!(
+            "{}/r/{}/{}.json?limit={}&raw_json=1",
+            self.base_url,
+            self.scope_subreddits().join("+"),
+            sort.as_slug(),
+            limit
+        );
+        if matches!(sort, RedditSort::Top) {
+            url.push_str("&t=");
+            url.push_str(time.as_str());
+        }
+        if let Some(after) = after {
+            url.push_str("&after=");
+            url.push_str(after);
+        }
+        url
+    }
+
+    /// Fetch up to `limit` posts following `after`, returning the next cursor
+    async fn fetch_listing(
+        &self,
+        mut after: Option<String>,
+        limit: usize,
+    ) -> Result<(Vec<FeedItem>, Option<String>)> {
+        let mut items = Vec::new();
+
+        while items.len() < limit {
+            let page_size = (limit - items.len()).min(MAX_PAGE_SIZE);
+            let url = self.build_listing_url(after.as_deref(), page_size);
+            let response = self.client.get(&url).send().await?;
+            if response.status() == StatusCode::TOO_MANY_REQUESTS {
+                return Err(ProviderError::RateLimit);
+            }
+            let response = response.error_for_status()?;
+            let listing: Listing = response
+                .json()
+                .await
+                .map_err(|e| ProviderError::Parse(e.to_string()))?;
+
+            let fetched = listing.data.children.len();
+            items.extend(
+                listing
+                    .data
+                    .children
+                    .into_iter()
+                    .filter_map(|child| self.convert_post(child.data)),
+            );
+            after = listing.data.after;
+            if after.is_none() || fetched == 0 {
+                break;
+            }
+        }
+
+        Ok((items, after))
+    }
+
+    /// Convert a listing post to a FeedItem
+    ///
+    /// Crossposts take their link, text and media from the original post.
+    /// Self-post text and media links become the article content.
+    fn convert_post(&self, post: RedditPost) -> Option<FeedItem> {
+        if post.title.is_empty() {
+            return None;
+        }
+
+        let post_type = post.post_type();
+        let published_at =
+            DateTime::from_timestamp(post.created_utc as i64, 0).unwrap_or_else(Utc::now);
+        let origin = post.origin();
+        let url = origin.outbound_url();
+        let media_urls = origin.media_urls();
+        let body = markdown_to_text(&origin.selftext);
+        let crosspost_from = post
+            .crosspost_parent_list
+            .first()
+            .map(|parent| format!("r/{}", parent.subreddit));
+
+        let summary = body
+            .lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty())
+            .take(3)
+            .collect::<Vec<_>>()
+            .join(" ");
+        let content = post_content(&body, origin.post_type(), &media_urls);
+
+        let metadata = FeedItemMetadata {
+            score: Some(post.score as i32),
+            comments: Some(post.num_comments as i32),
+            tags: vec![format!("r/{}", post.subreddit)],
+            image_url: post.thumbnail.filter(|t| t.starts_with("http")),
+            upvote_ratio: post.upvote_ratio,
+            subreddit: Some(post.subreddit.clone()),
+            reddit_id: Some(post.id),
+            flair: post.link_flair_text.filter(|f| !f.trim().is_empty()),
+            nsfw: post.over_18,
+            spoiler: post.spoiler,
+            post_type: Some(post_type),
+            media_urls,
+            crosspost_from,
+            ..Default::default()
+        };
+
+        let mut item = FeedItem::new(
+            post.name,
+            self.id().to_string(),
+            post.title,
+            format!("r/{}", post.subreddit),
+            published_at,
         )
+        .with_url(url)
+        .with_metadata(metadata);
+
+        if let Some(author) = post.author {
+            item = item.with_author(author);
+        }
+        if !summary.is_empty() {
+            item = item.with_summary(summary);
+        }
+        if let Some(content) = content {
+            item = item.with_content(content);
+        }
+
+        Some(item)
+    }
+
+    /// Submissions of `url` anywhere on Reddit
+    pub async fn find_by_url(&self, url: &str) -> Result<Vec<FeedItem>> {
+        let response = self
+            .client
+            .get(format!("{}/api/info.json", self.base_url))
+            .query(&[("url", url), ("limit", "100"), ("raw_json", "1")])
+            .send()
+            .await?;
+        if response.status() == StatusCode::TOO_MANY_REQUESTS {
+            return Err(ProviderError::RateLimit);
+        }
+
+        let listing: Listing = response
+            .error_for_status()?
+            .json()
+            .await
+            .map_err(|e| ProviderError::Parse(e.to_string()))?;
+        Ok(listing
+            .data
+            .children
+            .into_iter()
+            .filter_map(|child| self.convert_post(child.data))
+            .collect())
+    }
+
+    /// Fetch the first page of the combined listing, resetting cursors
+    async fn fetch_first_page(&self, limit: usize) -> Result<Vec<FeedItem>> {
+        let (items, after) = self.fetch_listing(None, limit).await?;
+        let mut cursors = self.cursors.lock().unwrap_or_else(|e| e.into_inner());
+        cursors.clear();
+        cursors.insert(items.len(), after);
+        Ok(items)
+    }
+
+    /// Fetch the page after `offset` posts using the cursor recorded for it
+    ///
+    /// Callers count posts after rules may have hidden some, so the first
+    /// cursor at or beyond `offset` is used.
+    async fn fetch_page_at(&self, offset: usize, limit: usize) -> Result<Vec<FeedItem>> {
+        let cursor = {
+            let cursors = self.cursors.lock().unwrap_or_else(|e| e.into_inner());
+            cursors
+                .range(offset..)
+                .next()
+                .or_else(|| cursors.iter().next_back())
+                .map(|(at, after)| (*at, after.clone()))
+        };
+        let Some((at, Some(after))) = cursor else {
+            // Past the end of the listing, or no first page yet
+            return Ok(Vec::new());
+        };
+
+        let (items, next) = self.fetch_listing(Some(after), limit).await?;
+        self.cursors
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(at + items.len(), next);
+        Ok(items)
     }
 
     /// Fetch RSS feed from a subreddit
@@ -193,10 +773,8 @@ impl RedditProvider {
                         match current_tag.as_str() {
                             "title" => entry.title = text,
                             "id" => entry.id = text,
-                            "published" | "updated" => {
-                                if entry.published.is_none() {
-                                    entry.published = Some(text);
-                                }
+                            "published" | "updated" if entry.published.is_none() => {
+                                entry.published = Some(text);
                             }
                             "name" => entry.author = Some(text),
                             "content" => entry.content = Some(text),
@@ -307,11 +885,11 @@ impl RedditProvider {
 
     /// Fetch items from all configured subreddits in parallel
     async fn fetch_from_all_subreddits(&self, limit: usize) -> Result<Vec<FeedItem>> {
-        let items_per_sub = (limit / self.subreddits.len()).max(10);
+        let subreddits = self.scope_subreddits();
+        let items_per_sub = (limit / subreddits.len().max(1)).max(10);
 
         // Fetch all subreddits in parallel for real-time performance
-        let futures: Vec<_> = self
-            .subreddits
+        let futures: Vec<_> = subreddits
             .iter()
             .map(|subreddit| self.fetch_single_subreddit(subreddit.clone(), items_per_sub))
             .collect();
@@ -321,7 +899,7 @@ impl RedditProvider {
         let mut all_items: Vec<FeedItem> = results.into_iter().flatten().collect();
 
         // Sort by publish date (newest first)
-        all_items.sort_by(|a, b| b.published_at.cmp(&a.published_at));
+        all_items.sort_by_key(|item| std::cmp::Reverse(item.published_at));
         all_items.truncate(limit);
 
         Ok(all_items)
@@ -367,7 +945,7 @@ impl RedditProvider {
     ) -> Result<Vec<Comment>> {
         let url = format!(
             "{}/r/{}/comments/{}.json",
-            REDDIT_BASE_URL, subreddit, post_id
+            self.base_url, subreddit, post_id
         );
 
         let response = self
@@ -487,7 +1065,21 @@ impl FeedProvider for RedditProvider {
     }
 
     async fn fetch_items(&self, limit: usize) -> Result<Vec<FeedItem>> {
-        self.fetch_from_all_subreddits(limit).await
+        match self.api {
+            RedditApi::Json => self.fetch_first_page(limit).await,
+            RedditApi::Rss => self.fetch_from_all_subreddits(limit).await,
+        }
+    }
+
+    async fn fetch_items_with_offset(&self, offset: usize, limit: usize) -> Result<Vec<FeedItem>> {
+        if offset == 0 {
+            return self.fetch_items(limit).await;
+        }
+        self.fetch_page_at(offset, limit).await
+    }
+
+    fn supports_offset(&self) -> bool {
+        self.api == RedditApi::Json
     }
 }
 
@@ -533,4 +1125,215 @@ mod tests {
         assert_eq!(score, Some(150));
         assert_eq!(comments, Some(42));
     }
+
+    fn post(id: &str, extra: &str) -> String {
+        format!(
+            r#"{{"kind": "t3", "data": {{"id": "{id}", "name": "t3_{id}", "title": "Post {id}",
+                "author": "alice", "subreddit": "rust", "score": 321, "num_comments": 45,
+                "upvote_ratio": 0.97, "permalink": "/r/rust/comments/{id}/post/",
+                "created_utc": 1767225600.0 {extra}}}}}"#
+        )
+    }
+
+    fn listing(posts: &[String], after: Option<&str>) -> String {
+        format!(
+            r#"{{"kind": "Listing", "data": {{"after": {}, "children": [{}]}}}}"#,
+            after
+                .map(|a| format!("\"{}\"", a))
+                .unwrap_or_else(|| "null".to_string()),
+            posts.join(",")
+        )
+    }
+
+    #[test]
+    fn test_post_media_and_crossposts() {
+        let gallery = post(
+            "g1",
+            r#", "is_gallery": true, "url": "https://www.reddit.com/gallery/g1",
+            "gallery_data": {"items": [{"media_id": "m2"}, {"media_id": "m1"}]},
+            "media_metadata": {
+                "m1": {"status": "valid", "s": {"u": "https://i.redd.it/one.jpg"}},
+                "m2": {"status": "valid", "s": {"gif": "https://i.redd.it/two.gif"}}
+            }"#,
+        );
+        let video = post(
+            "v1",
+            r#", "is_video": true, "url": "https://v.redd.it/v1",
+            "secure_media": {"reddit_video": {"fallback_url": "https://v.redd.it/v1/DASH_720.mp4"}}"#,
+        );
+        let crosspost = post(
+            "x1",
+            r#", "crosspost_parent": "t3_o1", "url": "/r/finance/comments/o1/post/",
+            "crosspost_parent_list": [{"id": "o1", "title": "Original", "subreddit": "finance",
+                "is_self": true, "selftext": "**Bold** claim, [source](https://example.com)",
+                "permalink": "/r/finance/comments/o1/post/"}]"#,
+        );
+        let listing: Listing =
+            serde_json::from_str(&listing(&[gallery, video, crosspost], None)).unwrap();
+
+        let provider = RedditProvider::new(vec![], None, true).unwrap();
+        let items: Vec<FeedItem> = listing
+            .data
+            .children
+            .into_iter()
+            .filter_map(|child| provider.convert_post(child.data))
+            .collect();
+
+        let gallery = &items[0];
+        assert_eq!(gallery.metadata.post_type, Some(PostType::Gallery));
+        assert_eq!(
+            gallery.metadata.media_urls,
+            vec!["https://i.redd.it/two.gif", "https://i.redd.it/one.jpg"]
+        );
+        assert_eq!(
+            gallery.content.as_deref(),
+            Some("Gallery (2 images):\n  1. https://i.redd.it/two.gif\n  2. https://i.redd.it/one.jpg")
+        );
+
+        let video = &items[1];
+        assert_eq!(
+            video.metadata.media_urls,
+            vec!["https://v.redd.it/v1/DASH_720.mp4"]
+        );
+        assert_eq!(video.url.as_deref(), Some("https://v.redd.it/v1"));
+
+        let crosspost = &items[2];
+        assert_eq!(crosspost.metadata.post_type, Some(PostType::Crosspost));
+        assert_eq!(
+            crosspost.metadata.crosspost_from.as_deref(),
+            Some("r/finance")
+        );
+        assert_eq!(
+            crosspost.url.as_deref(),
+            Some("https://www.reddit.com/r/finance/comments/o1/post/")
+        );
+        assert_eq!(
+            crosspost.content.as_deref(),
+            Some("Bold claim, source (https://example.com)")
+        );
+        assert!(crosspost.metadata.media_urls.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_json_listing_and_cursors() {
+        let mut server = mockito::Server::new_async().await;
+        let first = server
+            .mock("GET", "/r/rust+finance/top.json")
+            .match_query(mockito::Matcher::AllOf(vec![
+                mockito::Matcher::UrlEncoded("limit".into(), "2".into()),
+                mockito::Matcher::UrlEncoded("raw_json".into(), "1".into()),
+            ]))
+            .with_body(listing(
+                &[
+                    post(
+                        "a1",
+                        r#", "is_self": true, "selftext": "First line\n\nSecond",
+                        "link_flair_text": "Discussion", "spoiler": true"#,
+                    ),
+                    post(
+                        "b2",
+                        r#", "url": "https://i.redd.it/x.png", "post_hint": "image",
+                        "over_18": true, "thumbnail": "nsfw""#,
+                    ),
+                ],
+                Some("t3_b2"),
+            ))
+            .create_async()
+            .await;
+        let second = server
+            .mock("GET", "/r/rust+finance/top.json")
+            .match_query(mockito::Matcher::UrlEncoded("after".into(), "t3_b2".into()))
+            .with_body(listing(
+                &[post("c3", r#", "url": "https://example.com/a""#)],
+                None,
+            ))
+            .create_async()
+            .await;
+
+        let provider = RedditProvider::new(
+            vec!["rust".to_string(), "finance".to_string()],
+            Some("top".to_string()),
+            true,
+        )
+        .unwrap()
+        .with_base_url(&server.url());
+        assert!(provider.supports_offset());
+
+        let items = provider.fetch_items(2).await.unwrap();
+        assert_eq!(items.len(), 2);
+        let text = &items[0];
+        assert_eq!(text.id, "t3_a1");
+        assert_eq!(
+            text.url.as_deref(),
+            Some("https://www.reddit.com/r/rust/comments/a1/post/")
+        );
+        assert_eq!(text.summary.as_deref(), Some("First line Second"));
+        assert_eq!(text.metadata.score, Some(321));
+        assert_eq!(text.metadata.comments, Some(45));
+        assert_eq!(text.metadata.upvote_ratio, Some(0.97));
+        assert_eq!(text.metadata.flair.as_deref(), Some("Discussion"));
+        assert_eq!(text.metadata.post_type, Some(PostType::Text));
+        assert!(text.metadata.spoiler);
+        assert_eq!(text.metadata.reddit_id.as_deref(), Some("a1"));
+
+        let image = &items[1];
+        assert_eq!(image.metadata.post_type, Some(PostType::Image));
+        assert!(image.metadata.nsfw);
+        assert_eq!(image.metadata.image_url, None);
+
+        // One item hidden by rules: offset 1 still continues after the cursor
+        let more = provider.fetch_items_with_offset(1, 2).await.unwrap();
+        assert_eq!(more.len(), 1);
+        assert_eq!(more[0].metadata.post_type, Some(PostType::Link));
+        assert!(provider
+            .fetch_items_with_offset(3, 2)
+            .await
+            .unwrap()
+            .is_empty());
+
+        first.assert_async().await;
+        second.assert_async().await;
+    }
+
+    #[test]
+    fn test_scopes_and_overrides() {
+        let config: RedditConfig = toml::from_str(
+            r#"
+            subreddits = ["stocks", "rust"]
+            sort = "hot"
+            [multireddits]
+            markets = ["stocks", "investing"]
+            [overrides.stocks]
+            sort = "top"
+            time = "week"
+            "#,
+        )
+        .unwrap();
+
+        let tabs: Vec<String> = scopes(&config).iter().map(|s| s.label()).collect();
+        assert_eq!(tabs, vec!["all", "m/markets", "r/stocks", "r/rust"]);
+        assert_eq!(
+            RedditScope::parse("/r/Stocks"),
+            Some(RedditScope::Subreddit("Stocks".to_string()))
+        );
+        assert_eq!(RedditScope::parse("not a sub"), None);
+
+        let url = |scope| {
+            RedditProvider::from_config(&config, scope)
+                .unwrap()
+                .build_listing_url(None, 25)
+        };
+        assert_eq!(
+            url(RedditScope::All),
+            "https://www.reddit.com/r/stocks+rust/hot.json?limit=25&raw_json=1"
+        );
+        assert_eq!(
+            url(RedditScope::Multi("markets".to_string())),
+            "https://www.reddit.com/r/stocks+investing/hot.json?limit=25&raw_json=1"
+        );
+        assert_eq!(
+            url(RedditScope::Subreddit("stocks".to_string())),
+            "https://www.reddit.com/r/stocks/top.json?limit=25&raw_json=1&t=week"
+        );
+    }
 }
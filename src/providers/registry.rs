@@ -2,8 +2,15 @@
 //!
 //! Central registry for managing feed providers
 
+use crate::analysis::{entities, sentiment, EntityExtractor};
+use crate::config::Config;
 use crate::models::FeedItem;
-use crate::providers::{FeedProvider, ProviderError, ProviderStatus, Result};
+use crate::providers::{
+    ArxivProvider, CratesIoProvider, FeedProvider, FinnhubProvider, HackerNewsProvider,
+    ProviderError, ProviderStatus, RedditProvider, RedditScope, Result,
+};
+use crate::rules::{RuleError, RuleSet};
+use serde::Serialize;
 use std::collections::HashMap;
 use std::sync::Arc;
 
@@ -11,6 +18,8 @@ use std::sync::Arc;
 pub struct ProviderRegistry {
     providers: HashMap<String, Arc<dyn FeedProvider>>,
     order: Vec<String>, // Maintain insertion order
+    entities: Option<EntityExtractor>,
+    rules: RuleSet,
 }
 
 impl ProviderRegistry {
@@ -19,9 +28,70 @@ impl ProviderRegistry {
         Self {
             providers: HashMap::new(),
             order: Vec::new(),
+            entities: Some(EntityExtractor::bundled()),
+            rules: RuleSet::default(),
         }
     }
 
+    /// Create a registry with the built-in providers configured from `config`;
+    /// fails if a `[[rules]]` entry doesn't compile
+    pub fn from_config(config: &Config) -> std::result::Result<Self, RuleError> {
+        let mut registry = Self::new();
+
+        // Register HackerNews provider first (most used)
+        if let Ok(hn) = HackerNewsProvider::new(None) {
+            registry.register(hn);
+        }
+
+        // Register arXiv provider (research papers)
+        if let Ok(arxiv) = ArxivProvider::from_config(&config.arxiv) {
+            registry.register(arxiv);
+        }
+
+        // Register Crates.io provider
+        if let Ok(cratesio) = CratesIoProvider::new(None) {
+            registry.register(cratesio);
+        }
+
+        // Register Reddit provider
+        if let Ok(reddit) = RedditProvider::from_config(&config.reddit, RedditScope::All) {
+            registry.register(reddit);
+        }
+
+        // Register Finnhub provider (last - requires API key)
+        if let Ok(finnhub) = FinnhubProvider::new(
+            config.finnhub.api_key.clone(),
+            Some(config.finnhub.category.clone()),
+        ) {
+            registry.register(finnhub);
+        }
+
+        registry.set_entity_extractor(Self::entity_extractor(config));
+        registry.set_rules(RuleSet::compile(&config.rules)?);
+        Ok(registry)
+    }
+
+    /// Build the ticker extractor described by the [entities] config section
+    fn entity_extractor(config: &Config) -> Option<EntityExtractor> {
+        if !config.entities.enabled {
+            return None;
+        }
+
+        let mut extractor = if config.entities.bundled_symbols {
+            EntityExtractor::bundled()
+        } else {
+            EntityExtractor::new()
+        };
+
+        if let Some(path) = config.symbols_file() {
+            if let Err(e) = extractor.load_file(&path) {
+                tracing::warn!("Failed to load symbols from {}: {}", path.display(), e);
+            }
+        }
+
+        Some(extractor)
+    }
+
     /// Register a new provider
     pub fn register<P: FeedProvider + 'static>(&mut self, provider: P) {
         let id = provider.id().to_string();
@@ -40,6 +110,36 @@ impl ProviderRegistry {
         self.providers.insert(id, provider);
     }
 
+    /// Set the ticker extractor applied to fetched items (None disables tagging)
+    pub fn set_entity_extractor(&mut self, extractor: Option<EntityExtractor>) {
+        self.entities = extractor;
+    }
+
+    /// Set the rules applied to fetched items
+    pub fn set_rules(&mut self, rules: RuleSet) {
+        self.rules = rules;
+    }
+
+    /// Whether a tag is a ticker symbol. Checked against the extractor's
+    /// symbol list, so uppercase tags such as Reddit flair don't count; falls
+    /// back to the tag's shape when ticker extraction is disabled.
+    pub fn is_symbol(&self, tag: &str) -> bool {
+        match &self.entities {
+            Some(extractor) => extractor.is_symbol(tag),
+            None => entities::is_symbol_tag(tag),
+        }
+    }
+
+    /// Run offline enrichment (sentiment, tickers) and config rules over fetched items
+    pub fn enrich(&self, items: &mut Vec<FeedItem>) {
+        sentiment::annotate(items);
+        if let Some(entities) = &self.entities {
+            entities.tag_items(items);
+        }
+        // Rules run last so they can match on extracted tickers
+        self.rules.apply_all(items);
+    }
+
     /// Get a provider by ID
     pub fn get(&self, id: &str) -> Option<Arc<dyn FeedProvider>> {
         self.providers.get(id).cloned()
@@ -94,19 +194,73 @@ impl ProviderRegistry {
             }
         }
 
-        // Sort by publish date (newest first)
-        all_items.sort_by(|a, b| b.published_at.cmp(&a.published_at));
+        // Sort by publish date (newest first); rules then float pinned items to the top
+        all_items.sort_by_key(|item| std::cmp::Reverse(item.published_at));
+        self.enrich(&mut all_items);
 
         all_items
     }
 
+    /// Search every ready provider that can search its source (HN through
+    /// Algolia, arXiv through its export API), up to `limit` results each
+    pub async fn search(&self, query: &str, limit: usize) -> Vec<FeedItem> {
+        let mut all_items = Vec::new();
+
+        for provider in self.ready().into_iter().filter(|p| p.supports_search()) {
+            match provider.search(query, limit).await {
+                Ok(items) => all_items.extend(items),
+                Err(e) => tracing::warn!("Search on {} failed: {}", provider.id(), e),
+            }
+        }
+
+        self.enrich(&mut all_items);
+        all_items
+    }
+
     /// Fetch items from a specific provider
     pub async fn fetch_from(&self, provider_id: &str, limit: usize) -> Result<Vec<FeedItem>> {
+        self.fetch_counted(provider_id, limit)
+            .await
+            .map(|(items, _)| items)
+    }
+
+    /// Fetch items from a specific provider, along with how many the provider
+    /// returned before rules hid any (where the next page starts)
+    pub async fn fetch_counted(
+        &self,
+        provider_id: &str,
+        limit: usize,
+    ) -> Result<(Vec<FeedItem>, usize)> {
+        let provider = self.get(provider_id).ok_or_else(|| {
+            ProviderError::NotConfigured(format!("Provider '{}' not found", provider_id))
+        })?;
+
+        let mut items = provider.fetch_items(limit).await?;
+        let fetched = items.len();
+        self.enrich(&mut items);
+        Ok((items, fetched))
+    }
+
+    /// Fetch the next page of items from a specific provider. Like
+    /// `fetch_counted`, also returns how many items the provider returned.
+    pub async fn fetch_from_with_offset(
+        &self,
+        provider_id: &str,
+        offset: usize,
+        limit: usize,
+    ) -> Result<(Vec<FeedItem>, usize)> {
         let provider = self.get(provider_id).ok_or_else(|| {
             ProviderError::NotConfigured(format!("Provider '{}' not found", provider_id))
         })?;
 
-        provider.fetch_items(limit).await
+        if !provider.supports_offset() {
+            return Err(ProviderError::Other("Offset not supported".to_string()));
+        }
+
+        let mut items = provider.fetch_items_with_offset(offset, limit).await?;
+        let fetched = items.len();
+        self.enrich(&mut items);
+        Ok((items, fetched))
     }
 
     /// Get summary of provider statuses for UI
@@ -133,7 +287,7 @@ impl Default for ProviderRegistry {
 }
 
 /// Summary of a provider for UI display
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct ProviderSummary {
     pub id: String,
     pub name: String,
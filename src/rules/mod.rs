@@ -0,0 +1,299 @@
+//! Item rules
+//!
+//! User-defined `[[rules]]` from the config that hide, highlight, tag, pin
+//! or flag feed items for notification. Every matcher set on a rule must
+//! match for its actions to apply.
+
+use crate::config::RuleConfig;
+use crate::models::FeedItem;
+use crate::utils::parse_color;
+use regex::{Regex, RegexBuilder};
+use thiserror::Error;
+use url::Url;
+
+#[derive(Error, Debug)]
+pub enum RuleError {
+    #[error("rule '{rule}': invalid {field} pattern: {source}")]
+    Pattern {
+        rule: String,
+        field: &'static str,
+        source: regex::Error,
+    },
+
+    #[error("rule '{rule}': unknown highlight colour '{colour}'")]
+    Highlight { rule: String, colour: String },
+}
+
+/// A rule with its patterns compiled
+#[derive(Debug, Clone)]
+pub struct Rule {
+    pub name: String,
+    title: Option<Regex>,
+    domain: Option<Regex>,
+    provider: Option<Regex>,
+    author: Option<Regex>,
+    tag: Option<Regex>,
+    min_score: Option<i32>,
+    min_comments: Option<i32>,
+    hide: bool,
+    highlight: Option<String>,
+    add_tags: Vec<String>,
+    pin: bool,
+    notify: bool,
+}
+
+impl Rule {
+    /// Compile a rule from its config; patterns are case-insensitive
+    pub fn compile(config: &RuleConfig, index: usize) -> Result<Self, RuleError> {
+        let name = if config.name.is_empty() {
+            format!("#{}", index + 1)
+        } else {
+            config.name.clone()
+        };
+
+        let pattern = |field: &'static str, value: &Option<String>| {
+            value
+                .as_deref()
+                .map(|p| {
+                    RegexBuilder::new(p)
+                        .case_insensitive(true)
+                        .build()
+                        .map_err(|source| RuleError::Pattern {
+                            rule: name.clone(),
+                            field,
+                            source,
+                        })
+                })
+                .transpose()
+        };
+
+        if let Some(colour) = &config.highlight {
+            if parse_color(colour).is_none() {
+                return Err(RuleError::Highlight {
+                    rule: name,
+                    colour: colour.clone(),
+                });
+            }
+        }
+
+        Ok(Self {
+            title: pattern("title", &config.title)?,
+            domain: pattern("domain", &config.domain)?,
+            provider: pattern("provider", &config.provider)?,
+            author: pattern("author", &config.author)?,
+            tag: pattern("tag", &config.tag)?,
+            min_score: config.min_score,
+            min_comments: config.min_comments,
+            hide: config.hide,
+            highlight: config.highlight.clone(),
+            add_tags: config.add_tags.clone(),
+            pin: config.pin,
+            notify: config.notify,
+            name,
+        })
+    }
+
+    /// Check whether every matcher on this rule accepts the item
+    pub fn matches(&self, item: &FeedItem) -> bool {
+        fn check(pattern: &Option<Regex>, value: Option<&str>) -> bool {
+            match pattern {
+                Some(re) => value.map(|v| re.is_match(v)).unwrap_or(false),
+                None => true,
+            }
+        }
+
+        let domain = item.url.as_deref().and_then(domain_of);
+
+        check(&self.title, Some(&item.title))
+            && check(&self.domain, domain.as_deref())
+            && check(&self.provider, Some(&item.provider_id))
+            && check(&self.author, item.author.as_deref())
+            && self
+                .tag
+                .as_ref()
+                .map(|re| item.metadata.tags.iter().any(|t| re.is_match(t)))
+                .unwrap_or(true)
+            && self
+                .min_score
+                .map(|min| item.metadata.score.unwrap_or(0) >= min)
+                .unwrap_or(true)
+            && self
+                .min_comments
+                .map(|min| item.metadata.comments.unwrap_or(0) >= min)
+                .unwrap_or(true)
+    }
+
+    /// Apply this rule's actions to an item
+    fn apply(&self, item: &mut FeedItem) {
+        let meta = &mut item.metadata;
+        if !meta.matched_rules.contains(&self.name) {
+            meta.matched_rules.push(self.name.clone());
+        }
+        for tag in &self.add_tags {
+            if !meta.tags.contains(tag) {
+                meta.tags.push(tag.clone());
+            }
+        }
+        if let Some(colour) = &self.highlight {
+            meta.highlight = Some(colour.clone());
+        }
+        meta.hidden |= self.hide;
+        meta.pinned |= self.pin;
+        meta.notify |= self.notify;
+    }
+}
+
+/// Ordered collection of compiled rules
+#[derive(Debug, Clone, Default)]
+pub struct RuleSet {
+    rules: Vec<Rule>,
+}
+
+impl RuleSet {
+    /// Compile all enabled rules, failing on the first invalid pattern
+    pub fn compile(configs: &[RuleConfig]) -> Result<Self, RuleError> {
+        let rules = configs
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| c.enabled)
+            .map(|(idx, c)| Rule::compile(c, idx))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self { rules })
+    }
+
+    /// Number of active rules
+    pub fn len(&self) -> usize {
+        self.rules.len()
+    }
+
+    /// Check if there are no active rules
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Run every rule over an item; later rules win on highlight colour
+    pub fn apply(&self, item: &mut FeedItem) {
+        for rule in &self.rules {
+            if rule.matches(item) {
+                rule.apply(item);
+            }
+        }
+    }
+
+    /// Apply rules to a batch, dropping hidden items and moving pinned ones first
+    pub fn apply_all(&self, items: &mut Vec<FeedItem>) {
+        if self.rules.is_empty() {
+            return;
+        }
+
+        for item in items.iter_mut() {
+            self.apply(item);
+        }
+        items.retain(|item| !item.metadata.hidden);
+        // Stable, so pinned and unpinned items keep their relative order
+        items.sort_by_key(|item| !item.metadata.pinned);
+    }
+}
+
+/// Host of a URL without a leading "www."
+fn domain_of(url: &str) -> Option<String> {
+    let host = Url::parse(url).ok()?.host_str()?.to_lowercase();
+    Some(host.trim_start_matches("www.").to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(toml: &str) -> RuleConfig {
+        toml::from_str(toml).unwrap()
+    }
+
+    #[test]
+    fn test_all_matchers_must_match() {
+        let rules = RuleSet::compile(&[rule(
+            r#"
+            name = "big rust"
+            title = "rust"
+            domain = "^github\\.com$"
+            min_score = 100
+            highlight = "yellow"
+            "#,
+        )])
+        .unwrap();
+
+        let mut hit = FeedItem::test("1", "hackernews")
+            .with_title("Rust 2.0")
+            .with_url("https://www.github.com/x".to_string())
+            .with_score(150);
+        let mut low = FeedItem::test("2", "hackernews")
+            .with_title("Rust 2.0")
+            .with_url("https://github.com/x".to_string())
+            .with_score(5);
+        let mut other = FeedItem::test("3", "hackernews")
+            .with_title("Rust 2.0")
+            .with_url("https://gitlab.com/x".to_string())
+            .with_score(500);
+        rules.apply(&mut hit);
+        rules.apply(&mut low);
+        rules.apply(&mut other);
+
+        assert_eq!(hit.metadata.highlight.as_deref(), Some("yellow"));
+        assert_eq!(hit.metadata.matched_rules, vec!["big rust"]);
+        assert!(low.metadata.matched_rules.is_empty());
+        assert!(other.metadata.matched_rules.is_empty());
+    }
+
+    #[test]
+    fn test_hide_pin_and_tag() {
+        let rules = RuleSet::compile(&[
+            rule("provider = \"reddit\"\ntag = \"^r/(crypto|bitcoin)\"\nhide = true"),
+            rule("title = \"finterm\"\npin = true\nadd_tags = [\"ours\"]\nnotify = true"),
+        ])
+        .unwrap();
+
+        let mut spam = FeedItem::test("1", "reddit")
+            .with_title("Moon soon")
+            .with_url("https://reddit.com/a".to_string())
+            .with_score(10);
+        spam.metadata.tags = vec!["r/CryptoCurrency".to_string()];
+        let mut items = vec![
+            FeedItem::test("2", "hackernews")
+                .with_title("Unrelated")
+                .with_url("https://a.com".to_string())
+                .with_score(1),
+            spam,
+            FeedItem::test("3", "reddit")
+                .with_title("FinTerm 1.0 released")
+                .with_url("https://b.com".to_string())
+                .with_score(1),
+        ];
+
+        rules.apply_all(&mut items);
+
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].title, "FinTerm 1.0 released");
+        assert!(items[0].metadata.pinned);
+        assert!(items[0].metadata.notify);
+        assert_eq!(items[0].metadata.tags, vec!["ours"]);
+        assert_eq!(items[1].title, "Unrelated");
+    }
+
+    #[test]
+    fn test_invalid_pattern_and_disabled_rules() {
+        let err = RuleSet::compile(&[rule("name = \"bad\"\ntitle = \"(unclosed\"")]).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("rule 'bad': invalid title pattern"));
+
+        let err = RuleSet::compile(&[rule("name = \"typo\"\nhighlight = \"yelow\"")]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "rule 'typo': unknown highlight colour 'yelow'"
+        );
+        assert!(RuleSet::compile(&[rule("highlight = \"#ff8800\"")]).is_ok());
+
+        let rules = RuleSet::compile(&[rule("title = \"(unclosed\"\nenabled = false")]).unwrap();
+        assert!(rules.is_empty());
+    }
+}
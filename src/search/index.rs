@@ -0,0 +1,440 @@
+//! Local inverted index
+//!
+//! Every fetched item is stored in a `docs` tree and each of its words in a
+//! `postings` tree keyed `term \0 doc`, so lookups are prefix scans. Both
+//! trees live in the cache database but outside the TTL/eviction keyspace.
+//! Items not fetched again within the retention window are dropped on open.
+
+use crate::cache::{CacheError, CacheManager, Result};
+use crate::models::FeedItem;
+use crate::search::query::{tokenize, Field, Query};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+const DOCS_TREE: &str = "search:docs";
+const POSTINGS_TREE: &str = "search:postings";
+const SEPARATOR: u8 = 0;
+
+/// Items not indexed again for this long are forgotten
+const RETENTION_DAYS: i64 = 180;
+
+/// An item as stored in the index
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct IndexedDoc {
+    item: FeedItem,
+    indexed_at: DateTime<Utc>,
+}
+
+/// A search result with its relevance score
+#[derive(Debug, Clone)]
+pub struct SearchHit {
+    pub item: FeedItem,
+    pub score: f32,
+}
+
+/// Offline full-text index over every item finterm has seen
+pub struct SearchIndex {
+    docs: sled::Tree,
+    postings: sled::Tree,
+}
+
+impl SearchIndex {
+    /// Open the index stored alongside the cache, dropping stale items
+    pub fn open(cache: &CacheManager) -> Result<Self> {
+        let index = Self {
+            docs: cache.open_tree(DOCS_TREE)?,
+            postings: cache.open_tree(POSTINGS_TREE)?,
+        };
+        index.prune(Utc::now() - Duration::days(RETENTION_DAYS))?;
+        Ok(index)
+    }
+
+    /// Number of indexed items
+    pub fn len(&self) -> usize {
+        self.docs.len()
+    }
+
+    /// Check if nothing has been indexed yet
+    pub fn is_empty(&self) -> bool {
+        self.docs.is_empty()
+    }
+
+    /// Add or update a batch of items
+    pub fn index_items(&self, items: &[FeedItem]) -> Result<()> {
+        for item in items {
+            self.index_item(item)?;
+        }
+        Ok(())
+    }
+
+    /// Add or update one item, keeping text learned from earlier fetches
+    pub fn index_item(&self, item: &FeedItem) -> Result<()> {
+        let key = doc_key(item);
+        let mut item = item.clone();
+        // Comment threads are large and not searched
+        item.metadata.comments_data = None;
+
+        if let Some(previous) = self.load(&key)? {
+            self.remove_postings(&key, &previous.item);
+            let previous = previous.item;
+            item.content = item.content.or(previous.content);
+            item.summary = item.summary.or(previous.summary);
+            item.metadata.link_preview = item
+                .metadata
+                .link_preview
+                .or(previous.metadata.link_preview);
+        }
+
+        for (term, (fields, weight)) in term_weights(&item) {
+            let mut value = vec![fields];
+            value.extend_from_slice(&weight.to_le_bytes());
+            self.postings.insert(posting_key(&term, &key), value)?;
+        }
+
+        let doc = IndexedDoc {
+            item,
+            indexed_at: Utc::now(),
+        };
+        let bytes =
+            serde_json::to_vec(&doc).map_err(|e| CacheError::Serialization(e.to_string()))?;
+        self.docs.insert(key, bytes)?;
+
+        Ok(())
+    }
+
+    /// Run a query, best matches first. Filter-only queries list matching
+    /// items newest first; a query whose words were all too common or too
+    /// short to index finds nothing.
+    pub fn search(&self, query: &Query, limit: usize) -> Result<Vec<SearchHit>> {
+        if query.terms.is_empty() && !query.has_filters() {
+            return Ok(Vec::new());
+        }
+
+        let candidates: Vec<(Vec<u8>, f32)> = if query.terms.is_empty() {
+            self.docs
+                .iter()
+                .keys()
+                .filter_map(|k| k.ok())
+                .map(|k| (k.to_vec(), 0.0))
+                .collect()
+        } else {
+            self.score_terms(query)
+        };
+
+        let mut hits = Vec::new();
+        for (key, score) in candidates {
+            let Some(doc) = self.load(&key)? else {
+                continue;
+            };
+            if matches_filters(query, &doc.item) {
+                hits.push(SearchHit {
+                    item: doc.item,
+                    score,
+                });
+            }
+        }
+
+        hits.sort_by(|a, b| {
+            b.score
+                .total_cmp(&a.score)
+                .then(b.item.published_at.cmp(&a.item.published_at))
+        });
+        hits.truncate(limit);
+        Ok(hits)
+    }
+
+    /// Documents containing every query term, scored by weighted tf-idf
+    fn score_terms(&self, query: &Query) -> Vec<(Vec<u8>, f32)> {
+        let total_docs = self.docs.len().max(1) as f32;
+        let mut scores: Option<HashMap<Vec<u8>, f32>> = None;
+
+        for term in &query.terms {
+            let mut matches: HashMap<Vec<u8>, f32> = HashMap::new();
+            let mut prefix = term.text.as_bytes().to_vec();
+            if !term.prefix {
+                prefix.push(SEPARATOR);
+            }
+
+            for (key, value) in self.postings.scan_prefix(&prefix).filter_map(|r| r.ok()) {
+                let Some(split) = key.iter().position(|b| *b == SEPARATOR) else {
+                    continue;
+                };
+                let fields = value.first().copied().unwrap_or(0);
+                if term.field.is_some_and(|f| fields & f.bit() == 0) {
+                    continue;
+                }
+                let weight = value
+                    .get(1..5)
+                    .and_then(|b| b.try_into().ok())
+                    .map(f32::from_le_bytes)
+                    .unwrap_or(1.0);
+                let doc = key[split + 1..].to_vec();
+                let best = matches.entry(doc).or_insert(0.0);
+                *best = best.max(weight);
+            }
+
+            let idf = (1.0 + total_docs / (matches.len() as f32 + 1.0)).ln();
+            scores = Some(match scores {
+                None => matches.into_iter().map(|(k, w)| (k, w * idf)).collect(),
+                Some(previous) => previous
+                    .into_iter()
+                    .filter_map(|(k, s)| matches.get(&k).map(|w| (k, s + w * idf)))
+                    .collect(),
+            });
+        }
+
+        scores.unwrap_or_default().into_iter().collect()
+    }
+
+    fn load(&self, key: &[u8]) -> Result<Option<IndexedDoc>> {
+        match self.docs.get(key)? {
+            Some(bytes) => serde_json::from_slice(&bytes)
+                .map(Some)
+                .map_err(|e| CacheError::Serialization(e.to_string())),
+            None => Ok(None),
+        }
+    }
+
+    /// Drop items last indexed before `cutoff`
+    pub fn prune(&self, cutoff: DateTime<Utc>) -> Result<usize> {
+        let mut removed = 0;
+        for entry in self.docs.iter() {
+            let (key, bytes) = entry?;
+            let Ok(doc) = serde_json::from_slice::<IndexedDoc>(&bytes) else {
+                continue;
+            };
+            if doc.indexed_at < cutoff {
+                self.remove_postings(&key, &doc.item);
+                self.docs.remove(&key)?;
+                removed += 1;
+            }
+        }
+        Ok(removed)
+    }
+
+    fn remove_postings(&self, key: &[u8], item: &FeedItem) {
+        for term in term_weights(item).into_keys() {
+            let _ = self.postings.remove(posting_key(&term, key));
+        }
+    }
+}
+
+fn doc_key(item: &FeedItem) -> Vec<u8> {
+    format!("{}\u{1f}{}", item.provider_id, item.id).into_bytes()
+}
+
+fn posting_key(term: &str, doc: &[u8]) -> Vec<u8> {
+    let mut key = term.as_bytes().to_vec();
+    key.push(SEPARATOR);
+    key.extend_from_slice(doc);
+    key
+}
+
+/// Each term's field flags and a damped, field-weighted frequency
+fn term_weights(item: &FeedItem) -> HashMap<String, (u8, f32)> {
+    let mut terms: HashMap<String, (u8, f32)> = HashMap::new();
+    let mut add = |text: &str, field: Field| {
+        for term in tokenize(text) {
+            let entry = terms.entry(term).or_insert((0, 0.0));
+            entry.0 |= field.bit();
+            entry.1 += field.weight();
+        }
+    };
+
+    add(&item.title, Field::Title);
+    if let Some(summary) = &item.summary {
+        add(summary, Field::Summary);
+    }
+    if let Some(content) = &item.content {
+        add(content, Field::Content);
+    }
+    if let Some(author) = &item.author {
+        add(author, Field::Author);
+    }
+    for tag in &item.metadata.tags {
+        add(tag, Field::Tags);
+    }
+
+    for (_, weight) in terms.values_mut() {
+        *weight = 1.0 + weight.ln_1p();
+    }
+    terms
+}
+
+/// Apply the non-text parts of a query
+fn matches_filters(query: &Query, item: &FeedItem) -> bool {
+    let any_eq = |wanted: &[String], value: &str| {
+        wanted.is_empty() || wanted.iter().any(|w| w.eq_ignore_ascii_case(value))
+    };
+
+    if !any_eq(&query.providers, &item.provider_id)
+        || !any_eq(&query.sources, &item.source)
+        || !any_eq(&query.authors, item.author.as_deref().unwrap_or_default())
+    {
+        return false;
+    }
+
+    if !query.tags.is_empty()
+        && !query.tags.iter().all(|t| {
+            item.metadata
+                .tags
+                .iter()
+                .any(|tag| tag.eq_ignore_ascii_case(t))
+        })
+    {
+        return false;
+    }
+
+    if query.since.is_some_and(|since| item.published_at < since)
+        || query.until.is_some_and(|until| item.published_at >= until)
+    {
+        return false;
+    }
+
+    if query.phrases.is_empty() && query.excluded.is_empty() {
+        return true;
+    }
+
+    let text = [
+        Some(item.title.as_str()),
+        item.summary.as_deref(),
+        item.content.as_deref(),
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<_>>()
+    .join("\n")
+    .to_lowercase();
+
+    let words = tokenize(&text);
+    query.phrases.iter().all(|p| text.contains(p.as_str()))
+        && !query.excluded.iter().any(|e| words.contains(e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use tempfile::tempdir;
+
+    fn titles(hits: &[SearchHit]) -> Vec<&str> {
+        hits.iter().map(|h| h.item.title.as_str()).collect()
+    }
+
+    fn index_with_items() -> (tempfile::TempDir, CacheManager) {
+        let dir = tempdir().unwrap();
+        let cache = CacheManager::new(dir.path().to_path_buf(), 10).unwrap();
+        let index = SearchIndex::open(&cache).unwrap();
+
+        let mut apple = FeedItem::test("1", "reddit")
+            .with_title("Apple earnings beat estimates")
+            .with_age(Duration::days(1));
+        apple.metadata.tags = vec!["AAPL".to_string()];
+        let mut old = FeedItem::test("2", "reddit")
+            .with_title("Apple supplier earnings")
+            .with_age(Duration::days(30));
+        old.metadata.tags = vec!["AAPL".to_string()];
+        let fed = FeedItem::test("3", "finnhub")
+            .with_title("Fed holds rates steady")
+            .with_age(Duration::days(2))
+            .with_summary("Powell said earnings season looks strong".to_string());
+
+        index.index_items(&[apple, old, fed]).unwrap();
+        (dir, cache)
+    }
+
+    #[test]
+    fn test_terms_and_ranking() {
+        let (_dir, cache) = index_with_items();
+        let index = SearchIndex::open(&cache).unwrap();
+        assert_eq!(index.len(), 3);
+
+        let hits = index
+            .search(&Query::parse("earnings").unwrap(), 10)
+            .unwrap();
+        assert_eq!(hits.len(), 3);
+        // Title hits outrank the summary-only hit
+        assert_eq!(hits[2].item.title, "Fed holds rates steady");
+
+        let hits = index
+            .search(&Query::parse("earn* apple").unwrap(), 10)
+            .unwrap();
+        assert_eq!(hits.len(), 2);
+
+        let hits = index
+            .search(&Query::parse("title:earnings").unwrap(), 10)
+            .unwrap();
+        assert_eq!(hits.len(), 2);
+    }
+
+    #[test]
+    fn test_filters() {
+        let (_dir, cache) = index_with_items();
+        let index = SearchIndex::open(&cache).unwrap();
+
+        let hits = index
+            .search(
+                &Query::parse("provider:reddit tag:aapl since:7d").unwrap(),
+                10,
+            )
+            .unwrap();
+        assert_eq!(titles(&hits), vec!["Apple earnings beat estimates"]);
+
+        let hits = index
+            .search(
+                &Query::parse("earnings -supplier \"beat estimates\"").unwrap(),
+                10,
+            )
+            .unwrap();
+        assert_eq!(titles(&hits), vec!["Apple earnings beat estimates"]);
+
+        // Filters alone list everything they match; unindexable words match nothing
+        let hits = index
+            .search(&Query::parse("provider:reddit").unwrap(), 10)
+            .unwrap();
+        assert_eq!(hits.len(), 2);
+        assert!(index
+            .search(&Query::parse("the a").unwrap(), 10)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_prune_drops_stale_items() {
+        let (_dir, cache) = index_with_items();
+        let index = SearchIndex::open(&cache).unwrap();
+
+        assert_eq!(index.prune(Utc::now() - Duration::days(1)).unwrap(), 0);
+        assert_eq!(index.prune(Utc::now() + Duration::seconds(1)).unwrap(), 3);
+        assert!(index.is_empty());
+        assert!(index.postings.is_empty());
+    }
+
+    #[test]
+    fn test_reindex_replaces_terms_and_keeps_content() {
+        let (_dir, cache) = index_with_items();
+        let index = SearchIndex::open(&cache).unwrap();
+
+        let with_content = FeedItem::test("3", "finnhub")
+            .with_title("Fed holds rates steady")
+            .with_age(Duration::days(2))
+            .with_content("Treasury yields slipped after the decision".to_string());
+        index.index_item(&with_content).unwrap();
+
+        let renamed = FeedItem::test("3", "finnhub")
+            .with_title("Fed keeps rates unchanged")
+            .with_age(Duration::days(2));
+        index.index_item(&renamed).unwrap();
+
+        assert_eq!(index.len(), 3);
+        assert!(index
+            .search(&Query::parse("steady").unwrap(), 10)
+            .unwrap()
+            .is_empty());
+        let hits = index
+            .search(&Query::parse("treasury").unwrap(), 10)
+            .unwrap();
+        assert_eq!(titles(&hits), vec!["Fed keeps rates unchanged"]);
+    }
+}
@@ -0,0 +1,10 @@
+//! Offline search
+//!
+//! Full-text index over every fetched item plus the query language used
+//! to search it.
+
+pub mod index;
+pub mod query;
+
+pub use index::{SearchHit, SearchIndex};
+pub use query::{Query, QueryError};
@@ -0,0 +1,263 @@
+//! Search query language
+//!
+//! Free words must all match; `field:value` pairs filter or scope them:
+//!
+//! ```text
+//! fed rates provider:finnhub since:7d
+//! title:earnings tag:AAPL -rumor "guidance cut"
+//! ```
+//!
+//! Filters: `provider:`, `tag:`, `author:`, `source:`, `since:`, `until:`.
+//! Scopes: `title:`, `summary:`, `content:`. A trailing `*` matches a prefix.
+
+use crate::utils::parser::parse_since;
+use chrono::{DateTime, Utc};
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum QueryError {
+    #[error("Invalid date '{0}' (use e.g. 24h, 7d or 2026-01-31)")]
+    InvalidDate(String),
+}
+
+/// Indexed text fields, stored as bit flags on each posting
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Field {
+    Title = 1,
+    Summary = 2,
+    Content = 4,
+    Tags = 8,
+    Author = 16,
+}
+
+impl Field {
+    pub fn bit(self) -> u8 {
+        self as u8
+    }
+
+    /// Relative importance of a hit in this field
+    pub fn weight(self) -> f32 {
+        match self {
+            Field::Title => 3.0,
+            Field::Tags => 2.5,
+            Field::Author => 2.0,
+            Field::Summary => 1.5,
+            Field::Content => 1.0,
+        }
+    }
+}
+
+/// A word to look up in the index
+#[derive(Debug, Clone, PartialEq)]
+pub struct Term {
+    pub text: String,
+    /// Only count hits in this field
+    pub field: Option<Field>,
+    /// Match any indexed word starting with `text`
+    pub prefix: bool,
+}
+
+/// A parsed search query
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Query {
+    pub terms: Vec<Term>,
+    /// Quoted phrases that must appear verbatim (case-insensitive)
+    pub phrases: Vec<String>,
+    /// Words that must not appear
+    pub excluded: Vec<String>,
+    pub providers: Vec<String>,
+    pub tags: Vec<String>,
+    pub authors: Vec<String>,
+    pub sources: Vec<String>,
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+}
+
+impl Query {
+    /// Parse a query string
+    pub fn parse(input: &str) -> Result<Self, QueryError> {
+        let now = Utc::now();
+        let mut query = Query::default();
+
+        for token in split_query(input) {
+            if let Some(phrase) = token.strip_prefix('"') {
+                let phrase = phrase.trim_end_matches('"').to_lowercase();
+                query
+                    .terms
+                    .extend(tokenize(&phrase).into_iter().map(|text| Term {
+                        text,
+                        field: None,
+                        prefix: false,
+                    }));
+                if !phrase.trim().is_empty() {
+                    query.phrases.push(phrase);
+                }
+                continue;
+            }
+
+            if let Some(word) = token.strip_prefix('-').filter(|w| !w.is_empty()) {
+                query.excluded.extend(tokenize(word));
+                continue;
+            }
+
+            let (key, value) = match token.split_once(':') {
+                Some((key, value)) if !value.is_empty() => (key.to_lowercase(), value),
+                _ => (String::new(), token.as_str()),
+            };
+
+            match key.as_str() {
+                "provider" => query.providers.push(value.to_lowercase()),
+                "tag" => query.tags.push(value.to_lowercase()),
+                "author" | "by" => query.authors.push(value.to_lowercase()),
+                "source" => query.sources.push(value.to_lowercase()),
+                "since" => {
+                    query.since = Some(
+                        parse_since(value, now)
+                            .ok_or_else(|| QueryError::InvalidDate(value.to_string()))?,
+                    )
+                }
+                "until" | "before" => {
+                    query.until = Some(
+                        parse_since(value, now)
+                            .ok_or_else(|| QueryError::InvalidDate(value.to_string()))?,
+                    )
+                }
+                "title" => query.push_terms(value, Some(Field::Title)),
+                "summary" => query.push_terms(value, Some(Field::Summary)),
+                "content" | "body" => query.push_terms(value, Some(Field::Content)),
+                // Unknown keys (e.g. "10:30" or a URL) are just text
+                _ => query.push_terms(&token, None),
+            }
+        }
+
+        Ok(query)
+    }
+
+    /// Whether the query has no words and no filters
+    pub fn is_empty(&self) -> bool {
+        *self == Query::default()
+    }
+
+    /// Whether the query narrows results by something other than words
+    /// (provider, tag, author, source, dates, phrases or exclusions)
+    pub fn has_filters(&self) -> bool {
+        !self.phrases.is_empty()
+            || !self.excluded.is_empty()
+            || !self.providers.is_empty()
+            || !self.tags.is_empty()
+            || !self.authors.is_empty()
+            || !self.sources.is_empty()
+            || self.since.is_some()
+            || self.until.is_some()
+    }
+
+    fn push_terms(&mut self, text: &str, field: Option<Field>) {
+        let prefix = text.ends_with('*');
+        let words = tokenize(text.trim_end_matches('*'));
+        let last = words.len().saturating_sub(1);
+        self.terms
+            .extend(words.into_iter().enumerate().map(|(idx, text)| Term {
+                text,
+                field,
+                prefix: prefix && idx == last,
+            }));
+    }
+}
+
+/// Split on whitespace, keeping "quoted phrases" together
+fn split_query(input: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+
+    for c in input.chars() {
+        match c {
+            '"' if in_quotes => {
+                current.push(c);
+                tokens.push(std::mem::take(&mut current));
+                in_quotes = false;
+            }
+            '"' if current.is_empty() => {
+                current.push(c);
+                in_quotes = true;
+            }
+            c if c.is_whitespace() && !in_quotes => {
+                if !current.is_empty() {
+                    tokens.push(std::mem::take(&mut current));
+                }
+            }
+            c => current.push(c),
+        }
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+
+    tokens
+}
+
+/// Common words that are not worth indexing
+const STOP_WORDS: &[&str] = &[
+    "an", "and", "are", "as", "at", "be", "by", "for", "from", "has", "in", "is", "it", "of", "on",
+    "or", "that", "the", "this", "to", "was", "with",
+];
+
+/// Split text into lowercase index terms
+pub fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|t| !t.is_empty())
+        .map(|t| t.to_lowercase())
+        .filter(|t| {
+            (t.chars().count() > 1 || t.chars().all(|c| c.is_ascii_digit()))
+                && !STOP_WORDS.contains(&t.as_str())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_filters_and_terms() {
+        let query = Query::parse("Fed rates provider:Reddit tag:AAPL since:7d").unwrap();
+
+        assert_eq!(
+            query
+                .terms
+                .iter()
+                .map(|t| t.text.as_str())
+                .collect::<Vec<_>>(),
+            vec!["fed", "rates"]
+        );
+        assert_eq!(query.providers, vec!["reddit"]);
+        assert_eq!(query.tags, vec!["aapl"]);
+        assert!(query.since.unwrap() < Utc::now());
+    }
+
+    #[test]
+    fn test_parse_scopes_phrases_and_exclusions() {
+        let query = Query::parse(r#"title:earn* -rumor "Guidance cut""#).unwrap();
+
+        assert_eq!(
+            query.terms[0],
+            Term {
+                text: "earn".to_string(),
+                field: Some(Field::Title),
+                prefix: true,
+            }
+        );
+        assert_eq!(query.terms.len(), 3);
+        assert_eq!(query.phrases, vec!["guidance cut"]);
+        assert_eq!(query.excluded, vec!["rumor"]);
+    }
+
+    #[test]
+    fn test_invalid_date() {
+        assert_eq!(
+            Query::parse("since:later"),
+            Err(QueryError::InvalidDate("later".to_string()))
+        );
+        assert!(Query::parse("  ").unwrap().is_empty());
+    }
+}
@@ -0,0 +1,263 @@
+//! Minimal HTTP/1.1 handling
+//!
+//! Just enough for a local read-only API: request heads without bodies, one
+//! request per connection, JSON responses and an event-stream preamble.
+
+use serde::Serialize;
+use std::collections::HashMap;
+use std::io;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+/// Longest request head accepted before giving up on a client
+const MAX_HEAD_BYTES: usize = 16 * 1024;
+
+/// A parsed request line plus the headers the server checks
+#[derive(Debug, Clone, PartialEq)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    pub query: HashMap<String, String>,
+    pub host: Option<String>,
+    pub origin: Option<String>,
+}
+
+impl Request {
+    /// Build a request from its method and target (`/path?query`)
+    pub fn new(method: &str, target: &str) -> Self {
+        let (path, query) = target.split_once('?').unwrap_or((target, ""));
+        Self {
+            method: method.to_uppercase(),
+            path: path.to_string(),
+            query: url::form_urlencoded::parse(query.as_bytes())
+                .into_owned()
+                .collect(),
+            host: None,
+            origin: None,
+        }
+    }
+
+    /// Host name from the `Host` header, without the port
+    pub fn host_name(&self) -> Option<&str> {
+        let host = self.host.as_deref()?;
+        Some(match host.strip_prefix('[') {
+            // [::1]:8080
+            Some(rest) => rest.split(']').next().unwrap_or_default(),
+            None => host.rsplit_once(':').map(|(name, _)| name).unwrap_or(host),
+        })
+    }
+
+    /// A query-string parameter, if present and non-empty
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.query
+            .get(name)
+            .map(|v| v.as_str())
+            .filter(|v| !v.is_empty())
+    }
+
+    /// Path segments without empty parts, e.g. `["items", "42", "comments"]`
+    pub fn segments(&self) -> Vec<&str> {
+        self.path.split('/').filter(|s| !s.is_empty()).collect()
+    }
+}
+
+/// Read a request head; `None` when the client closed without sending one
+pub async fn read_request<R: AsyncBufRead + Unpin>(reader: &mut R) -> io::Result<Option<Request>> {
+    let mut line = String::new();
+    if read_line(reader, &mut line, MAX_HEAD_BYTES).await? == 0 {
+        return Ok(None);
+    }
+
+    let mut parts = line.split_whitespace();
+    let (Some(method), Some(target), Some(version)) = (parts.next(), parts.next(), parts.next())
+    else {
+        return Err(invalid("malformed request line"));
+    };
+    if !version.starts_with("HTTP/1.") {
+        return Err(invalid("unsupported HTTP version"));
+    }
+    let mut request = Request::new(method, target);
+
+    // Read headers up to the blank line, keeping Host and Origin
+    let mut total = line.len();
+    loop {
+        line.clear();
+        let read = read_line(reader, &mut line, MAX_HEAD_BYTES - total).await?;
+        total += read;
+        if read == 0 || line.trim_end().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            let value = Some(value.trim().to_string());
+            if name.eq_ignore_ascii_case("host") {
+                request.host = value;
+            } else if name.eq_ignore_ascii_case("origin") {
+                request.origin = value;
+            }
+        }
+    }
+
+    Ok(Some(request))
+}
+
+/// Read one line of at most `limit` bytes; longer lines are an error rather
+/// than being buffered whole
+async fn read_line<R: AsyncBufRead + Unpin>(
+    reader: &mut R,
+    line: &mut String,
+    limit: usize,
+) -> io::Result<usize> {
+    let mut bytes = Vec::new();
+    let read = (&mut *reader)
+        .take(limit as u64)
+        .read_until(b'\n', &mut bytes)
+        .await?;
+    if read == limit && bytes.last() != Some(&b'\n') {
+        return Err(invalid("request head too large"));
+    }
+    line.push_str(std::str::from_utf8(&bytes).map_err(|_| invalid("request head is not UTF-8"))?);
+    Ok(read)
+}
+
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+/// A complete response with a body
+#[derive(Debug, Clone, PartialEq)]
+pub struct Response {
+    pub status: u16,
+    pub content_type: &'static str,
+    pub body: String,
+}
+
+impl Response {
+    /// 200 with a JSON body
+    pub fn json<T: Serialize>(value: &T) -> Self {
+        match serde_json::to_string(value) {
+            Ok(body) => Self {
+                status: 200,
+                content_type: "application/json",
+                body,
+            },
+            Err(e) => Self::error(500, &e.to_string()),
+        }
+    }
+
+    /// 200 with an Atom feed body
+    pub fn atom(body: String) -> Self {
+        Self {
+            status: 200,
+            content_type: "application/atom+xml; charset=utf-8",
+            body,
+        }
+    }
+
+    /// An error status with a `{"error": ...}` body
+    pub fn error(status: u16, message: &str) -> Self {
+        Self {
+            status,
+            content_type: "application/json",
+            body: serde_json::json!({ "error": message }).to_string(),
+        }
+    }
+
+    /// Write the response; `allow_origin` is an origin from
+    /// `[server] allowed_origins` that may read it cross-origin
+    pub async fn write_to<W: AsyncWrite + Unpin>(
+        &self,
+        writer: &mut W,
+        allow_origin: Option<&str>,
+    ) -> io::Result<()> {
+        let head = format!(
+            "HTTP/1.1 {} {}\r\n\
+             Content-Type: {}\r\n\
+             Content-Length: {}\r\n\
+             {}\
+             Connection: close\r\n\r\n",
+            self.status,
+            reason(self.status),
+            self.content_type,
+            self.body.len(),
+            cors_headers(allow_origin)
+        );
+        writer.write_all(head.as_bytes()).await?;
+        writer.write_all(self.body.as_bytes()).await?;
+        writer.flush().await
+    }
+}
+
+/// Start a `text/event-stream` response; events follow until the client leaves
+pub async fn write_event_stream_head<W: AsyncWrite + Unpin>(
+    writer: &mut W,
+    allow_origin: Option<&str>,
+) -> io::Result<()> {
+    let head = format!(
+        "HTTP/1.1 200 OK\r\n\
+         Content-Type: text/event-stream\r\n\
+         Cache-Control: no-cache\r\n\
+         {}\
+         Connection: keep-alive\r\n\r\n",
+        cors_headers(allow_origin)
+    );
+    writer.write_all(head.as_bytes()).await?;
+    writer.flush().await
+}
+
+/// CORS headers letting one allowed origin read the response
+fn cors_headers(allow_origin: Option<&str>) -> String {
+    match allow_origin {
+        Some(origin) => format!(
+            "Access-Control-Allow-Origin: {}\r\nVary: Origin\r\n",
+            origin
+        ),
+        None => String::new(),
+    }
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        403 => "Forbidden",
+        408 => "Request Timeout",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        500 => "Internal Server Error",
+        502 => "Bad Gateway",
+        503 => "Service Unavailable",
+        _ => "",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::BufReader;
+
+    #[tokio::test]
+    async fn test_read_request() {
+        let raw = "GET /items?provider=hackernews&limit=5&q=fed+rates HTTP/1.1\r\n\
+                   Host: localhost\r\n\r\n";
+        let mut reader = BufReader::new(raw.as_bytes());
+        let request = read_request(&mut reader).await.unwrap().unwrap();
+
+        assert_eq!(request.method, "GET");
+        assert_eq!(request.segments(), vec!["items"]);
+        assert_eq!(request.param("provider"), Some("hackernews"));
+        assert_eq!(request.param("q"), Some("fed rates"));
+        assert_eq!(request.param("missing"), None);
+        assert_eq!(request.host_name(), Some("localhost"));
+        assert_eq!(request.origin, None);
+
+        let mut garbage = BufReader::new("nonsense\r\n\r\n".as_bytes());
+        assert!(read_request(&mut garbage).await.is_err());
+        let mut empty = BufReader::new("".as_bytes());
+        assert!(read_request(&mut empty).await.unwrap().is_none());
+
+        // A line that never ends stops at the head limit
+        let endless = "G".repeat(MAX_HEAD_BYTES * 4);
+        let mut endless = BufReader::new(endless.as_bytes());
+        let err = read_request(&mut endless).await.unwrap_err();
+        assert_eq!(err.to_string(), "request head too large");
+    }
+}
@@ -0,0 +1,683 @@
+//! Local HTTP API
+//!
+//! `finterm serve` exposes the provider registry and local index as JSON so
+//! scripts and dashboards can reuse finterm's adapters without linking it:
+//!
+//! ```text
+//! GET /providers                          provider summaries and status
+//! GET /items?provider=&limit=             one provider, or all of them (cached)
+//! GET /search?q=&limit=&provider=         local index plus provider search
+//! GET /items/{id}/comments?provider=      discussion thread of a fetched item
+//! GET /events                             server-sent events of new items
+//! GET /feed.atom                          Atom feed of the latest merged fetch
+//! GET /bookmarks.atom                     Atom feed of bookmarks
+//! ```
+//!
+//! Requests must name the bind address or localhost in `Host`, which keeps
+//! DNS-rebinding pages out. Browsers may only read responses from the origins
+//! listed in `[server] allowed_origins`; other cross-origin requests are refused.
+
+mod http;
+
+use crate::atom::{self, AtomFeed};
+use crate::bookmarks::Bookmarks;
+use crate::cache::CacheManager;
+use crate::models::{CacheKey, FeedItem};
+use crate::providers::{self, ProviderError, ProviderRegistry};
+use crate::search::{Query, SearchIndex};
+use http::{Request, Response};
+use std::collections::{HashMap, HashSet};
+use std::io;
+use std::net::IpAddr;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
+use thiserror::Error;
+use tokio::io::{AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::broadcast;
+
+const DEFAULT_LIMIT: usize = 50;
+/// Items remembered for comment lookups and new-item detection
+const MAX_REMEMBERED: usize = 10_000;
+const EVENT_BUFFER: usize = 256;
+const KEEPALIVE: Duration = Duration::from_secs(15);
+/// How long a client gets to send its request head
+const HEAD_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Error, Debug)]
+pub enum ServerError {
+    #[error("IO error: {0}")]
+    Io(#[from] io::Error),
+}
+
+pub type Result<T> = std::result::Result<T, ServerError>;
+
+/// Shared by every connection and the background poller
+struct ServerState {
+    registry: ProviderRegistry,
+    index: Option<SearchIndex>,
+    bookmarks: Option<Bookmarks>,
+    /// Fetched items are reused from here until the TTL (seconds) runs out
+    cache: Option<(Mutex<CacheManager>, u64)>,
+    /// Result of the latest fetch across all providers
+    latest: RwLock<Vec<FeedItem>>,
+    /// Items seen so far, keyed by (provider, id)
+    seen: RwLock<HashMap<(String, String), FeedItem>>,
+    events: broadcast::Sender<FeedItem>,
+    poll_limit: usize,
+    /// Origins whose pages may read responses (CORS)
+    allowed_origins: Vec<String>,
+}
+
+/// HTTP front end for a provider registry
+pub struct ApiServer {
+    state: ServerState,
+    poll_interval: Duration,
+}
+
+impl ApiServer {
+    /// Create a server; `poll_limit` items per provider are fetched every `poll_interval`
+    pub fn new(
+        registry: ProviderRegistry,
+        index: Option<SearchIndex>,
+        poll_interval: Duration,
+        poll_limit: usize,
+    ) -> Self {
+        let (events, _) = broadcast::channel(EVENT_BUFFER);
+        Self {
+            state: ServerState {
+                registry,
+                index,
+                bookmarks: None,
+                cache: None,
+                latest: RwLock::new(Vec::new()),
+                seen: RwLock::new(HashMap::new()),
+                events,
+                poll_limit,
+                allowed_origins: Vec::new(),
+            },
+            poll_interval,
+        }
+    }
+
+    /// Also publish these bookmarks at `/bookmarks.atom`
+    pub fn with_bookmarks(mut self, bookmarks: Bookmarks) -> Self {
+        self.state.bookmarks = Some(bookmarks);
+        self
+    }
+
+    /// Serve `/items` from this cache while entries are younger than `ttl` seconds
+    pub fn with_cache(mut self, cache: CacheManager, ttl: u64) -> Self {
+        self.state.cache = Some((Mutex::new(cache), ttl));
+        self
+    }
+
+    /// Let pages from these origins read responses, e.g. `http://localhost:3000`
+    pub fn with_allowed_origins(mut self, origins: Vec<String>) -> Self {
+        self.state.allowed_origins = origins;
+        self
+    }
+
+    /// Accept connections until the listener fails
+    pub async fn serve(self, listener: TcpListener) -> Result<()> {
+        let state = Arc::new(self.state);
+
+        let poller = Arc::clone(&state);
+        let interval = self.poll_interval;
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                let items = poller.registry.fetch_all(poller.poll_limit).await;
+                poller.set_latest(&items);
+                poller.record(items);
+            }
+        });
+
+        loop {
+            let (stream, peer) = listener.accept().await?;
+            let state = Arc::clone(&state);
+            tokio::spawn(async move {
+                if let Err(e) = handle_connection(state, stream).await {
+                    tracing::debug!("Connection from {} ended: {}", peer, e);
+                }
+            });
+        }
+    }
+}
+
+impl ServerState {
+    /// Index fetched items and announce the ones not seen before
+    fn record(&self, items: Vec<FeedItem>) -> Vec<FeedItem> {
+        if let Some(index) = &self.index {
+            if let Err(e) = index.index_items(&items) {
+                tracing::warn!("Failed to index items: {}", e);
+            }
+        }
+
+        let mut seen = self.seen.write().unwrap_or_else(|e| e.into_inner());
+        for item in &items {
+            let key = (item.provider_id.clone(), item.id.clone());
+            if seen.insert(key, item.clone()).is_none() {
+                // Nobody listening is fine
+                let _ = self.events.send(item.clone());
+            }
+        }
+
+        if seen.len() > MAX_REMEMBERED {
+            let mut by_age: Vec<_> = seen
+                .iter()
+                .map(|(key, item)| (item.published_at, key.clone()))
+                .collect();
+            by_age.sort();
+            for (_, key) in by_age.into_iter().take(seen.len() - MAX_REMEMBERED) {
+                seen.remove(&key);
+            }
+        }
+
+        items
+    }
+
+    /// Remember items for comment lookups without announcing them
+    fn remember(&self, items: &[FeedItem]) {
+        let mut seen = self.seen.write().unwrap_or_else(|e| e.into_inner());
+        for item in items {
+            seen.entry((item.provider_id.clone(), item.id.clone()))
+                .or_insert_with(|| item.clone());
+        }
+    }
+
+    fn set_latest(&self, items: &[FeedItem]) {
+        *self.latest.write().unwrap_or_else(|e| e.into_inner()) = items.to_vec();
+    }
+
+    fn find(&self, id: &str, provider: Option<&str>) -> Option<FeedItem> {
+        let seen = self.seen.read().unwrap_or_else(|e| e.into_inner());
+        seen.values()
+            .find(|item| item.id == id && provider.iter().all(|p| item.provider_id == *p))
+            .cloned()
+    }
+
+    /// Check `Host` and `Origin`. Returns the origin to allow via CORS, or
+    /// the response refusing the request. `local_ip` is the address the
+    /// connection came in on.
+    fn authorize<'a>(
+        &self,
+        request: &'a Request,
+        local_ip: IpAddr,
+    ) -> std::result::Result<Option<&'a str>, Response> {
+        let host_ok = match request.host_name() {
+            Some(name) if name.eq_ignore_ascii_case("localhost") => true,
+            Some(name) => name
+                .parse::<IpAddr>()
+                .map(|ip| ip.is_loopback() || ip == local_ip)
+                .unwrap_or(false),
+            None => false,
+        };
+        if !host_ok {
+            return Err(Response::error(
+                403,
+                "Host must be localhost or the bind address",
+            ));
+        }
+
+        match request.origin.as_deref() {
+            None => Ok(None),
+            Some(origin) if self.allowed_origins.iter().any(|o| o == origin) => Ok(Some(origin)),
+            Some(origin) => Err(Response::error(
+                403,
+                &format!("Origin '{}' is not in [server] allowed_origins", origin),
+            )),
+        }
+    }
+
+    async fn route(&self, request: &Request) -> Response {
+        if request.method != "GET" {
+            return Response::error(405, "Only GET is supported");
+        }
+
+        match request.segments().as_slice() {
+            ["providers"] => Response::json(&self.registry.status_summary()),
+            ["items"] => self.items(request).await,
+            ["search"] => self.search(request).await,
+            ["items", id, "comments"] => self.comments(id, request.param("provider")).await,
+            ["feed.atom"] => self.feed(),
+            ["bookmarks.atom"] => self.bookmarks_feed(),
+            _ => Response::error(404, "Not found"),
+        }
+    }
+
+    async fn items(&self, request: &Request) -> Response {
+        let limit = match limit_param(request) {
+            Ok(limit) => limit,
+            Err(response) => return response,
+        };
+
+        let provider = request.param("provider");
+        let key = CacheKey::ProviderItems(provider.unwrap_or("all").to_string());
+        if let Some(mut items) = self.cached_items(&key, limit) {
+            items.truncate(limit);
+            return Response::json(&self.record(items));
+        }
+
+        let items = match provider {
+            Some(provider) => match self.registry.fetch_from(provider, limit).await {
+                Ok(items) => items,
+                Err(ProviderError::NotConfigured(e)) => return Response::error(404, &e),
+                Err(e) => return Response::error(502, &e.to_string()),
+            },
+            None => {
+                let items = self.registry.fetch_all(limit).await;
+                self.set_latest(&items);
+                items
+            }
+        };
+        self.cache_items(key, limit, &items);
+
+        Response::json(&self.record(items))
+    }
+
+    /// Items cached for `key` by a fetch of at least `limit`, if still fresh
+    fn cached_items(&self, key: &CacheKey, limit: usize) -> Option<Vec<FeedItem>> {
+        let (cache, _) = self.cache.as_ref()?;
+        let mut cache = cache.lock().unwrap_or_else(|e| e.into_inner());
+        let (fetched_limit, items) = cache.get::<(usize, Vec<FeedItem>)>(key.clone()).ok()?;
+        (fetched_limit >= limit).then_some(items)
+    }
+
+    fn cache_items(&self, key: CacheKey, limit: usize, items: &[FeedItem]) {
+        let Some((cache, ttl)) = &self.cache else {
+            return;
+        };
+        let mut cache = cache.lock().unwrap_or_else(|e| e.into_inner());
+        if let Err(e) = cache.set(key, (limit, items), *ttl) {
+            tracing::warn!("Failed to cache items: {}", e);
+        }
+    }
+
+    /// Local index hits first, then results from providers that search
+    /// their source; `provider=` asks just that provider
+    async fn search(&self, request: &Request) -> Response {
+        let Some(q) = request.param("q") else {
+            return Response::error(400, "Missing q parameter");
+        };
+        let limit = match limit_param(request) {
+            Ok(limit) => limit,
+            Err(response) => return response,
+        };
+
+        if let Some(id) = request.param("provider") {
+            let Some(provider) = self.registry.get(id) else {
+                return Response::error(404, &format!("Provider '{}' not found", id));
+            };
+            if !provider.supports_search() {
+                return Response::error(400, &format!("Provider '{}' has no search", id));
+            }
+            return match provider.search(q, limit).await {
+                Ok(mut items) => {
+                    self.registry.enrich(&mut items);
+                    self.remember(&items);
+                    Response::json(&items)
+                }
+                Err(e) => Response::error(502, &e.to_string()),
+            };
+        }
+
+        let mut items: Vec<FeedItem> = match &self.index {
+            Some(index) => {
+                let query = match Query::parse(q) {
+                    Ok(query) => query,
+                    Err(e) => return Response::error(400, &e.to_string()),
+                };
+                match index.search(&query, limit) {
+                    Ok(hits) => hits.into_iter().map(|hit| hit.item).collect(),
+                    Err(e) => return Response::error(500, &e.to_string()),
+                }
+            }
+            None => Vec::new(),
+        };
+
+        let found: HashSet<(String, String)> = items
+            .iter()
+            .map(|item| (item.provider_id.clone(), item.id.clone()))
+            .collect();
+        let remote: Vec<FeedItem> = self
+            .registry
+            .search(q, limit)
+            .await
+            .into_iter()
+            .filter(|item| !found.contains(&(item.provider_id.clone(), item.id.clone())))
+            .collect();
+        self.remember(&remote);
+        items.extend(remote);
+
+        Response::json(&items)
+    }
+
+    async fn comments(&self, id: &str, provider: Option<&str>) -> Response {
+        let Some(item) = self.find(id, provider) else {
+            return Response::error(
+                404,
+                &format!("Unknown item '{}' (fetch it from /items first)", id),
+            );
+        };
+
+        match providers::fetch_comments(&item, 3).await {
+            Ok(comments) => Response::json(&comments),
+            Err(e) => Response::error(502, &e.to_string()),
+        }
+    }
+
+    fn feed(&self) -> Response {
+        let items = self
+            .latest
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone();
+        let feed = AtomFeed::from_items(atom::MERGED_FEED_ID, "finterm", atom::dedupe(items));
+        Response::atom(feed.render())
+    }
+
+    fn bookmarks_feed(&self) -> Response {
+        let Some(bookmarks) = &self.bookmarks else {
+            return Response::error(503, "Bookmarks are unavailable");
+        };
+
+        match bookmarks.list() {
+            Ok(saved) => Response::atom(atom::bookmarks_feed(saved).render()),
+            Err(e) => Response::error(500, &e.to_string()),
+        }
+    }
+}
+
+fn limit_param(request: &Request) -> std::result::Result<usize, Response> {
+    match request.param("limit") {
+        None => Ok(DEFAULT_LIMIT),
+        Some(limit) => limit
+            .parse()
+            .ok()
+            .filter(|l| *l > 0)
+            .ok_or_else(|| Response::error(400, &format!("Invalid limit '{}'", limit))),
+    }
+}
+
+async fn handle_connection(state: Arc<ServerState>, stream: TcpStream) -> io::Result<()> {
+    let local_ip = stream.local_addr()?.ip();
+    let (read, mut write) = stream.into_split();
+    let mut reader = BufReader::new(read);
+
+    let head = tokio::time::timeout(HEAD_TIMEOUT, http::read_request(&mut reader)).await;
+    let Ok(head) = head else {
+        return Response::error(408, "Timed out waiting for the request")
+            .write_to(&mut write, None)
+            .await;
+    };
+    let request = match head {
+        Ok(Some(request)) => request,
+        Ok(None) => return Ok(()),
+        Err(e) if e.kind() == io::ErrorKind::InvalidData => {
+            return Response::error(400, &e.to_string())
+                .write_to(&mut write, None)
+                .await;
+        }
+        Err(e) => return Err(e),
+    };
+
+    let allow_origin = match state.authorize(&request, local_ip) {
+        Ok(origin) => origin,
+        Err(response) => return response.write_to(&mut write, None).await,
+    };
+
+    if request.method == "GET" && request.segments() == ["events"] {
+        return stream_events(&state, &mut write, allow_origin).await;
+    }
+
+    state
+        .route(&request)
+        .await
+        .write_to(&mut write, allow_origin)
+        .await
+}
+
+/// Send each newly fetched item as an `item` event until the client disconnects
+async fn stream_events<W: AsyncWrite + Unpin>(
+    state: &ServerState,
+    writer: &mut W,
+    allow_origin: Option<&str>,
+) -> io::Result<()> {
+    let mut events = state.events.subscribe();
+    http::write_event_stream_head(writer, allow_origin).await?;
+
+    let mut keepalive = tokio::time::interval(KEEPALIVE);
+    keepalive.tick().await;
+
+    loop {
+        let chunk = tokio::select! {
+            event = events.recv() => match event {
+                Ok(item) => {
+                    let data = serde_json::to_string(&item).map_err(io::Error::other)?;
+                    format!("event: item\ndata: {}\n\n", data)
+                }
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    format!(": skipped {} items\n\n", skipped)
+                }
+                Err(broadcast::error::RecvError::Closed) => return Ok(()),
+            },
+            _ = keepalive.tick() => ": keepalive\n\n".to_string(),
+        };
+
+        writer.write_all(chunk.as_bytes()).await?;
+        writer.flush().await?;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::{FeedProvider, ProviderStatus};
+    use async_trait::async_trait;
+    use chrono::Utc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tokio::io::{AsyncBufReadExt, AsyncReadExt};
+
+    /// Returns one new item per fetch
+    struct CountingProvider {
+        fetches: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl FeedProvider for CountingProvider {
+        fn id(&self) -> &str {
+            "mock"
+        }
+        fn name(&self) -> &str {
+            "Mock"
+        }
+        fn description(&self) -> &str {
+            "Test provider"
+        }
+        fn icon(&self) -> &str {
+            "M"
+        }
+        fn status(&self) -> ProviderStatus {
+            ProviderStatus::Ready
+        }
+        fn supports_search(&self) -> bool {
+            true
+        }
+        async fn search(&self, query: &str, _limit: usize) -> providers::Result<Vec<FeedItem>> {
+            Ok(vec![FeedItem::new(
+                format!("hit-{}", query),
+                "mock".to_string(),
+                format!("About {}", query),
+                "Mock".to_string(),
+                Utc::now(),
+            )])
+        }
+        async fn fetch_items(&self, _limit: usize) -> providers::Result<Vec<FeedItem>> {
+            let n = self.fetches.fetch_add(1, Ordering::SeqCst);
+            Ok(vec![FeedItem::new(
+                format!("item-{}", n),
+                "mock".to_string(),
+                format!("Story {}", n),
+                "Mock".to_string(),
+                Utc::now(),
+            )])
+        }
+    }
+
+    impl ServerState {
+        fn with_origins(mut self, origins: Vec<String>) -> Self {
+            self.allowed_origins = origins;
+            self
+        }
+    }
+
+    fn state() -> ServerState {
+        let mut registry = ProviderRegistry::new();
+        registry.register(CountingProvider {
+            fetches: AtomicUsize::new(0),
+        });
+        ApiServer::new(registry, None, Duration::from_secs(3600), 10).state
+    }
+
+    async fn get(state: &ServerState, target: &str) -> Response {
+        state.route(&Request::new("GET", target)).await
+    }
+
+    #[tokio::test]
+    async fn test_routes() {
+        let state = state();
+
+        let providers = get(&state, "/providers").await;
+        assert_eq!(providers.status, 200);
+        assert!(providers.body.contains(r#""id":"mock""#));
+        assert!(providers.body.contains(r#""status":{"state":"ready"}"#));
+
+        let items = get(&state, "/items?provider=mock&limit=5").await;
+        assert_eq!(items.status, 200);
+        assert!(items.body.contains("item-0"));
+
+        assert_eq!(get(&state, "/items?provider=nope").await.status, 404);
+        assert_eq!(get(&state, "/items?limit=zero").await.status, 400);
+        // No local index: provider search still answers
+        let search = get(&state, "/search?q=fed").await;
+        assert_eq!(search.status, 200);
+        assert!(search.body.contains(r#""id":"hit-fed""#));
+        assert!(get(&state, "/search?q=fed&provider=mock")
+            .await
+            .body
+            .contains("About fed"));
+        assert_eq!(get(&state, "/search?q=fed&provider=nope").await.status, 404);
+        assert_eq!(get(&state, "/search").await.status, 400);
+        assert_eq!(get(&state, "/unknown").await.status, 404);
+        assert_eq!(get(&state, "/bookmarks.atom").await.status, 503);
+
+        // The merged feed is the latest fetch across providers
+        get(&state, "/items").await;
+        let feed = get(&state, "/feed.atom").await;
+        assert!(feed.content_type.starts_with("application/atom+xml"));
+        assert_eq!(feed.body.matches("<entry>").count(), 1);
+        assert_eq!(get(&state, "/items/missing/comments").await.status, 404);
+        // Known item, but the mock source has no threads
+        assert_eq!(get(&state, "/items/item-0/comments").await.status, 502);
+        assert_eq!(
+            state.route(&Request::new("POST", "/items")).await.status,
+            405
+        );
+    }
+
+    #[tokio::test]
+    async fn test_items_served_from_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = CacheManager::new(dir.path().to_path_buf(), 10).unwrap();
+        let mut state = state();
+        state.cache = Some((Mutex::new(cache), 3600));
+
+        let first = get(&state, "/items?provider=mock&limit=5").await;
+        assert!(first.body.contains("item-0"));
+        // Fresh entry: no second fetch
+        let again = get(&state, "/items?provider=mock&limit=5").await;
+        assert_eq!(again.body, first.body);
+        // A bigger page than was cached goes back to the provider
+        let bigger = get(&state, "/items?provider=mock&limit=20").await;
+        assert!(bigger.body.contains("item-1"));
+    }
+
+    #[tokio::test]
+    async fn test_event_stream_over_tcp() {
+        let mut registry = ProviderRegistry::new();
+        registry.register(CountingProvider {
+            fetches: AtomicUsize::new(0),
+        });
+        let server = ApiServer::new(registry, None, Duration::from_secs(3600), 10);
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(server.serve(listener));
+
+        // Subscribe, then trigger a fetch that brings in a new item
+        let mut events = BufReader::new(TcpStream::connect(addr).await.unwrap());
+        events
+            .get_mut()
+            .write_all(b"GET /events HTTP/1.1\r\nHost: 127.0.0.1\r\n\r\n")
+            .await
+            .unwrap();
+        let mut line = String::new();
+        while line != "\r\n" {
+            line.clear();
+            events.read_line(&mut line).await.unwrap();
+        }
+
+        let mut client = TcpStream::connect(addr).await.unwrap();
+        client
+            .write_all(b"GET /items HTTP/1.1\r\nHost: localhost\r\n\r\n")
+            .await
+            .unwrap();
+        let mut response = String::new();
+        client.read_to_string(&mut response).await.unwrap();
+        assert!(response.starts_with("HTTP/1.1 200 OK"));
+
+        let mut event = String::new();
+        while !event.starts_with("data:") {
+            event.clear();
+            tokio::time::timeout(Duration::from_secs(5), events.read_line(&mut event))
+                .await
+                .unwrap()
+                .unwrap();
+        }
+        assert!(event.contains(r#""provider_id":"mock""#));
+    }
+
+    #[test]
+    fn test_authorize_host_and_origin() {
+        let state = state().with_origins(vec!["http://localhost:3000".to_string()]);
+        let local: IpAddr = "127.0.0.1".parse().unwrap();
+        let request = |host: Option<&str>, origin: Option<&str>| {
+            let mut request = Request::new("GET", "/items");
+            request.host = host.map(String::from);
+            request.origin = origin.map(String::from);
+            request
+        };
+        let status = |r: &Request| {
+            state
+                .authorize(r, local)
+                .map(|origin| origin.map(String::from))
+                .map_err(|e| e.status)
+        };
+
+        assert_eq!(status(&request(Some("localhost:8080"), None)), Ok(None));
+        assert_eq!(status(&request(Some("127.0.0.1:8080"), None)), Ok(None));
+        assert_eq!(status(&request(Some("[::1]:8080"), None)), Ok(None));
+        // DNS rebinding: a foreign name resolving to 127.0.0.1
+        assert_eq!(status(&request(Some("evil.example:8080"), None)), Err(403));
+        assert_eq!(status(&request(None, None)), Err(403));
+
+        let allowed = request(Some("localhost:8080"), Some("http://localhost:3000"));
+        assert_eq!(
+            status(&allowed),
+            Ok(Some("http://localhost:3000".to_string()))
+        );
+        let foreign = request(Some("localhost:8080"), Some("https://evil.example"));
+        assert_eq!(status(&foreign), Err(403));
+    }
+}
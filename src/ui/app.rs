@@ -2,22 +2,39 @@
 //!
 //! Main application state and event handling with provider-based architecture
 
+use crate::bookmarks::Bookmarks;
+use crate::cache::archive::{Archive, HistoryPoint, Snapshot};
+use crate::cache::threads::{thread_key, ThreadVisits};
 use crate::cache::CacheManager;
 use crate::config::Config;
 use crate::models::Comment;
-use crate::models::FeedItem;
+use crate::models::{CacheKey, FeedItem, GroupBy, LinkPreview, SentimentLabel, SortMode};
+use crate::notifications::Notifier;
+use crate::papers::{self, QueueEntry, ReadingQueue};
+use crate::providers::link_preview::{self, is_extractable_url};
+use crate::providers::readability;
+use crate::providers::reddit::{self, RedditProvider, RedditScope};
 use crate::providers::{
-    ArxivProvider, CratesIoProvider, FinnhubProvider, HackerNewsProvider, ProviderRegistry,
-    RedditProvider,
+    self, ArxivProvider, ArxivSearch, HackerNewsProvider, HnLive, HnProfile, HnSearch, HnUpdate,
+    ProviderRegistry,
 };
+use crate::search::{Query, SearchIndex};
 use crate::ui::views;
-use crate::utils::Action;
-use crossterm::event::{self, Event, KeyCode, KeyEvent};
+use crate::ui::views::dashboard::Prompt;
+use crate::utils::parser::parse_as_of;
+use crate::utils::{clipboard, fuzzy, Action};
+use crate::webhooks::{Webhook, WebhookDispatcher};
+use chrono::{DateTime, Local, Utc};
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyModifiers};
 use ratatui::backend::Backend;
 use ratatui::Terminal;
-use std::io;
+use reqwest::Client;
+use std::collections::{HashMap, HashSet};
+use std::io::{self, Write};
+use std::path::PathBuf;
 use std::time::{Duration, Instant};
 use thiserror::Error;
+use tokio::sync::mpsc;
 
 #[derive(Error, Debug)]
 pub enum AppError {
@@ -33,6 +50,9 @@ pub enum AppError {
 
 pub type Result<T> = std::result::Result<T, AppError>;
 
+/// How long a failed link preview waits before it is fetched again
+const PREVIEW_RETRY_DELAY: Duration = Duration::from_secs(60);
+
 /// Application view state
 #[derive(Debug, Clone, PartialEq)]
 pub enum AppState {
@@ -42,12 +62,36 @@ pub enum AppState {
     Dashboard,
     /// Single feed view
     Feed(String), // provider_id
+    /// Local index search results
+    Search(String), // query
+    /// Archived snapshot of a list view
+    Archive(String), // view key
     /// Article/item detail view
     Article,
     /// Comments view for current item
     Comments,
     /// Help screen
     Help,
+    /// Log of notifications that fired
+    Notifications,
+    /// HN and Reddit threads about the article's link
+    Discussions,
+    /// Hacker News user profile
+    User,
+    /// Queued arXiv papers
+    ReadingQueue,
+}
+
+/// An open user profile and the view it was opened from
+struct UserPage {
+    profile: HnProfile,
+    show_comments: bool,
+    selected: usize,
+    back_state: AppState,
+    back_item: Option<FeedItem>,
+    back_return_state: AppState,
+    back_comments: Vec<Comment>,
+    back_comments_selected: usize,
 }
 
 /// Main application struct with provider-based architecture
@@ -59,20 +103,107 @@ pub struct App {
     // Provider system
     pub registry: ProviderRegistry,
     pub cache: CacheManager,
+    pub index: Option<SearchIndex>,
+    pub archive: Option<Archive>,
+    pub thread_visits: Option<ThreadVisits>,
+    pub bookmarks: Option<Bookmarks>,
+    pub reading_queue: Option<ReadingQueue>,
+    pub webhooks: Option<WebhookDispatcher>,
+    pub http_client: Client,
 
     // Data
+    /// Everything fetched for the current view, before filtering
+    pub source_items: Vec<FeedItem>,
+    /// Items the provider has returned for the current feed, hidden ones
+    /// included; the offset of the next page
+    feed_offset: usize,
+    /// Items currently shown in the list
     pub items: Vec<FeedItem>,
     pub selected_idx: usize,
     pub current_item: Option<FeedItem>,
 
+    // List filters
+    pub sentiment_filter: Option<SentimentLabel>,
+    pub ticker_filter: Option<String>,
+    /// Local fuzzy filter query and whether it is being typed
+    pub filter_query: String,
+    pub filter_editing: bool,
+    /// Open line prompt and its text
+    pub prompt: Option<(Prompt, String)>,
+    /// List view to return to when leaving the article view
+    return_state: AppState,
+
+    // List ordering, remembered per view (dashboard or provider id)
+    pub sort_mode: SortMode,
+    pub group_by: GroupBy,
+    view_orders: HashMap<String, (SortMode, GroupBy)>,
+
+    // Archive browsing: when the shown snapshot was taken, and the selected
+    // item's rank history keyed by item id
+    archive_at: Option<DateTime<Utc>>,
+    item_history: Option<(String, Vec<HistoryPoint>)>,
+
     // Landing page state
     pub landing_selected: usize,
 
+    /// Subreddit or multireddit shown in the Reddit feed
+    pub reddit_scope: RedditScope,
+    /// Algolia query shown in the Hacker News feed instead of its stories
+    pub hn_search: Option<String>,
+    /// Export API query shown in the arXiv feed instead of its category
+    pub arxiv_search: Option<String>,
+    /// Streamed rank and story changes while the Hacker News feed is open
+    hn_live: Option<HnLive>,
+    /// Where the config was loaded from, for saving subreddit changes
+    config_path: Option<PathBuf>,
+
     // Comments state
     pub comments: Vec<Comment>,
     pub comments_selected: usize,
     pub comments_scroll: usize,
     pub comments_loading: bool,
+    /// Comments of the open thread added since the last visit
+    new_comment_ids: HashSet<String>,
+    /// Comments added since the last visit, per listed thread
+    new_comment_counts: HashMap<String, i32>,
+
+    // Article state
+    pub content_loading: bool,
+    /// Item whose media was last opened, and the next gallery index
+    media_cursor: Option<(String, usize)>,
+
+    // Notifications for new high-priority items, and the log view's state
+    pub notifier: Notifier,
+    pub notifications_selected: usize,
+    log_return: AppState,
+
+    // Discussions of an article's link, and the article to return to
+    pub discussions: Vec<FeedItem>,
+    pub discussions_selected: usize,
+    discussions_url: String,
+    discussions_return: Option<(FeedItem, AppState)>,
+
+    // User profiles, innermost last
+    user_pages: Vec<UserPage>,
+
+    // Reading queue as listed, and the view (with its article) to return to
+    pub queue_entries: Vec<QueueEntry>,
+    pub queue_selected: usize,
+    queue_return: (AppState, Option<FeedItem>, AppState),
+
+    // PDF downloads finishing in the background: paper title and outcome
+    download_tx: mpsc::UnboundedSender<(String, papers::Result<PathBuf>)>,
+    download_rx: mpsc::UnboundedReceiver<(String, papers::Result<PathBuf>)>,
+
+    // Article bodies extracted in the background: item id, URL and content
+    content_tx: mpsc::UnboundedSender<(String, String, Option<String>)>,
+    content_rx: mpsc::UnboundedReceiver<(String, String, Option<String>)>,
+
+    // Link previews fetched in the background, keyed by URL
+    preview_requested: HashSet<String>,
+    preview_failed: HashMap<String, Instant>,
+    preview_tx: mpsc::UnboundedSender<(String, Option<LinkPreview>)>,
+    preview_rx: mpsc::UnboundedReceiver<(String, Option<LinkPreview>)>,
 
     // UI state
     pub scroll_offset: usize,
@@ -85,59 +216,127 @@ impl App {
     /// Create a new application instance
     pub fn new(config: Config) -> Result<Self> {
         // Create provider registry
-        let mut registry = ProviderRegistry::new();
+        let registry =
+            ProviderRegistry::from_config(&config).map_err(|e| AppError::Config(e.to_string()))?;
 
-        // Register HackerNews provider first (most used)
-        if let Ok(hn) = HackerNewsProvider::new(None) {
-            registry.register(hn);
-        }
+        // Create cache
+        let cache_dir = config.cache_dir();
+        let cache = CacheManager::new(cache_dir, config.cache.max_size_mb)
+            .map_err(|e| AppError::Config(e.to_string()))?;
 
-        // Register arXiv provider (research papers)
-        if let Ok(arxiv) = ArxivProvider::new(Some("cs.ai".to_string())) {
-            registry.register(arxiv);
-        }
+        let index = if config.cache.search_index {
+            SearchIndex::open(&cache)
+                .map_err(|e| tracing::warn!("Search index unavailable: {}", e))
+                .ok()
+        } else {
+            None
+        };
 
-        // Register Crates.io provider
-        if let Ok(cratesio) = CratesIoProvider::new(None) {
-            registry.register(cratesio);
-        }
+        let archive = if config.cache.archive {
+            Archive::open(&cache, config.cache.archive_retention_days)
+                .map_err(|e| tracing::warn!("Archive unavailable: {}", e))
+                .ok()
+        } else {
+            None
+        };
 
-        // Register Reddit provider
-        if let Ok(reddit) = RedditProvider::new(
-            config.reddit.subreddits.clone(),
-            Some(config.reddit.sort.clone()),
-            true,
-        ) {
-            registry.register(reddit);
-        }
+        let thread_visits = ThreadVisits::open(&cache)
+            .map_err(|e| tracing::warn!("Thread visits unavailable: {}", e))
+            .ok();
 
-        // Register Finnhub provider (last - requires API key)
-        if let Ok(finnhub) = FinnhubProvider::new(
-            config.finnhub.api_key.clone(),
-            Some(config.finnhub.category.clone()),
-        ) {
-            registry.register(finnhub);
-        }
+        let bookmarks = Bookmarks::open(&config.data_dir())
+            .map_err(|e| tracing::warn!("Bookmarks unavailable: {}", e))
+            .ok();
 
-        // Create cache
-        let cache_dir = config.cache_dir();
-        let cache = CacheManager::new(cache_dir, config.cache.max_size_mb)
+        let reading_queue = ReadingQueue::open(&config.data_dir())
+            .map_err(|e| tracing::warn!("Reading queue unavailable: {}", e))
+            .ok();
+
+        let webhooks = if config.webhooks.is_empty() {
+            None
+        } else {
+            Webhook::compile_all(&config.webhooks, &config.rules)
+                .map_err(|e| e.to_string())
+                .and_then(|hooks| WebhookDispatcher::open(hooks, &cache).map_err(|e| e.to_string()))
+                .map_err(|e| tracing::warn!("Webhooks unavailable: {}", e))
+                .ok()
+        };
+
+        let notifier = Notifier::from_config(&config.notifications)
+            .map_err(|e| AppError::Config(e.to_string()))?
+            .with_cache(&cache);
+
+        let http_client = Client::builder()
+            .timeout(Duration::from_secs(15))
+            .build()
             .map_err(|e| AppError::Config(e.to_string()))?;
 
+        let (preview_tx, preview_rx) = mpsc::unbounded_channel();
+        let (content_tx, content_rx) = mpsc::unbounded_channel();
+        let (download_tx, download_rx) = mpsc::unbounded_channel();
+
         Ok(Self {
             config,
             state: AppState::Landing, // Start at landing page
             should_quit: false,
             registry,
             cache,
+            index,
+            archive,
+            thread_visits,
+            bookmarks,
+            reading_queue,
+            webhooks,
+            http_client,
+            source_items: Vec::new(),
+            feed_offset: 0,
             items: Vec::new(),
             selected_idx: 0,
             current_item: None,
+            sentiment_filter: None,
+            ticker_filter: None,
+            filter_query: String::new(),
+            filter_editing: false,
+            prompt: None,
+            return_state: AppState::Dashboard,
+            sort_mode: SortMode::default(),
+            group_by: GroupBy::default(),
+            view_orders: HashMap::new(),
+            archive_at: None,
+            item_history: None,
             landing_selected: 0,
+            reddit_scope: RedditScope::All,
+            hn_search: None,
+            arxiv_search: None,
+            hn_live: None,
+            config_path: None,
             comments: Vec::new(),
             comments_selected: 0,
             comments_scroll: 0,
             comments_loading: false,
+            new_comment_ids: HashSet::new(),
+            new_comment_counts: HashMap::new(),
+            content_loading: false,
+            media_cursor: None,
+            notifier,
+            notifications_selected: 0,
+            log_return: AppState::Landing,
+            discussions: Vec::new(),
+            discussions_selected: 0,
+            discussions_url: String::new(),
+            discussions_return: None,
+            user_pages: Vec::new(),
+            queue_entries: Vec::new(),
+            queue_selected: 0,
+            queue_return: (AppState::Landing, None, AppState::Dashboard),
+            download_tx,
+            download_rx,
+            preview_requested: HashSet::new(),
+            preview_failed: HashMap::new(),
+            preview_tx,
+            preview_rx,
+            content_tx,
+            content_rx,
             scroll_offset: 0,
             status_message: None,
             last_update: Instant::now(),
@@ -145,6 +344,12 @@ impl App {
         })
     }
 
+    /// Builder method: save runtime config changes (e.g. subreddits) to `path`
+    pub fn with_config_path(mut self, path: PathBuf) -> Self {
+        self.config_path = Some(path);
+        self
+    }
+
     /// Run the application main loop
     pub async fn run<B: Backend>(&mut self, terminal: &mut Terminal<B>) -> Result<()> {
         loop {
@@ -157,6 +362,17 @@ impl App {
                 self.load_comments_for_current_item().await?;
             }
 
+            // Collect background article extraction and link previews
+            self.apply_article_content();
+            self.apply_link_previews();
+            self.request_link_preview();
+            self.refresh_item_history();
+            self.apply_downloads();
+
+            // Follow live Hacker News changes
+            self.sync_hn_live();
+            self.apply_hn_updates();
+
             // Handle input with timeout
             if event::poll(Duration::from_millis(100))? {
                 if let Event::Key(key) = event::read()? {
@@ -178,17 +394,472 @@ impl App {
 
         let action = map_key_event(key, self.config.ui.vim_mode);
 
+        if self.is_list_view() {
+            if self.prompt.is_some() {
+                self.handle_prompt_input(key).await?;
+                return Ok(());
+            }
+            if self.filter_editing {
+                self.handle_filter_input(key);
+                return Ok(());
+            }
+        }
+
         match &self.state {
             AppState::Landing => self.handle_landing_input(key, action).await?,
-            AppState::Dashboard | AppState::Feed(_) => self.handle_feed_input(key, action).await?,
-            AppState::Article => self.handle_article_input(action),
-            AppState::Comments => self.handle_comments_input(action),
+            AppState::Dashboard
+            | AppState::Feed(_)
+            | AppState::Search(_)
+            | AppState::Archive(_) => self.handle_feed_input(key, action).await?,
+            AppState::Article => self.handle_article_input(action).await?,
+            AppState::Comments => self.handle_comments_input(action).await,
             AppState::Help => self.handle_help_input(action),
+            AppState::Notifications => self.handle_notifications_input(action),
+            AppState::Discussions => self.handle_discussions_input(action),
+            AppState::User => self.handle_user_input(action).await?,
+            AppState::ReadingQueue => self.handle_queue_input(action),
+        }
+
+        Ok(())
+    }
+
+    fn is_reddit_feed(&self) -> bool {
+        matches!(&self.state, AppState::Feed(id) if id == "reddit")
+    }
+
+    /// Whether the feed list is on screen
+    fn is_list_view(&self) -> bool {
+        matches!(
+            self.state,
+            AppState::Dashboard | AppState::Feed(_) | AppState::Search(_) | AppState::Archive(_)
+        )
+    }
+
+    /// Handle typing into the search, time-travel or subreddit prompt
+    async fn handle_prompt_input(&mut self, key: KeyEvent) -> Result<()> {
+        let Some((prompt, input)) = self.prompt.as_mut() else {
+            return Ok(());
+        };
+
+        match key.code {
+            KeyCode::Esc => self.prompt = None,
+            KeyCode::Enter => {
+                let prompt = *prompt;
+                let text = input.trim().to_string();
+                self.prompt = None;
+                // An empty ticker clears the filter; other prompts ignore it
+                if text.is_empty() && prompt != Prompt::Ticker {
+                    return Ok(());
+                }
+                match prompt {
+                    Prompt::Search => self.run_search(&text),
+                    Prompt::AsOf => self.open_archive(&text),
+                    Prompt::Subreddit => self.pick_subreddit(&text).await?,
+                    Prompt::HnSearch => self.search_hn(&text).await?,
+                    Prompt::ArxivSearch => self.search_arxiv(&text).await?,
+                    Prompt::Ticker => self.filter_ticker(&text),
+                }
+            }
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => input.push(c),
+            _ => {}
         }
 
         Ok(())
     }
 
+    /// Show only items tagged with `symbol` (`$TSLA` or `tsla` work too);
+    /// an empty symbol clears the filter
+    fn filter_ticker(&mut self, symbol: &str) {
+        let symbol = symbol.trim_start_matches('$').to_uppercase();
+        self.ticker_filter = (!symbol.is_empty()).then_some(symbol);
+        self.apply_view();
+        self.status_message = Some(match &self.ticker_filter {
+            Some(symbol) => format!("Ticker: {} ({} items)", symbol, self.items.len()),
+            None => format!("Ticker filter cleared ({} items)", self.items.len()),
+        });
+    }
+
+    /// Show a subreddit or multireddit, or add (`+name`) / remove (`-name`) a
+    /// configured subreddit and save the config
+    async fn pick_subreddit(&mut self, text: &str) -> Result<()> {
+        if let Some(name) = text.strip_prefix('+') {
+            let Some(RedditScope::Subreddit(name)) = RedditScope::parse(name) else {
+                self.status_message = Some(format!("Not a subreddit name: {}", name));
+                return Ok(());
+            };
+            let subreddits = &mut self.config.reddit.subreddits;
+            if !subreddits.iter().any(|s| s.eq_ignore_ascii_case(&name)) {
+                subreddits.push(name.clone());
+                self.save_subreddits();
+            }
+            return self.show_reddit_scope(RedditScope::Subreddit(name)).await;
+        }
+
+        if let Some(name) = text.strip_prefix('-') {
+            let name = match (RedditScope::parse(name), &self.reddit_scope) {
+                (Some(RedditScope::Subreddit(name)), _) => name,
+                (Some(RedditScope::All), RedditScope::Subreddit(current)) => current.clone(),
+                _ => {
+                    self.status_message = Some("Name a subreddit to remove: -name".to_string());
+                    return Ok(());
+                }
+            };
+            let subreddits = &mut self.config.reddit.subreddits;
+            let before = subreddits.len();
+            subreddits.retain(|s| !s.eq_ignore_ascii_case(&name));
+            if subreddits.len() == before {
+                self.status_message = Some(format!("r/{} is not configured", name));
+                return Ok(());
+            }
+            self.save_subreddits();
+            let scope = if self.reddit_scope == RedditScope::Subreddit(name) {
+                RedditScope::All
+            } else {
+                self.reddit_scope.clone()
+            };
+            return self.show_reddit_scope(scope).await;
+        }
+
+        match RedditScope::parse(text) {
+            Some(RedditScope::Multi(name))
+                if !self.config.reddit.multireddits.contains_key(&name) =>
+            {
+                self.status_message = Some(format!("No multireddit m/{} under [reddit]", name));
+                Ok(())
+            }
+            Some(scope) => self.show_reddit_scope(scope).await,
+            None => {
+                self.status_message = Some(format!("Not a subreddit name: {}", text));
+                Ok(())
+            }
+        }
+    }
+
+    /// Move to the next or previous Reddit tab
+    async fn cycle_reddit_tab(&mut self, step: isize) -> Result<()> {
+        let tabs = reddit::scopes(&self.config.reddit);
+        let current = tabs.iter().position(|t| *t == self.reddit_scope);
+        let next = match current {
+            Some(idx) => (idx as isize + step).rem_euclid(tabs.len() as isize) as usize,
+            None => 0,
+        };
+        self.show_reddit_scope(tabs[next].clone()).await
+    }
+
+    /// Point the Reddit provider at a scope and load it
+    async fn show_reddit_scope(&mut self, scope: RedditScope) -> Result<()> {
+        self.set_reddit_scope(scope);
+        self.selected_idx = 0;
+        self.fetch_provider_items("reddit").await
+    }
+
+    /// Replace the registered Reddit provider with one showing `scope`
+    fn set_reddit_scope(&mut self, scope: RedditScope) {
+        match RedditProvider::from_config(&self.config.reddit, scope.clone()) {
+            Ok(provider) => {
+                self.registry.register(provider);
+                self.reddit_scope = scope;
+            }
+            Err(e) => self.status_message = Some(format!("Error: {}", e)),
+        }
+    }
+
+    /// Show Algolia results for `text` as the Hacker News feed
+    async fn search_hn(&mut self, text: &str) -> Result<()> {
+        let search = match HnSearch::parse(text) {
+            Ok(search) => search,
+            Err(e) => {
+                self.status_message = Some(format!("Error: {}", e));
+                return Ok(());
+            }
+        };
+        match HackerNewsProvider::new(None) {
+            Ok(provider) => self.registry.register(provider.with_search(search)),
+            Err(e) => {
+                self.status_message = Some(format!("Error: {}", e));
+                return Ok(());
+            }
+        }
+
+        if self.reddit_scope != RedditScope::All {
+            self.set_reddit_scope(RedditScope::All);
+        }
+        self.hn_search = Some(text.to_string());
+        self.state = AppState::Feed("hackernews".to_string());
+        self.selected_idx = 0;
+        self.fetch_provider_items("hackernews").await
+    }
+
+    /// Put the Hacker News stories back after a search
+    fn clear_hn_search(&mut self) {
+        if self.hn_search.take().is_some() {
+            if let Ok(provider) = HackerNewsProvider::new(None) {
+                self.registry.register(provider);
+            }
+        }
+    }
+
+    /// Show export API results for a query as the arXiv feed
+    async fn search_arxiv(&mut self, text: &str) -> Result<()> {
+        let search = match ArxivSearch::parse(text) {
+            Ok(search) => search,
+            Err(e) => {
+                self.status_message = Some(format!("Error: {}", e));
+                return Ok(());
+            }
+        };
+        match ArxivProvider::new(None) {
+            Ok(provider) => self.registry.register(provider.with_search(search)),
+            Err(e) => {
+                self.status_message = Some(format!("Error: {}", e));
+                return Ok(());
+            }
+        }
+
+        self.arxiv_search = Some(text.to_string());
+        self.state = AppState::Feed("arxiv".to_string());
+        self.selected_idx = 0;
+        self.fetch_provider_items("arxiv").await
+    }
+
+    /// Put the arXiv category feed back after a search
+    fn clear_arxiv_search(&mut self) {
+        if self.arxiv_search.take().is_some() {
+            if let Ok(provider) = ArxivProvider::from_config(&self.config.arxiv) {
+                self.registry.register(provider);
+            }
+        }
+    }
+
+    /// Write the subreddit list back to the file the config was loaded from.
+    /// Only that key is touched: runtime overrides such as `--no-cache` stay
+    /// out of the file and its comments are kept.
+    fn save_subreddits(&mut self) {
+        let Some(path) = &self.config_path else {
+            self.status_message = Some("Not saved: no config file".to_string());
+            return;
+        };
+        if let Err(e) = Config::save_subreddits(path, &self.config.reddit.subreddits) {
+            self.status_message = Some(format!("Failed to save config: {}", e));
+        }
+    }
+
+    /// Archive key for a provider's feed; Reddit tabs and HN searches are
+    /// archived separately
+    fn feed_key(&self, provider_id: &str) -> String {
+        match (provider_id, &self.hn_search) {
+            ("reddit", _) if self.reddit_scope != RedditScope::All => {
+                format!("reddit/{}", self.reddit_scope.label())
+            }
+            ("hackernews", Some(query)) => format!("hackernews/search: {}", query),
+            ("arxiv", _) => match &self.arxiv_search {
+                Some(query) => format!("arxiv/search: {}", query),
+                None => provider_id.to_string(),
+            },
+            _ => provider_id.to_string(),
+        }
+    }
+
+    /// Query the local index and show the results as a list
+    fn run_search(&mut self, query: &str) {
+        let Some(index) = &self.index else {
+            self.status_message = Some("Search index is disabled".to_string());
+            return;
+        };
+
+        let indexed = index.len();
+        let results = Query::parse(query)
+            .map_err(|e| e.to_string())
+            .and_then(|q| index.search(&q, 500).map_err(|e| e.to_string()));
+
+        match results {
+            Ok(hits) => {
+                self.state = AppState::Search(query.to_string());
+                self.restore_view_order();
+                self.source_items = hits.into_iter().map(|hit| hit.item).collect();
+                self.items.clear();
+                self.selected_idx = 0;
+                self.filter_query.clear();
+                self.refresh_new_comment_counts();
+                self.apply_view();
+                self.status_message = Some(format!(
+                    "{} results for '{}' ({} items indexed)",
+                    self.items.len(),
+                    query,
+                    indexed
+                ));
+            }
+            Err(e) => self.status_message = Some(format!("Search: {}", e)),
+        }
+    }
+
+    /// Show the archived snapshot of the current view closest before a time
+    fn open_archive(&mut self, when: &str) {
+        let Some(archive) = &self.archive else {
+            return;
+        };
+        let Some(feed) = self.archive_feed() else {
+            self.status_message = Some("Nothing is archived for search results".to_string());
+            return;
+        };
+        let Some(at) = parse_as_of(when, Local::now()) else {
+            self.status_message = Some(format!(
+                "Invalid time '{}' (use e.g. 18h, 09:30, yesterday 09:00 or 2026-01-31 14:00)",
+                when
+            ));
+            return;
+        };
+
+        match archive.snapshot_at(&feed, at) {
+            Ok(Some(snapshot)) => self.show_snapshot(snapshot),
+            Ok(None) => {
+                self.status_message = Some(format!(
+                    "No snapshot of {} before {}",
+                    feed,
+                    at.with_timezone(&Local).format("%a %d %b %H:%M")
+                ))
+            }
+            Err(e) => self.status_message = Some(format!("Archive: {}", e)),
+        }
+    }
+
+    /// Step to the previous (`-1`) or next (`1`) snapshot of the archived view
+    fn step_snapshot(&mut self, step: isize) {
+        let (Some(archive), AppState::Archive(feed), Some(current)) =
+            (&self.archive, &self.state, self.archive_at)
+        else {
+            return;
+        };
+
+        match archive.step_from(feed, current, step) {
+            Ok(Some(snapshot)) => self.show_snapshot(snapshot),
+            Ok(None) if step < 0 => self.status_message = Some("Oldest snapshot".to_string()),
+            Ok(None) => self.status_message = Some("Newest snapshot".to_string()),
+            Err(e) => self.status_message = Some(format!("Archive: {}", e)),
+        }
+    }
+
+    fn show_snapshot(&mut self, snapshot: Snapshot) {
+        let count = self
+            .archive
+            .as_ref()
+            .map(|a| a.snapshot_times(&snapshot.feed))
+            .unwrap_or_default();
+        let position = count.iter().position(|t| *t == snapshot.taken_at);
+
+        if self.state != AppState::Archive(snapshot.feed.clone()) {
+            self.state = AppState::Archive(snapshot.feed.clone());
+            self.restore_view_order();
+        }
+        self.archive_at = Some(snapshot.taken_at);
+        self.source_items = snapshot.items;
+        self.refresh_new_comment_counts();
+        self.apply_view();
+        self.status_message = Some(format!(
+            "Snapshot {} of {} ({} items)  ←/→:Older/Newer",
+            position.map(|p| p + 1).unwrap_or(0),
+            count.len(),
+            self.items.len()
+        ));
+    }
+
+    /// Archive key of the list on screen (search results are not archived)
+    fn archive_feed(&self) -> Option<String> {
+        match &self.state {
+            AppState::Dashboard => Some("dashboard".to_string()),
+            AppState::Feed(provider_id) => Some(self.feed_key(provider_id)),
+            AppState::Archive(feed) => Some(feed.clone()),
+            _ => None,
+        }
+    }
+
+    /// Snapshot a freshly fetched list
+    fn archive_items(&self, feed: &str, items: &[FeedItem]) {
+        if let Some(archive) = &self.archive {
+            if let Err(e) = archive.record(feed, items) {
+                tracing::warn!("Failed to archive {}: {}", feed, e);
+            }
+        }
+    }
+
+    /// Load the selected item's rank history when the selection changes
+    fn refresh_item_history(&mut self) {
+        let (Some(archive), Some(feed)) = (&self.archive, self.archive_feed()) else {
+            return;
+        };
+        let Some(item) = self.items.get(self.selected_idx) else {
+            self.item_history = None;
+            return;
+        };
+        if self
+            .item_history
+            .as_ref()
+            .is_some_and(|(id, _)| *id == item.id)
+        {
+            return;
+        }
+
+        let history = archive.history(&feed, item).unwrap_or_else(|e| {
+            tracing::warn!("Failed to read rank history: {}", e);
+            Vec::new()
+        });
+        self.item_history = Some((item.id.clone(), history));
+    }
+
+    /// Send newly matched items to webhooks in the background
+    fn dispatch_webhooks(&self, items: &[FeedItem]) {
+        if let Some(webhooks) = &self.webhooks {
+            // Outcomes are logged by the delivery tasks
+            webhooks.dispatch(items);
+        }
+    }
+
+    /// Add freshly fetched items to the local search index
+    fn index_items(&self, items: &[FeedItem]) {
+        if let Some(index) = &self.index {
+            if let Err(e) = index.index_items(items) {
+                tracing::warn!("Failed to index items: {}", e);
+            }
+        }
+    }
+
+    /// Handle typing into the fuzzy filter prompt
+    fn handle_filter_input(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Esc => {
+                self.filter_editing = false;
+                self.filter_query.clear();
+                self.apply_view();
+            }
+            KeyCode::Enter => {
+                self.filter_editing = false;
+                self.status_message = Some(format!(
+                    "Filter: {} ({} items, Esc clears)",
+                    self.filter_query,
+                    self.items.len()
+                ));
+            }
+            KeyCode::Up => self.selected_idx = self.selected_idx.saturating_sub(1),
+            KeyCode::Down if self.selected_idx + 1 < self.items.len() => {
+                self.selected_idx += 1;
+            }
+            KeyCode::Backspace => {
+                self.filter_query.pop();
+                self.apply_view();
+                self.selected_idx = 0;
+            }
+            KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.filter_query.push(c);
+                self.apply_view();
+                self.selected_idx = 0;
+            }
+            _ => {}
+        }
+    }
+
     /// Handle landing page input
     async fn handle_landing_input(&mut self, key: KeyEvent, action: Action) -> Result<()> {
         let provider_count = self.registry.len();
@@ -196,16 +867,14 @@ impl App {
         match action {
             Action::Quit => self.should_quit = true,
             Action::Help => self.state = AppState::Help,
-            Action::NavigateUp => {
-                if self.landing_selected > 0 {
-                    self.landing_selected -= 1;
-                }
+            Action::NotificationLog => self.open_notification_log(),
+            Action::ReadingQueue => self.open_reading_queue(),
+            Action::NavigateUp if self.landing_selected > 0 => {
+                self.landing_selected -= 1;
             }
-            Action::NavigateDown => {
-                if self.landing_selected < provider_count {
-                    // +1 for "All" option
-                    self.landing_selected += 1;
-                }
+            Action::NavigateDown if self.landing_selected < provider_count => {
+                // +1 for "All" option
+                self.landing_selected += 1;
             }
             Action::Select => {
                 self.select_from_landing().await?;
@@ -236,15 +905,25 @@ impl App {
         match action {
             Action::Quit => self.should_quit = true,
             Action::Help => self.state = AppState::Help,
+            Action::Back if !self.filter_query.is_empty() => {
+                self.filter_query.clear();
+                self.apply_view();
+                self.status_message = Some("Filter cleared".to_string());
+            }
             Action::Back => {
+                // Other views fetch every subreddit and the HN stories again
+                if self.reddit_scope != RedditScope::All {
+                    self.set_reddit_scope(RedditScope::All);
+                }
+                self.clear_hn_search();
+                self.clear_arxiv_search();
                 self.state = AppState::Landing;
+                self.source_items.clear();
                 self.items.clear();
                 self.selected_idx = 0;
             }
-            Action::NavigateUp => {
-                if self.selected_idx > 0 {
-                    self.selected_idx -= 1;
-                }
+            Action::NavigateUp if self.selected_idx > 0 => {
+                self.selected_idx -= 1;
             }
             Action::NavigateDown => {
                 if self.selected_idx < self.items.len().saturating_sub(1) {
@@ -260,25 +939,134 @@ impl App {
             Action::GoToBottom => {
                 self.selected_idx = self.items.len().saturating_sub(1);
             }
+            Action::Search => {
+                self.prompt = Some((Prompt::Search, String::new()));
+            }
+            Action::SearchHn => {
+                // The arXiv feed searches papers instead
+                let prompt = match &self.state {
+                    AppState::Feed(id) if id == "arxiv" => Prompt::ArxivSearch,
+                    _ => Prompt::HnSearch,
+                };
+                self.prompt = Some((prompt, String::new()));
+            }
+            Action::ViewUser => {
+                let author = self
+                    .items
+                    .get(self.selected_idx)
+                    .filter(|item| item.provider_id == "hackernews")
+                    .and_then(|item| item.author.clone());
+                self.open_user(author).await;
+            }
+            Action::TimeTravel => {
+                if self.archive.is_none() {
+                    self.status_message =
+                        Some("Archive is off (set archive = true under [cache])".to_string());
+                } else {
+                    self.prompt = Some((Prompt::AsOf, String::new()));
+                }
+            }
+            Action::NavigateLeft => self.step_snapshot(-1),
+            Action::NavigateRight => self.step_snapshot(1),
             Action::Select => {
                 if let Some(item) = self.items.get(self.selected_idx) {
                     self.current_item = Some(item.clone());
+                    self.return_state = self.state.clone();
                     self.state = AppState::Article;
                     self.scroll_offset = 0;
+                    self.request_full_content();
                 }
             }
             Action::Refresh => {
                 self.refresh_current_feed().await?;
             }
+            Action::CycleSentimentFilter => {
+                self.sentiment_filter = match self.sentiment_filter {
+                    None => Some(SentimentLabel::Positive),
+                    Some(SentimentLabel::Positive) => Some(SentimentLabel::Negative),
+                    Some(SentimentLabel::Negative) => Some(SentimentLabel::Neutral),
+                    Some(SentimentLabel::Neutral) => None,
+                };
+                self.apply_view();
+                let label = self
+                    .sentiment_filter
+                    .as_ref()
+                    .map(|l| l.as_str())
+                    .unwrap_or("All");
+                self.status_message =
+                    Some(format!("Sentiment: {} ({} items)", label, self.items.len()));
+            }
+            Action::CycleSort => {
+                self.sort_mode = self.sort_mode.next();
+                if self.sort_mode == SortMode::Feed && !self.has_feed_order() {
+                    self.sort_mode = self.sort_mode.next();
+                }
+                self.update_view_order();
+                self.status_message = Some(format!("Sort: {}", self.sort_mode.as_str()));
+            }
+            Action::CycleGroup => {
+                self.group_by = self.group_by.next();
+                self.update_view_order();
+                self.status_message = Some(format!("Group by: {}", self.group_by.as_str()));
+            }
+            Action::FuzzyFilter => {
+                self.filter_editing = true;
+            }
+            Action::FilterTicker => {
+                // Pre-fill with the selected item's ticker, else the current filter
+                let symbol = self
+                    .items
+                    .get(self.selected_idx)
+                    .and_then(|item| {
+                        item.metadata
+                            .tags
+                            .iter()
+                            .find(|t| self.registry.is_symbol(t))
+                            .cloned()
+                    })
+                    .or_else(|| self.ticker_filter.clone())
+                    .unwrap_or_default();
+                self.prompt = Some((Prompt::Ticker, symbol));
+            }
             Action::OpenInBrowser => {
-                if let Some(item) = self.items.get(self.selected_idx) {
-                    if let Some(url) = &item.url {
-                        #[cfg(target_os = "macos")]
-                        let _ = std::process::Command::new("open").arg(url).spawn();
-                        self.status_message = Some("Opened in browser".to_string());
-                    }
+                if let Some(url) = self
+                    .items
+                    .get(self.selected_idx)
+                    .and_then(|i| i.url.clone())
+                {
+                    self.open_url(&url);
+                }
+            }
+            Action::OpenMedia => {
+                if let Some(item) = self.items.get(self.selected_idx).cloned() {
+                    self.open_media(&item);
+                }
+            }
+            Action::SaveBookmark => {
+                if let Some(item) = self.items.get(self.selected_idx).cloned() {
+                    self.toggle_bookmark(&item);
                 }
             }
+            Action::DownloadPdf
+            | Action::CopyBibtex
+            | Action::AppendBibtex
+            | Action::QueuePaper => {
+                if let Some(item) = self.items.get(self.selected_idx).cloned() {
+                    self.paper_action(action, &item);
+                }
+            }
+            Action::ReadingQueue => self.open_reading_queue(),
+            Action::NotificationLog => self.open_notification_log(),
+            Action::SwitchTab | Action::PrevTab if self.is_reddit_feed() => {
+                let step = if action == Action::SwitchTab { 1 } else { -1 };
+                self.cycle_reddit_tab(step).await?;
+            }
+            Action::PickSubreddit if self.is_reddit_feed() => {
+                self.prompt = Some((Prompt::Subreddit, String::new()));
+            }
+            Action::PickSubreddit => {
+                self.status_message = Some("Subreddits are picked in the Reddit feed".to_string());
+            }
             _ => {}
         }
 
@@ -286,23 +1074,17 @@ impl App {
     }
 
     /// Handle article view input
-    fn handle_article_input(&mut self, action: Action) {
+    async fn handle_article_input(&mut self, action: Action) -> Result<()> {
         match action {
             Action::Quit => self.should_quit = true,
             Action::Back => {
-                // Go back to feed list
+                // Go back to the list the article was opened from
                 if self.state == AppState::Article {
-                    // Determine what state to return to
-                    let provider_id = self.current_item.as_ref().map(|i| i.provider_id.clone());
-
-                    if let Some(id) = provider_id {
-                        self.state = AppState::Feed(id);
-                    } else {
-                        self.state = AppState::Dashboard;
-                    }
+                    self.state = self.return_state.clone();
                 }
                 self.current_item = None;
                 self.scroll_offset = 0;
+                self.content_loading = false;
             }
             Action::PageDown | Action::NavigateDown => {
                 self.scroll_offset = self.scroll_offset.saturating_add(5);
@@ -314,43 +1096,139 @@ impl App {
                 self.scroll_offset = 0;
             }
             Action::OpenInBrowser => {
-                if let Some(item) = &self.current_item {
-                    if let Some(url) = &item.url {
-                        #[cfg(target_os = "macos")]
-                        let _ = std::process::Command::new("open").arg(url).spawn();
-                        self.status_message = Some("Opened in browser".to_string());
-                    }
+                if let Some(url) = self.current_item.as_ref().and_then(|i| i.url.clone()) {
+                    self.open_url(&url);
                 }
             }
-            Action::NextArticle => {
-                if self.selected_idx < self.items.len().saturating_sub(1) {
-                    self.selected_idx += 1;
-                    self.current_item = self.items.get(self.selected_idx).cloned();
-                    self.scroll_offset = 0;
+            Action::OpenMedia => {
+                if let Some(item) = self.current_item.clone() {
+                    self.open_media(&item);
                 }
             }
-            Action::PrevArticle => {
-                if self.selected_idx > 0 {
-                    self.selected_idx -= 1;
-                    self.current_item = self.items.get(self.selected_idx).cloned();
-                    self.scroll_offset = 0;
+            Action::NextArticle if self.selected_idx < self.items.len().saturating_sub(1) => {
+                self.selected_idx += 1;
+                self.current_item = self.items.get(self.selected_idx).cloned();
+                self.scroll_offset = 0;
+                self.request_full_content();
+            }
+            Action::PrevArticle if self.selected_idx > 0 => {
+                self.selected_idx -= 1;
+                self.current_item = self.items.get(self.selected_idx).cloned();
+                self.scroll_offset = 0;
+                self.request_full_content();
+            }
+            Action::SaveBookmark => {
+                if let Some(item) = self.current_item.clone() {
+                    self.toggle_bookmark(&item);
+                }
+            }
+            Action::DownloadPdf
+            | Action::CopyBibtex
+            | Action::AppendBibtex
+            | Action::QueuePaper => {
+                if let Some(item) = self.current_item.clone() {
+                    self.paper_action(action, &item);
                 }
             }
-            Action::ViewComments => {
+            Action::ReadingQueue => self.open_reading_queue(),
+            Action::ViewComments if self.current_item.is_some() => {
                 // Transition to comments view - comments will be loaded async
-                if self.current_item.is_some() {
-                    self.state = AppState::Comments;
+                self.state = AppState::Comments;
+                self.comments_loading = true;
+                self.status_message = Some("Loading comments...".to_string());
+            }
+            Action::FindDiscussions => self.find_discussions().await,
+            Action::ViewUser => {
+                let author = self
+                    .current_item
+                    .as_ref()
+                    .filter(|item| item.provider_id == "hackernews")
+                    .and_then(|item| item.author.clone());
+                self.open_user(author).await;
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    /// Look up HN and Reddit threads about the current article's link
+    async fn find_discussions(&mut self) {
+        let Some(item) = self.current_item.clone() else {
+            return;
+        };
+        let Some(url) = item.url.clone() else {
+            self.status_message = Some("This item has no link to look up".to_string());
+            return;
+        };
+
+        self.status_message = Some("Looking up discussions...".to_string());
+        match providers::find_discussions(&url).await {
+            Ok(discussions) => {
+                self.status_message = Some(format!("Found {} discussions", discussions.len()));
+                self.discussions = discussions;
+                self.discussions_selected = 0;
+                self.discussions_url = url;
+                self.discussions_return = Some((item, self.return_state.clone()));
+                self.state = AppState::Discussions;
+            }
+            Err(e) => self.status_message = Some(format!("Error finding discussions: {}", e)),
+        }
+    }
+
+    /// Handle discussions list input
+    fn handle_discussions_input(&mut self, action: Action) {
+        let count = self.discussions.len();
+
+        match action {
+            Action::Quit => self.should_quit = true,
+            Action::Back => {
+                // Back to the article the lookup started from
+                if let Some((item, return_state)) = self.discussions_return.take() {
+                    self.current_item = Some(item);
+                    self.return_state = return_state;
+                }
+                self.state = AppState::Article;
+                self.scroll_offset = 0;
+            }
+            Action::NavigateUp if self.discussions_selected > 0 => {
+                self.discussions_selected -= 1;
+            }
+            Action::NavigateDown if self.discussions_selected < count.saturating_sub(1) => {
+                self.discussions_selected += 1;
+            }
+            Action::GoToTop => self.discussions_selected = 0,
+            Action::GoToBottom => self.discussions_selected = count.saturating_sub(1),
+            Action::Select | Action::ViewComments => {
+                // Straight into the thread; Esc then shows the post itself
+                if let Some(item) = self.discussions.get(self.discussions_selected) {
+                    self.current_item = Some(item.clone());
+                    self.return_state = AppState::Discussions;
+                    self.scroll_offset = 0;
+                    self.comments.clear();
+                    self.comments_selected = 0;
+                    self.comments_scroll = 0;
                     self.comments_loading = true;
+                    self.state = AppState::Comments;
                     self.status_message = Some("Loading comments...".to_string());
                 }
             }
+            Action::OpenInBrowser => {
+                if let Some(link) = self
+                    .discussions
+                    .get(self.discussions_selected)
+                    .and_then(FeedItem::discussion_url)
+                {
+                    self.open_url(&link);
+                }
+            }
             _ => {}
         }
     }
 
     /// Handle comments view input
-    fn handle_comments_input(&mut self, action: Action) {
-        let comment_count = self.comments.len();
+    async fn handle_comments_input(&mut self, action: Action) {
+        let comment_count = views::comments::flatten_comments(&self.comments).len();
 
         match action {
             Action::Quit => self.should_quit = true,
@@ -358,18 +1236,15 @@ impl App {
                 // Return to article view
                 self.state = AppState::Article;
                 self.comments.clear();
+                self.new_comment_ids.clear();
                 self.comments_selected = 0;
                 self.comments_scroll = 0;
             }
-            Action::NavigateUp => {
-                if self.comments_selected > 0 {
-                    self.comments_selected -= 1;
-                }
+            Action::NavigateUp if self.comments_selected > 0 => {
+                self.comments_selected -= 1;
             }
-            Action::NavigateDown => {
-                if self.comments_selected < comment_count.saturating_sub(1) {
-                    self.comments_selected += 1;
-                }
+            Action::NavigateDown if self.comments_selected < comment_count.saturating_sub(1) => {
+                self.comments_selected += 1;
             }
             Action::GoToTop => {
                 self.comments_selected = 0;
@@ -378,6 +1253,201 @@ impl App {
             Action::GoToBottom => {
                 self.comments_selected = comment_count.saturating_sub(1);
             }
+            Action::NextResult => self.jump_to_new_comment(true),
+            Action::PrevResult => self.jump_to_new_comment(false),
+            Action::ViewUser => {
+                let is_hn = self
+                    .current_item
+                    .as_ref()
+                    .is_some_and(|item| item.provider_id == "hackernews");
+                let author = views::comments::flatten_comments(&self.comments)
+                    .get(self.comments_selected)
+                    .filter(|_| is_hn)
+                    .map(|(comment, _)| comment.author.clone());
+                self.open_user(author).await;
+            }
+            _ => {}
+        }
+    }
+
+    /// Fetch and show a Hacker News user's profile
+    async fn open_user(&mut self, username: Option<String>) {
+        let Some(username) = username.filter(|name| name != "[deleted]") else {
+            self.status_message =
+                Some("Profiles are available for Hacker News authors".to_string());
+            return;
+        };
+
+        self.status_message = Some(format!("Loading {}...", username));
+        let profile = match HackerNewsProvider::new(None) {
+            Ok(provider) => provider.fetch_profile(&username, 30).await,
+            Err(e) => Err(e),
+        };
+        match profile {
+            Ok(profile) => {
+                self.status_message = None;
+                self.user_pages.push(UserPage {
+                    show_comments: profile.submissions.is_empty() && !profile.comments.is_empty(),
+                    profile,
+                    selected: 0,
+                    back_state: self.state.clone(),
+                    back_item: self.current_item.clone(),
+                    back_return_state: self.return_state.clone(),
+                    back_comments: self.comments.clone(),
+                    back_comments_selected: self.comments_selected,
+                });
+                self.state = AppState::User;
+            }
+            Err(e) => self.status_message = Some(format!("Error loading {}: {}", username, e)),
+        }
+    }
+
+    /// Handle user profile input
+    async fn handle_user_input(&mut self, action: Action) -> Result<()> {
+        let Some(page) = self.user_pages.last_mut() else {
+            self.state = AppState::Landing;
+            return Ok(());
+        };
+        let count = if page.show_comments {
+            page.profile.comments.len()
+        } else {
+            page.profile.submissions.len()
+        };
+
+        match action {
+            Action::Quit => self.should_quit = true,
+            Action::Back => {
+                // Back to wherever the profile was opened from
+                if let Some(page) = self.user_pages.pop() {
+                    self.state = page.back_state;
+                    self.current_item = page.back_item;
+                    self.return_state = page.back_return_state;
+                    self.comments = page.back_comments;
+                    self.comments_selected = page.back_comments_selected;
+                }
+            }
+            Action::SwitchTab | Action::PrevTab | Action::NavigateLeft | Action::NavigateRight => {
+                page.show_comments = !page.show_comments;
+                page.selected = 0;
+            }
+            Action::NavigateUp if page.selected > 0 => page.selected -= 1,
+            Action::NavigateDown if page.selected < count.saturating_sub(1) => {
+                page.selected += 1;
+            }
+            Action::GoToTop => page.selected = 0,
+            Action::GoToBottom => page.selected = count.saturating_sub(1),
+            Action::Select | Action::ViewComments if page.show_comments => {
+                // Open the thread the comment was posted in
+                let Some(id) = page
+                    .profile
+                    .comments
+                    .get(page.selected)
+                    .and_then(|c| c.id.parse::<u64>().ok())
+                else {
+                    return Ok(());
+                };
+                self.status_message = Some("Loading thread...".to_string());
+                let story = match HackerNewsProvider::new(None) {
+                    Ok(provider) => provider.fetch_story_for(id).await,
+                    Err(e) => Err(e),
+                };
+                match story {
+                    Ok(story) => {
+                        self.current_item = Some(story);
+                        self.return_state = AppState::User;
+                        self.scroll_offset = 0;
+                        self.comments.clear();
+                        self.comments_selected = 0;
+                        self.comments_scroll = 0;
+                        self.comments_loading = true;
+                        self.state = AppState::Comments;
+                    }
+                    Err(e) => self.status_message = Some(format!("Error: {}", e)),
+                }
+            }
+            Action::Select | Action::ViewComments => {
+                if let Some(item) = page.profile.submissions.get(page.selected).cloned() {
+                    let comments = action == Action::ViewComments;
+                    self.current_item = Some(item);
+                    self.return_state = AppState::User;
+                    self.scroll_offset = 0;
+                    if comments {
+                        self.comments.clear();
+                        self.comments_selected = 0;
+                        self.comments_scroll = 0;
+                        self.comments_loading = true;
+                        self.state = AppState::Comments;
+                    } else {
+                        self.state = AppState::Article;
+                        self.request_full_content();
+                    }
+                }
+            }
+            Action::OpenInBrowser => {
+                let link = if page.show_comments {
+                    page.profile
+                        .comments
+                        .get(page.selected)
+                        .map(|c| format!("https://news.ycombinator.com/item?id={}", c.id))
+                } else {
+                    page.profile
+                        .submissions
+                        .get(page.selected)
+                        .and_then(|item| item.url.clone().or_else(|| item.discussion_url()))
+                };
+                if let Some(link) = link {
+                    self.open_url(&link);
+                }
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    /// Show the notification log, returning to the current view on Esc
+    fn open_notification_log(&mut self) {
+        self.log_return = self.state.clone();
+        self.notifications_selected = 0;
+        self.state = AppState::Notifications;
+    }
+
+    /// Handle notification log input
+    fn handle_notifications_input(&mut self, action: Action) {
+        let count = self.notifier.log().len();
+
+        match action {
+            Action::Quit => self.should_quit = true,
+            Action::Back | Action::NotificationLog => {
+                self.state = self.log_return.clone();
+            }
+            Action::NavigateUp if self.notifications_selected > 0 => {
+                self.notifications_selected -= 1;
+            }
+            Action::NavigateDown if self.notifications_selected < count.saturating_sub(1) => {
+                self.notifications_selected += 1;
+            }
+            Action::GoToTop => self.notifications_selected = 0,
+            Action::GoToBottom => self.notifications_selected = count.saturating_sub(1),
+            Action::Select => {
+                if let Some(notification) = self.notifier.log().get(self.notifications_selected) {
+                    self.current_item = Some(notification.item.clone());
+                    self.return_state = AppState::Notifications;
+                    self.state = AppState::Article;
+                    self.scroll_offset = 0;
+                    self.request_full_content();
+                }
+            }
+            Action::OpenInBrowser => {
+                if let Some(url) = self
+                    .notifier
+                    .log()
+                    .get(self.notifications_selected)
+                    .and_then(|n| n.item.url.clone())
+                {
+                    self.open_url(&url);
+                }
+            }
             _ => {}
         }
     }
@@ -393,6 +1463,221 @@ impl App {
         }
     }
 
+    /// Save or unsave an item
+    fn toggle_bookmark(&mut self, item: &FeedItem) {
+        let Some(bookmarks) = &self.bookmarks else {
+            self.status_message = Some("Bookmarks are unavailable".to_string());
+            return;
+        };
+
+        self.status_message = Some(match bookmarks.toggle(item) {
+            Ok(true) => format!("Bookmarked ({} saved)", bookmarks.len()),
+            Ok(false) => "Bookmark removed".to_string(),
+            Err(e) => format!("Bookmark failed: {}", e),
+        });
+    }
+
+    /// Download, cite or queue an arXiv paper
+    fn paper_action(&mut self, action: Action, item: &FeedItem) {
+        let (Some(key), Some(entry)) = (papers::bibtex_key(item), papers::bibtex(item)) else {
+            self.status_message = Some("Paper actions are for arXiv items".to_string());
+            return;
+        };
+
+        match action {
+            Action::DownloadPdf => self.download_pdf(item),
+            Action::CopyBibtex => {
+                self.status_message = Some(match clipboard::copy(&entry) {
+                    Ok(()) => format!("Copied BibTeX entry {}", key),
+                    Err(e) => format!("Copy failed: {}", e),
+                });
+            }
+            Action::AppendBibtex => {
+                let path = self.config.bibtex_file();
+                self.status_message = Some(match papers::append_bibtex(&path, item) {
+                    Ok(Some(key)) => format!("Added {} to {}", key, path.display()),
+                    Ok(None) => format!("Already in {}", path.display()),
+                    Err(e) => format!("BibTeX export failed: {}", e),
+                });
+            }
+            Action::QueuePaper => self.queue_paper(item),
+            _ => {}
+        }
+    }
+
+    /// Start downloading a paper's PDF into the papers directory
+    fn download_pdf(&mut self, item: &FeedItem) {
+        let dir = self.config.papers_dir();
+        let item = item.clone();
+        let tx = self.download_tx.clone();
+        self.status_message = Some(format!("Downloading PDF of \"{}\"...", item.title));
+        tokio::spawn(async move {
+            let result = papers::download_pdf(&item, &dir).await;
+            let _ = tx.send((item.title, result));
+        });
+    }
+
+    /// Report PDF downloads that finished since the last frame
+    fn apply_downloads(&mut self) {
+        while let Ok((title, result)) = self.download_rx.try_recv() {
+            self.status_message = Some(match result {
+                Ok(path) => format!("Saved {}", path.display()),
+                Err(e) => format!("PDF of \"{}\": {}", title, e),
+            });
+        }
+    }
+
+    /// Add a paper to the reading queue, or move it on to its next state
+    fn queue_paper(&mut self, item: &FeedItem) {
+        let Some(queue) = &self.reading_queue else {
+            self.status_message = Some("Reading queue is unavailable".to_string());
+            return;
+        };
+
+        self.status_message = Some(match queue.advance(item) {
+            Ok(state) => format!(
+                "Reading queue: {} ({} papers, W to open)",
+                state.as_str(),
+                queue.len()
+            ),
+            Err(e) => format!("Reading queue failed: {}", e),
+        });
+        self.refresh_reading_queue();
+    }
+
+    /// Show the reading queue, returning to the current view on Esc
+    fn open_reading_queue(&mut self) {
+        if self.reading_queue.is_none() {
+            self.status_message = Some("Reading queue is unavailable".to_string());
+            return;
+        }
+        self.queue_return = (
+            self.state.clone(),
+            self.current_item.clone(),
+            self.return_state.clone(),
+        );
+        self.queue_selected = 0;
+        self.refresh_reading_queue();
+        self.state = AppState::ReadingQueue;
+    }
+
+    /// Reload the listed queue, keeping the selection on the same paper
+    fn refresh_reading_queue(&mut self) {
+        let Some(queue) = &self.reading_queue else {
+            return;
+        };
+        let selected_id = self
+            .queue_entries
+            .get(self.queue_selected)
+            .map(|e| e.item.id.clone());
+
+        self.queue_entries = queue.list().unwrap_or_else(|e| {
+            tracing::warn!("Failed to read reading queue: {}", e);
+            Vec::new()
+        });
+        self.queue_selected = selected_id
+            .and_then(|id| self.queue_entries.iter().position(|e| e.item.id == id))
+            .unwrap_or(self.queue_selected)
+            .min(self.queue_entries.len().saturating_sub(1));
+    }
+
+    /// Handle reading queue input
+    fn handle_queue_input(&mut self, action: Action) {
+        let count = self.queue_entries.len();
+        let selected = self
+            .queue_entries
+            .get(self.queue_selected)
+            .map(|e| e.item.clone());
+
+        match action {
+            Action::Quit => self.should_quit = true,
+            Action::Back | Action::ReadingQueue => {
+                let (state, item, return_state) = self.queue_return.clone();
+                self.state = state;
+                self.current_item = item;
+                self.return_state = return_state;
+            }
+            Action::NavigateUp if self.queue_selected > 0 => {
+                self.queue_selected -= 1;
+            }
+            Action::NavigateDown if self.queue_selected < count.saturating_sub(1) => {
+                self.queue_selected += 1;
+            }
+            Action::GoToTop => self.queue_selected = 0,
+            Action::GoToBottom => self.queue_selected = count.saturating_sub(1),
+            Action::Select => {
+                if let Some(item) = selected {
+                    self.current_item = Some(item);
+                    self.return_state = AppState::ReadingQueue;
+                    self.state = AppState::Article;
+                    self.scroll_offset = 0;
+                    self.request_full_content();
+                }
+            }
+            Action::DownloadPdf
+            | Action::CopyBibtex
+            | Action::AppendBibtex
+            | Action::QueuePaper => {
+                if let Some(item) = selected {
+                    self.paper_action(action, &item);
+                }
+            }
+            Action::Remove => {
+                let (Some(queue), Some(item)) = (&self.reading_queue, selected) else {
+                    return;
+                };
+                self.status_message = Some(match queue.remove(&item) {
+                    Ok(_) => format!("Removed \"{}\" from the queue", item.title),
+                    Err(e) => format!("Reading queue failed: {}", e),
+                });
+                self.refresh_reading_queue();
+            }
+            Action::OpenInBrowser => {
+                if let Some(url) = selected.and_then(|item| item.url) {
+                    self.open_url(&url);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Open a URL with the platform's default handler
+    fn open_url(&mut self, url: &str) -> bool {
+        #[cfg(target_os = "macos")]
+        let result = std::process::Command::new("open").arg(url).spawn();
+        #[cfg(target_os = "windows")]
+        let result = std::process::Command::new("cmd")
+            .args(["/C", "start", "", url])
+            .spawn();
+        #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+        let result = std::process::Command::new("xdg-open").arg(url).spawn();
+
+        let opened = result.is_ok();
+        self.status_message = Some(match result {
+            Ok(_) => "Opened in browser".to_string(),
+            Err(e) => format!("Failed to open browser: {}", e),
+        });
+        opened
+    }
+
+    /// Open a post's image or video; repeated presses step through a gallery
+    fn open_media(&mut self, item: &FeedItem) {
+        let urls = &item.metadata.media_urls;
+        if urls.is_empty() {
+            self.status_message = Some("No media in this post".to_string());
+            return;
+        }
+
+        let idx = match &self.media_cursor {
+            Some((id, next)) if *id == item.id => next % urls.len(),
+            _ => 0,
+        };
+        if self.open_url(&urls[idx]) && urls.len() > 1 {
+            self.status_message = Some(format!("Opened media {}/{}", idx + 1, urls.len()));
+        }
+        self.media_cursor = Some((item.id.clone(), idx + 1));
+    }
+
     /// Select a provider from landing page
     async fn select_from_landing(&mut self) -> Result<()> {
         let provider_count = self.registry.len();
@@ -400,6 +1685,7 @@ impl App {
         if self.landing_selected >= provider_count {
             // "All" selected - go to dashboard
             self.state = AppState::Dashboard;
+            self.restore_view_order();
             self.fetch_all_items().await?;
         } else {
             // Specific provider selected
@@ -407,6 +1693,7 @@ impl App {
             if let Some(id) = ids.get(self.landing_selected) {
                 let id = id.to_string();
                 self.state = AppState::Feed(id.clone());
+                self.restore_view_order();
                 self.fetch_provider_items(&id).await?;
             }
         }
@@ -414,22 +1701,295 @@ impl App {
         Ok(())
     }
 
+    /// Key the current list view's ordering is remembered under
+    fn view_key(&self) -> Option<String> {
+        match &self.state {
+            AppState::Dashboard => Some("dashboard".to_string()),
+            AppState::Feed(provider_id) => Some(provider_id.clone()),
+            AppState::Search(_) => Some("search".to_string()),
+            AppState::Archive(feed) => Some(feed.clone()),
+            _ => None,
+        }
+    }
+
+    /// Whether the current list has an order of its own. The dashboard merges
+    /// every provider newest first, so "Feed order" would just be "Newest".
+    fn has_feed_order(&self) -> bool {
+        self.view_key().as_deref() != Some("dashboard")
+    }
+
+    /// Load the sort/group choice last used in this view
+    fn restore_view_order(&mut self) {
+        let (sort, group) = self
+            .view_key()
+            .and_then(|key| self.view_orders.get(&key).copied())
+            .unwrap_or_default();
+        self.sort_mode = if sort == SortMode::Feed && !self.has_feed_order() {
+            SortMode::Newest
+        } else {
+            sort
+        };
+        self.group_by = group;
+    }
+
+    /// Remember the current sort/group choice for this view and re-sort
+    fn update_view_order(&mut self) {
+        if let Some(key) = self.view_key() {
+            self.view_orders
+                .insert(key, (self.sort_mode, self.group_by));
+        }
+        self.apply_view();
+    }
+
+    /// Display options for the feed list
+    fn list_options(&self) -> views::dashboard::ListOptions<'_> {
+        views::dashboard::ListOptions {
+            filter: &self.filter_query,
+            filter_editing: self.filter_editing,
+            prompt: self
+                .prompt
+                .as_ref()
+                .map(|(prompt, input)| (*prompt, input.as_str())),
+            sort: self.sort_mode,
+            group_by: self.group_by,
+            history: self
+                .item_history
+                .as_ref()
+                .filter(|(id, _)| {
+                    self.items
+                        .get(self.selected_idx)
+                        .is_some_and(|item| item.id == *id)
+                })
+                .map(|(_, history)| history.as_slice())
+                .unwrap_or_default(),
+            new_comments: &self.new_comment_counts,
+        }
+    }
+
+    /// Reddit feed title with the current tab and its position, e.g. "Reddit · r/rust (3/6)"
+    fn reddit_tab_title(&self, name: &str) -> String {
+        let tabs = reddit::scopes(&self.config.reddit);
+        let label = self.reddit_scope.label();
+        match tabs.iter().position(|t| *t == self.reddit_scope) {
+            Some(idx) => format!("{} · {} ({}/{})", name, label, idx + 1, tabs.len()),
+            None => format!("{} · {}", name, label),
+        }
+    }
+
     /// Fetch items from all providers
     async fn fetch_all_items(&mut self) -> Result<()> {
         self.loading = true;
         self.status_message = Some("Loading...".to_string());
 
         let limit = self.config.finnhub.max_articles.max(100);
-        self.items = self.registry.fetch_all(limit).await;
-        self.selected_idx = 0;
+        self.source_items = self.registry.fetch_all(limit).await;
+        self.index_items(&self.source_items);
+        self.archive_items("dashboard", &self.source_items);
+        self.dispatch_webhooks(&self.source_items);
+        self.items.clear();
+        self.refresh_new_comment_counts();
+        self.apply_view();
+        self.preview_requested.clear();
 
         self.loading = false;
-        self.status_message = Some(format!("Loaded {} items", self.items.len()));
+        self.status_message = Some(self.loaded_message());
         self.last_update = Instant::now();
 
         Ok(())
     }
 
+    /// Status line after a fetch, announcing new high-priority items and
+    /// raising a terminal notification for them
+    fn loaded_message(&mut self) -> String {
+        let loaded = format!("Loaded {} items", self.items.len());
+
+        let fired = self.notifier.check(&self.source_items);
+        let Some(first) = fired.first() else {
+            return loaded;
+        };
+
+        if let Some(escape) = self.notifier.escape_sequence(&fired) {
+            // Written around ratatui: the escapes don't move the cursor
+            let mut stdout = io::stdout();
+            let _ = stdout
+                .write_all(escape.as_bytes())
+                .and_then(|_| stdout.flush());
+        }
+
+        let mut message = format!("{} | 🔔 [{}] {}", loaded, first.reason, first.item.title);
+        if fired.len() > 1 {
+            message.push_str(&format!(" (+{} more)", fired.len() - 1));
+        }
+        message
+    }
+
+    /// Subscribe to live Hacker News changes while its story feed (or an
+    /// article opened from it) is shown, and unsubscribe otherwise
+    fn sync_hn_live(&mut self) {
+        let hn_feed = AppState::Feed("hackernews".to_string());
+        let in_feed = match &self.state {
+            AppState::Article | AppState::Comments => self.return_state == hn_feed,
+            state => *state == hn_feed,
+        };
+        if !self.config.hackernews.live || !in_feed || self.hn_search.is_some() {
+            self.hn_live = None;
+            return;
+        }
+        if self.hn_live.is_some() || self.loading || self.source_items.is_empty() {
+            return;
+        }
+
+        let known: Vec<u64> = self
+            .source_items
+            .iter()
+            .filter_map(|item| item.metadata.hn_id)
+            .collect();
+        match HackerNewsProvider::new(None) {
+            Ok(provider) => self.hn_live = Some(provider.subscribe(&known, known.len())),
+            Err(e) => tracing::warn!("Live Hacker News unavailable: {}", e),
+        }
+    }
+
+    /// Merge streamed Hacker News changes into the list
+    fn apply_hn_updates(&mut self) {
+        let Some(live) = &mut self.hn_live else {
+            return;
+        };
+        let mut updates = Vec::new();
+        while let Some(update) = live.try_next() {
+            updates.push(update);
+        }
+        if updates.is_empty() {
+            return;
+        }
+
+        let mut added = Vec::new();
+        for update in updates {
+            match update {
+                HnUpdate::Items(mut items) => {
+                    self.registry.enrich(&mut items);
+                    for item in items {
+                        match self.source_items.iter_mut().find(|i| i.id == item.id) {
+                            Some(existing) => *existing = item,
+                            None => {
+                                added.push(item.clone());
+                                self.source_items.push(item);
+                            }
+                        }
+                    }
+                }
+                HnUpdate::Ranking(ids) => {
+                    // Ranked stories lead in stream order; the rest keep theirs
+                    self.source_items.sort_by_key(|item| {
+                        item.metadata
+                            .hn_id
+                            .and_then(|id| ids.iter().position(|&r| r == id))
+                            .unwrap_or(usize::MAX)
+                    });
+                }
+            }
+        }
+
+        if !added.is_empty() {
+            self.index_items(&added);
+            self.dispatch_webhooks(&added);
+            self.status_message = Some(match added.len() {
+                1 => format!("Live: new story \"{}\"", added[0].title),
+                n => format!("Live: {} new stories", n),
+            });
+        }
+        self.refresh_new_comment_counts();
+        self.apply_view();
+    }
+
+    /// Select the next (or previous) comment added since the last visit,
+    /// wrapping around the thread
+    fn jump_to_new_comment(&mut self, forward: bool) {
+        let new: Vec<usize> = views::comments::flatten_comments(&self.comments)
+            .iter()
+            .enumerate()
+            .filter(|(_, (comment, _))| self.new_comment_ids.contains(&comment.id))
+            .map(|(idx, _)| idx)
+            .collect();
+
+        let current = self.comments_selected;
+        let target = if forward {
+            new.iter().find(|&&idx| idx > current).or(new.first())
+        } else {
+            new.iter().rev().find(|&&idx| idx < current).or(new.last())
+        };
+
+        self.status_message = Some(match target {
+            Some(&idx) => {
+                self.comments_selected = idx;
+                let position = new.iter().position(|&i| i == idx).unwrap_or(0) + 1;
+                format!("New comment {}/{}", position, new.len())
+            }
+            None if self.new_comment_ids.is_empty() => {
+                "No new comments since your last visit".to_string()
+            }
+            // New comments inside collapsed threads
+            None => "New comments are in collapsed threads".to_string(),
+        });
+    }
+
+    /// Look up how many comments each loaded thread gained since it was last
+    /// opened; done once per load so filtering the list stays cheap
+    fn refresh_new_comment_counts(&mut self) {
+        self.new_comment_counts.clear();
+        let Some(visits) = &self.thread_visits else {
+            return;
+        };
+        for item in &self.source_items {
+            if let (Some(key), Some(count)) = (thread_key(item), visits.new_comments(item)) {
+                self.new_comment_counts.insert(key, count);
+            }
+        }
+    }
+
+    /// Rebuild the visible list from fetched items and active filters
+    fn apply_view(&mut self) {
+        let selected_id = self.items.get(self.selected_idx).map(|i| i.id.clone());
+
+        self.items = self
+            .source_items
+            .iter()
+            .filter(|item| match &self.sentiment_filter {
+                Some(label) => item
+                    .metadata
+                    .sentiment
+                    .as_ref()
+                    .map(|s| &s.label == label)
+                    .unwrap_or(false),
+                None => true,
+            })
+            .filter(|item| match &self.ticker_filter {
+                Some(symbol) => item.metadata.tags.iter().any(|t| t == symbol),
+                None => true,
+            })
+            .cloned()
+            .collect();
+
+        self.sort_mode.sort(&mut self.items);
+
+        // Fuzzy filter keeps only matches, best first (stable for equal scores)
+        if !self.filter_query.trim().is_empty() {
+            let mut scored: Vec<(i64, FeedItem)> = std::mem::take(&mut self.items)
+                .into_iter()
+                .filter_map(|item| fuzzy::match_item(&self.filter_query, &item).map(|s| (s, item)))
+                .collect();
+            scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+            self.items = scored.into_iter().map(|(_, item)| item).collect();
+        }
+
+        self.group_by.group(&mut self.items);
+
+        // Keep the cursor on the same item when it survives the filter
+        self.selected_idx = selected_id
+            .and_then(|id| self.items.iter().position(|i| i.id == id))
+            .unwrap_or(0);
+    }
+
     /// Load more items for infinite scroll
     async fn load_more_items(&mut self) -> Result<()> {
         if self.loading {
@@ -439,36 +1999,49 @@ impl App {
         self.loading = true;
         self.status_message = Some("Loading more...".to_string());
 
-        let current_count = self.items.len();
         let batch_size = 50;
 
         if let AppState::Feed(provider_id) = &self.state.clone() {
-            if let Some(provider) = self.registry.get(provider_id) {
-                if provider.supports_offset() {
-                    match provider
-                        .fetch_items_with_offset(current_count, batch_size)
-                        .await
-                    {
-                        Ok(new_items) => {
-                            let new_count = new_items.len();
-                            if new_count > 0 {
-                                self.items.extend(new_items);
-                                self.status_message = Some(format!(
-                                    "Loaded {} more ({} total)",
-                                    new_count,
-                                    self.items.len()
-                                ));
-                            } else {
-                                self.status_message = Some("End of feed".to_string());
-                            }
-                        }
-                        Err(e) => {
-                            self.status_message = Some(format!("Error: {}", e));
+            let supports_offset = self
+                .registry
+                .get(provider_id)
+                .map(|p| p.supports_offset())
+                .unwrap_or(false);
+
+            if supports_offset {
+                match self
+                    .registry
+                    .fetch_from_with_offset(provider_id, self.feed_offset, batch_size)
+                    .await
+                {
+                    Ok((mut new_items, fetched)) => {
+                        self.feed_offset += fetched;
+                        // Feeds shift while paging, so a page can repeat items
+                        let seen: HashSet<&str> =
+                            self.source_items.iter().map(|s| s.id.as_str()).collect();
+                        new_items.retain(|item| !seen.contains(item.id.as_str()));
+                        let new_count = new_items.len();
+                        self.index_items(&new_items);
+                        self.dispatch_webhooks(&new_items);
+                        if new_count > 0 {
+                            self.source_items.extend(new_items);
+                            self.refresh_new_comment_counts();
+                            self.apply_view();
+                            self.status_message = Some(format!(
+                                "Loaded {} more ({} total)",
+                                new_count,
+                                self.items.len()
+                            ));
+                        } else {
+                            self.status_message = Some("End of feed".to_string());
                         }
                     }
-                } else {
-                    self.status_message = Some("End of feed".to_string());
+                    Err(e) => {
+                        self.status_message = Some(format!("Error: {}", e));
+                    }
                 }
+            } else {
+                self.status_message = Some("End of feed".to_string());
             }
         }
 
@@ -483,11 +2056,18 @@ impl App {
 
         let limit = self.config.finnhub.max_articles.max(100);
 
-        match self.registry.fetch_from(provider_id, limit).await {
-            Ok(items) => {
-                self.items = items;
-                self.selected_idx = 0;
-                self.status_message = Some(format!("Loaded {} items", self.items.len()));
+        match self.registry.fetch_counted(provider_id, limit).await {
+            Ok((items, fetched)) => {
+                self.feed_offset = fetched;
+                self.index_items(&items);
+                self.archive_items(&self.feed_key(provider_id), &items);
+                self.dispatch_webhooks(&items);
+                self.source_items = items;
+                self.items.clear();
+                self.refresh_new_comment_counts();
+                self.apply_view();
+                self.preview_requested.clear();
+                self.status_message = Some(self.loaded_message());
             }
             Err(e) => {
                 self.status_message = Some(format!("Error: {}", e));
@@ -510,11 +2090,190 @@ impl App {
                 let id = id.clone();
                 self.fetch_provider_items(&id).await?;
             }
+            AppState::Search(query) => {
+                let query = query.clone();
+                self.run_search(&query);
+            }
             _ => {}
         }
         Ok(())
     }
 
+    /// Start full-article extraction for the current item if enabled; the
+    /// page is fetched in the background and picked up by `apply_article_content`
+    fn request_full_content(&mut self) {
+        if !self.config.hackernews.fetch_full_content {
+            return;
+        }
+
+        let (item_id, url) = match &self.current_item {
+            Some(item) if item.content.is_none() => match &item.url {
+                Some(url) if is_extractable_url(url) => (item.id.clone(), url.clone()),
+                _ => return,
+            },
+            _ => return,
+        };
+
+        if self.config.cache.enabled {
+            if let Ok(content) = self.cache.get::<String>(CacheKey::HnContent(url.clone())) {
+                self.set_article_content(&item_id, content);
+                return;
+            }
+        }
+
+        self.content_loading = true;
+        self.status_message = Some("Loading article...".to_string());
+
+        let client = self.http_client.clone();
+        let tx = self.content_tx.clone();
+        tokio::spawn(async move {
+            let content = readability::fetch_article_content(&client, &url).await;
+            let _ = tx.send((item_id, url, content));
+        });
+    }
+
+    /// Attach article bodies that finished extracting since the last frame
+    fn apply_article_content(&mut self) {
+        while let Ok((item_id, url, content)) = self.content_rx.try_recv() {
+            let is_current = self
+                .current_item
+                .as_ref()
+                .map(|i| i.id == item_id)
+                .unwrap_or(false);
+            if is_current {
+                self.content_loading = false;
+            }
+
+            let Some(content) = content else {
+                if is_current && self.state == AppState::Article {
+                    self.status_message = Some("Full article not available".to_string());
+                }
+                continue;
+            };
+
+            if self.config.cache.enabled {
+                if let Err(e) = self.cache.set(
+                    CacheKey::HnContent(url),
+                    content.clone(),
+                    self.config.cache.ttl,
+                ) {
+                    tracing::warn!("Failed to cache article content: {}", e);
+                }
+            }
+
+            if is_current && self.state == AppState::Article {
+                self.status_message = Some("Article loaded".to_string());
+            }
+            self.set_article_content(&item_id, content);
+        }
+    }
+
+    /// Store an extracted article body on the item wherever it is loaded
+    fn set_article_content(&mut self, item_id: &str, content: String) {
+        // Keep the list copies in sync so reopening the item is instant
+        for item in self
+            .source_items
+            .iter_mut()
+            .chain(self.items.iter_mut())
+            .filter(|i| i.id == item_id)
+        {
+            item.content = Some(content.clone());
+        }
+        if let Some(item) = self.current_item.as_mut().filter(|i| i.id == item_id) {
+            item.content = Some(content);
+        }
+        // Make the article body searchable too
+        if let Some(item) = self.current_item.as_ref().filter(|i| i.id == item_id) {
+            self.index_items(std::slice::from_ref(item));
+        }
+    }
+
+    /// Start fetching a link preview for the selected list item
+    fn request_link_preview(&mut self) {
+        if !self.is_list_view() {
+            return;
+        }
+
+        let url = match self.items.get(self.selected_idx) {
+            Some(item) if item.metadata.link_preview.is_none() => match &item.url {
+                Some(url) if is_extractable_url(url) => url.clone(),
+                _ => return,
+            },
+            _ => return,
+        };
+
+        if let Some(failed_at) = self.preview_failed.get(&url) {
+            if failed_at.elapsed() < PREVIEW_RETRY_DELAY {
+                return;
+            }
+        }
+
+        if !self.preview_requested.insert(url.clone()) {
+            return;
+        }
+
+        if self.config.cache.enabled {
+            if let Ok(preview) = self
+                .cache
+                .get::<LinkPreview>(CacheKey::LinkPreview(url.clone()))
+            {
+                self.set_link_preview(&url, preview);
+                return;
+            }
+        }
+
+        let client = self.http_client.clone();
+        let tx = self.preview_tx.clone();
+        tokio::spawn(async move {
+            let preview = link_preview::fetch_link_preview(&client, &url).await;
+            let _ = tx.send((url, preview));
+        });
+    }
+
+    /// Attach link previews that finished fetching since the last frame
+    fn apply_link_previews(&mut self) {
+        while let Ok((url, preview)) = self.preview_rx.try_recv() {
+            let Some(preview) = preview else {
+                // Let the URL be requested again once the retry delay passes
+                self.preview_requested.remove(&url);
+                self.preview_failed.insert(url, Instant::now());
+                continue;
+            };
+            self.preview_failed.remove(&url);
+
+            if self.config.cache.enabled {
+                if let Err(e) = self.cache.set(
+                    CacheKey::LinkPreview(url.clone()),
+                    preview.clone(),
+                    self.config.cache.ttl,
+                ) {
+                    tracing::warn!("Failed to cache link preview: {}", e);
+                }
+            }
+
+            self.set_link_preview(&url, preview);
+        }
+    }
+
+    /// Store a preview on every loaded item pointing at the URL
+    fn set_link_preview(&mut self, url: &str, preview: LinkPreview) {
+        for item in self
+            .source_items
+            .iter_mut()
+            .chain(self.items.iter_mut())
+            .filter(|i| i.url.as_deref() == Some(url))
+        {
+            item.metadata.link_preview = Some(preview.clone());
+        }
+        if let Some(item) = self
+            .current_item
+            .as_mut()
+            .filter(|i| i.url.as_deref() == Some(url))
+        {
+            item.metadata.link_preview = Some(preview);
+        }
+    }
+
     /// Load comments for the current item
     pub async fn load_comments_for_current_item(&mut self) -> Result<()> {
         let item = match &self.current_item {
@@ -524,50 +2283,34 @@ impl App {
 
         self.comments_loading = true;
         self.comments.clear();
-
-        let provider_id = item.provider_id.as_str();
-
-        match provider_id {
-            "hackernews" => {
-                if let Some(hn_id) = item.metadata.hn_id {
-                    if let Ok(hn) = HackerNewsProvider::new(None) {
-                        match hn.fetch_comments(hn_id, 3).await {
-                            Ok(comments) => {
-                                self.comments = comments;
-                                self.status_message =
-                                    Some(format!("Loaded {} comments", self.comments.len()));
-                            }
-                            Err(e) => {
-                                self.status_message =
-                                    Some(format!("Error loading comments: {}", e));
-                            }
-                        }
-                    }
-                }
-            }
-            "reddit" => {
-                if let (Some(subreddit), Some(post_id)) = (
-                    item.metadata.subreddit.as_ref(),
-                    item.metadata.reddit_id.as_ref(),
-                ) {
-                    // Create a new Reddit provider instance for comment fetching
-                    if let Ok(reddit) = RedditProvider::new(vec![], None, true) {
-                        match reddit.fetch_comments(subreddit, post_id, 3).await {
-                            Ok(comments) => {
-                                self.comments = comments;
-                                self.status_message =
-                                    Some(format!("Loaded {} comments", self.comments.len()));
-                            }
-                            Err(e) => {
-                                self.status_message =
-                                    Some(format!("Error loading comments: {}", e));
-                            }
-                        }
-                    }
+        self.new_comment_ids.clear();
+
+        match providers::fetch_comments(&item, 3).await {
+            Ok(comments) => {
+                self.comments = comments;
+                self.new_comment_ids = match &self.thread_visits {
+                    Some(visits) => visits
+                        .record(&item, &self.comments)
+                        .map_err(|e| tracing::warn!("Failed to record thread visit: {}", e))
+                        .unwrap_or_default(),
+                    None => HashSet::new(),
+                };
+                if let Some(key) = thread_key(&item) {
+                    self.new_comment_counts.remove(&key);
                 }
+
+                self.status_message = Some(if self.new_comment_ids.is_empty() {
+                    format!("Loaded {} comments", self.comments.len())
+                } else {
+                    format!(
+                        "Loaded {} comments, {} new since last visit (n: jump)",
+                        self.comments.len(),
+                        self.new_comment_ids.len()
+                    )
+                });
             }
-            _ => {
-                self.status_message = Some("Comments not available for this source".to_string());
+            Err(e) => {
+                self.status_message = Some(format!("Error loading comments: {}", e));
             }
         }
 
@@ -592,14 +2335,26 @@ impl App {
                     self.selected_idx,
                     self.status_message.as_deref(),
                     self.loading,
+                    &self.list_options(),
                 );
             }
             AppState::Feed(provider_id) => {
-                let (name, icon) = self
+                let (mut name, icon) = self
                     .registry
                     .get(provider_id)
                     .map(|p| (p.name().to_string(), p.icon().to_string()))
                     .unwrap_or(("Unknown".to_string(), "?".to_string()));
+                if provider_id == "reddit" {
+                    name = self.reddit_tab_title(&name);
+                }
+                let search = match provider_id.as_str() {
+                    "hackernews" => self.hn_search.as_ref(),
+                    "arxiv" => self.arxiv_search.as_ref(),
+                    _ => None,
+                };
+                if let Some(query) = search {
+                    name = format!("{} · search: {}", name, query);
+                }
 
                 let provider_color = ProviderColors::for_provider(provider_id);
 
@@ -612,11 +2367,60 @@ impl App {
                     self.selected_idx,
                     self.status_message.as_deref(),
                     self.loading,
+                    &self.list_options(),
+                );
+            }
+            AppState::Archive(feed) => {
+                let taken = self
+                    .archive_at
+                    .map(|t| t.with_timezone(&Local).format("%a %d %b %H:%M").to_string())
+                    .unwrap_or_default();
+                let (provider_id, tab) = feed.split_once('/').unwrap_or((feed, ""));
+                let name = self
+                    .registry
+                    .get(provider_id)
+                    .map(|p| p.name().to_string())
+                    .map(|name| {
+                        if tab.is_empty() {
+                            name
+                        } else {
+                            format!("{} · {}", name, tab)
+                        }
+                    })
+                    .unwrap_or_else(|| "All Sources".to_string());
+                views::dashboard::render(
+                    f,
+                    &format!("{} as of {}", name, taken),
+                    "⏱",
+                    ProviderColors::for_provider(feed),
+                    &self.items,
+                    self.selected_idx,
+                    self.status_message.as_deref(),
+                    self.loading,
+                    &self.list_options(),
+                );
+            }
+            AppState::Search(query) => {
+                views::dashboard::render(
+                    f,
+                    &format!("Search: {}", query),
+                    "/",
+                    ProviderColors::hackernews(),
+                    &self.items,
+                    self.selected_idx,
+                    self.status_message.as_deref(),
+                    self.loading,
+                    &self.list_options(),
                 );
             }
             AppState::Article => {
                 if let Some(item) = &self.current_item {
-                    views::article::render_feed_item(f, item, self.scroll_offset);
+                    views::article::render_feed_item(
+                        f,
+                        item,
+                        self.scroll_offset,
+                        self.status_message.as_deref(),
+                    );
                 }
             }
             AppState::Comments => {
@@ -628,6 +2432,7 @@ impl App {
                 views::comments::render(
                     f,
                     &self.comments,
+                    &self.new_comment_ids,
                     self.comments_selected,
                     self.comments_scroll,
                     &provider_name,
@@ -636,6 +2441,37 @@ impl App {
             AppState::Help => {
                 views::help::render(f, &crate::utils::get_help_text(self.config.ui.vim_mode));
             }
+            AppState::Notifications => {
+                views::notifications::render(f, self.notifier.log(), self.notifications_selected);
+            }
+            AppState::User => {
+                if let Some(page) = self.user_pages.last() {
+                    views::user::render(
+                        f,
+                        &page.profile,
+                        page.show_comments,
+                        page.selected,
+                        self.status_message.as_deref(),
+                    );
+                }
+            }
+            AppState::Discussions => {
+                views::discussions::render(
+                    f,
+                    &self.discussions_url,
+                    &self.discussions,
+                    self.discussions_selected,
+                    self.status_message.as_deref(),
+                );
+            }
+            AppState::ReadingQueue => {
+                views::reading_queue::render(
+                    f,
+                    &self.queue_entries,
+                    self.queue_selected,
+                    self.status_message.as_deref(),
+                );
+            }
         }
     }
 }
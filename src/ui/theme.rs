@@ -2,6 +2,8 @@
 //!
 //! Bloomberg-inspired color scheme and consistent styling
 
+use crate::models::SentimentLabel;
+use crate::utils::parse_color;
 use ratatui::style::{Color, Modifier, Style};
 
 /// Provider-specific brand colors
@@ -87,6 +89,19 @@ impl Theme {
         Color::Yellow
     }
 
+    pub fn sentiment(label: &SentimentLabel) -> Color {
+        match label {
+            SentimentLabel::Positive => Self::positive(),
+            SentimentLabel::Negative => Self::negative(),
+            SentimentLabel::Neutral => Self::warning(),
+        }
+    }
+
+    /// Parse a rule highlight colour ("yellow", "lightblue", "#ff8800")
+    pub fn highlight(name: &str) -> Option<Color> {
+        parse_color(name)
+    }
+
     // Border colors
     pub fn border_default() -> Color {
         Color::Rgb(51, 51, 51)
@@ -13,7 +13,12 @@ use ratatui::{
 };
 
 /// Render a FeedItem in article view
-pub fn render_feed_item(f: &mut Frame, item: &FeedItem, scroll_offset: usize) {
+pub fn render_feed_item(
+    f: &mut Frame,
+    item: &FeedItem,
+    scroll_offset: usize,
+    status_message: Option<&str>,
+) {
     let size = f.size();
 
     let chunks = Layout::default()
@@ -58,7 +63,7 @@ pub fn render_feed_item(f: &mut Frame, item: &FeedItem, scroll_offset: usize) {
     );
 
     // Render help bar with 'c' for comments
-    render_help_bar(f, chunks[4]);
+    render_help_bar(f, chunks[4], item, status_message);
 }
 
 fn render_metadata(f: &mut Frame, area: Rect, item: &FeedItem) {
@@ -74,6 +79,20 @@ fn render_metadata(f: &mut Frame, area: Rect, item: &FeedItem) {
         meta_parts.push(Span::styled(author, Style::default().fg(Color::Yellow)));
     }
 
+    if let Some(post_type) = &item.metadata.post_type {
+        meta_parts.push(Span::raw(" | "));
+        meta_parts.push(Span::styled(
+            post_type.as_str(),
+            Style::default().fg(Color::Magenta),
+        ));
+        if let Some(origin) = &item.metadata.crosspost_from {
+            meta_parts.push(Span::styled(
+                format!(" from {}", origin),
+                Style::default().fg(Color::Magenta),
+            ));
+        }
+    }
+
     if let Some(sentiment) = &item.metadata.sentiment {
         meta_parts.push(Span::raw(" | "));
         let sentiment_style = match sentiment.label {
@@ -191,20 +210,41 @@ fn render_progress_bar(
     f.render_widget(gauge, area);
 }
 
-fn render_help_bar(f: &mut Frame, area: Rect) {
-    let help = Paragraph::new(Line::from(vec![
+fn render_help_bar(f: &mut Frame, area: Rect, item: &FeedItem, status_message: Option<&str>) {
+    let mut spans = vec![
         Span::styled("j/k", Style::default().fg(Color::Yellow)),
         Span::raw(":Scroll "),
         Span::styled("[/]", Style::default().fg(Color::Yellow)),
         Span::raw(":Prev/Next "),
         Span::styled("c", Style::default().fg(Color::Yellow)),
         Span::raw(":Comments "),
+        Span::styled("D", Style::default().fg(Color::Yellow)),
+        Span::raw(":Discussions "),
         Span::styled("o", Style::default().fg(Color::Yellow)),
         Span::raw(":Open "),
-        Span::styled("Esc", Style::default().fg(Color::Yellow)),
-        Span::raw(":Back"),
-    ]))
-    .block(
+    ];
+    if !item.metadata.media_urls.is_empty() {
+        spans.push(Span::styled("m", Style::default().fg(Color::Yellow)));
+        spans.push(Span::raw(":Media "));
+    }
+    if item.metadata.arxiv_id.is_some() {
+        spans.push(Span::styled("p", Style::default().fg(Color::Yellow)));
+        spans.push(Span::raw(":PDF "));
+        spans.push(Span::styled("b/B", Style::default().fg(Color::Yellow)));
+        spans.push(Span::raw(":BibTeX "));
+        spans.push(Span::styled("w", Style::default().fg(Color::Yellow)));
+        spans.push(Span::raw(":Queue "));
+    }
+    spans.push(Span::styled("Esc", Style::default().fg(Color::Yellow)));
+    spans.push(Span::raw(":Back"));
+    if let Some(message) = status_message {
+        spans.push(Span::styled(
+            format!("  {}", message),
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+
+    let help = Paragraph::new(Line::from(spans)).block(
         Block::default()
             .borders(Borders::TOP)
             .border_style(Style::default().fg(Color::DarkGray)),
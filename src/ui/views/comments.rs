@@ -10,14 +10,16 @@ use ratatui::{
     widgets::{Block, Borders, List, ListItem, Paragraph},
     Frame,
 };
+use std::collections::HashSet;
 
 const INDENT_WIDTH: usize = 2;
 const MAX_VISIBLE_DEPTH: u32 = 5;
 
-/// Render comments view
+/// Render comments view; `new_ids` are highlighted as added since the last visit
 pub fn render(
     f: &mut Frame,
     comments: &[Comment],
+    new_ids: &HashSet<String>,
     selected_idx: usize,
     scroll_offset: usize,
     provider_name: &str,
@@ -33,22 +35,35 @@ pub fn render(
         ])
         .split(size);
 
-    render_header(f, chunks[0], comments, provider_name);
-    render_comments(f, chunks[1], comments, selected_idx, scroll_offset);
-    render_help(f, chunks[2]);
+    render_header(f, chunks[0], comments, new_ids.len(), provider_name);
+    render_comments(f, chunks[1], comments, new_ids, selected_idx, scroll_offset);
+    render_help(f, chunks[2], !new_ids.is_empty());
 }
 
-fn render_header(f: &mut Frame, area: Rect, comments: &[Comment], provider_name: &str) {
+fn render_header(
+    f: &mut Frame,
+    area: Rect,
+    comments: &[Comment],
+    new_count: usize,
+    provider_name: &str,
+) {
     let total = total_comment_count(comments);
     let title = format!(" Comments ({}) - {} ", total, provider_name);
 
-    let header = Paragraph::new(Line::from(vec![Span::styled(
+    let mut spans = vec![Span::styled(
         title,
         Style::default()
             .fg(Color::Cyan)
             .add_modifier(Modifier::BOLD),
-    )]))
-    .block(
+    )];
+    if new_count > 0 {
+        spans.push(Span::styled(
+            format!("{} new since last visit", new_count),
+            Style::default().fg(Color::Yellow),
+        ));
+    }
+
+    let header = Paragraph::new(Line::from(spans)).block(
         Block::default()
             .borders(Borders::ALL)
             .border_style(Style::default().fg(Color::Cyan)),
@@ -57,26 +72,33 @@ fn render_header(f: &mut Frame, area: Rect, comments: &[Comment], provider_name:
     f.render_widget(header, area);
 }
 
-fn render_comments(
+/// Render comments as a threaded list, two lines each. The list scrolls
+/// further than `scroll_offset` when needed to keep the selection on screen.
+pub fn render_comments(
     f: &mut Frame,
     area: Rect,
     comments: &[Comment],
+    new_ids: &HashSet<String>,
     selected_idx: usize,
     scroll_offset: usize,
 ) {
     // Flatten comments for display
     let flattened = flatten_comments(comments);
-    let visible_height = area.height.saturating_sub(2) as usize;
+    let visible = (area.height.saturating_sub(2) as usize / 2).max(1);
+    let scroll_offset = scroll_offset
+        .max(selected_idx.saturating_sub(visible - 1))
+        .min(selected_idx);
 
     let list_items: Vec<ListItem> = flattened
         .iter()
         .skip(scroll_offset)
-        .take(visible_height)
+        .take(visible)
         .enumerate()
         .map(|(display_idx, (comment, depth))| {
             let actual_idx = scroll_offset + display_idx;
             let is_selected = actual_idx == selected_idx;
-            render_comment_line(comment, *depth, is_selected, area.width as usize)
+            let is_new = new_ids.contains(&comment.id);
+            render_comment_line(comment, *depth, is_selected, is_new, area.width as usize)
         })
         .collect();
 
@@ -93,6 +115,7 @@ fn render_comment_line(
     comment: &Comment,
     depth: u32,
     is_selected: bool,
+    is_new: bool,
     width: usize,
 ) -> ListItem<'static> {
     let indent = "  ".repeat((depth as usize).min(MAX_VISIBLE_DEPTH as usize) * INDENT_WIDTH);
@@ -116,13 +139,22 @@ fn render_comment_line(
         .map(|s| format!(" [{}]", s))
         .unwrap_or_default();
 
-    let header = Line::from(vec![
+    let mut header = vec![
         Span::raw(indent.clone()),
         Span::raw(depth_indicator),
         Span::styled(comment.author.clone(), author_style),
         Span::styled(score_text, score_style),
         Span::styled(format!(" {}", comment.time_ago()), time_style),
-    ]);
+    ];
+    if is_new {
+        header.push(Span::styled(
+            " ● new",
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ));
+    }
+    let header = Line::from(header);
 
     // Text preview (truncated)
     let text = comment.text_plain.as_ref().unwrap_or(&comment.text);
@@ -145,18 +177,23 @@ fn render_comment_line(
     ListItem::new(vec![header, text_line])
 }
 
-fn render_help(f: &mut Frame, area: Rect) {
-    let help = Paragraph::new(Line::from(vec![
-        Span::styled("jk/", Style::default().fg(Color::Yellow)),
-        Span::raw("Navigate "),
+fn render_help(f: &mut Frame, area: Rect, has_new: bool) {
+    let mut help = vec![
+        Span::styled("j/k", Style::default().fg(Color::Yellow)),
+        Span::raw(":Navigate "),
         Span::styled("Enter", Style::default().fg(Color::Yellow)),
         Span::raw(":Expand "),
         Span::styled("c", Style::default().fg(Color::Yellow)),
         Span::raw(":Collapse "),
-        Span::styled("Esc", Style::default().fg(Color::Yellow)),
-        Span::raw(":Back"),
-    ]))
-    .block(
+    ];
+    if has_new {
+        help.push(Span::styled("n/N", Style::default().fg(Color::Yellow)));
+        help.push(Span::raw(":Next/prev new "));
+    }
+    help.push(Span::styled("Esc", Style::default().fg(Color::Yellow)));
+    help.push(Span::raw(":Back"));
+
+    let help = Paragraph::new(Line::from(help)).block(
         Block::default()
             .borders(Borders::TOP)
             .border_style(Style::default().fg(Color::DarkGray)),
@@ -166,7 +203,7 @@ fn render_help(f: &mut Frame, area: Rect) {
 }
 
 /// Flatten nested comments into a list with depth info
-fn flatten_comments(comments: &[Comment]) -> Vec<(&Comment, u32)> {
+pub fn flatten_comments(comments: &[Comment]) -> Vec<(&Comment, u32)> {
     let mut result = Vec::new();
     for comment in comments {
         flatten_comment_recursive(comment, 0, &mut result);
@@ -2,8 +2,12 @@
 //!
 //! Bloomberg-style multi-panel layout with feed list and preview
 
-use crate::models::FeedItem;
+use crate::analysis::entities::is_symbol_tag;
+use crate::cache::archive::HistoryPoint;
+use crate::cache::threads::thread_key;
+use crate::models::{FeedItem, GroupBy, LinkPreview, SentimentLabel, SortMode};
 use crate::ui::theme::Theme;
+use crate::utils::fuzzy;
 use crate::utils::parser::truncate;
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
@@ -12,6 +16,73 @@ use ratatui::{
     widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
     Frame,
 };
+use std::collections::HashMap;
+
+/// How the feed list is filtered, ordered and grouped
+#[derive(Debug, Clone, Copy)]
+pub struct ListOptions<'a> {
+    /// Fuzzy filter query (empty when not filtering)
+    pub filter: &'a str,
+    /// Whether the filter prompt has focus
+    pub filter_editing: bool,
+    /// Line prompt and its text while one is open
+    pub prompt: Option<(Prompt, &'a str)>,
+    pub sort: SortMode,
+    pub group_by: GroupBy,
+    /// Archived rank/score of the selected item, oldest first
+    pub history: &'a [HistoryPoint],
+    /// Comments added since the last visit, by thread key
+    pub new_comments: &'a HashMap<String, i32>,
+}
+
+/// Single-line prompts shown in place of the status bar
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Prompt {
+    /// Query the local search index
+    Search,
+    /// Pick a point in time to browse the archive at
+    AsOf,
+    /// Show, add or remove a subreddit in the Reddit feed
+    Subreddit,
+    /// Search Hacker News through Algolia
+    HnSearch,
+    /// Search arXiv through the export API
+    ArxivSearch,
+    /// Show only items tagged with a ticker
+    Ticker,
+}
+
+impl Prompt {
+    fn label(&self) -> &'static str {
+        match self {
+            Prompt::Search => "Search: ",
+            Prompt::AsOf => "As of: ",
+            Prompt::Subreddit => "Subreddit: ",
+            Prompt::HnSearch => "HN search: ",
+            Prompt::ArxivSearch => "arXiv search: ",
+            Prompt::Ticker => "Ticker: ",
+        }
+    }
+
+    fn hint(&self) -> &'static str {
+        match self {
+            Prompt::Search => {
+                "  Enter:Search Esc:Cancel  e.g. fed rates provider:reddit tag:AAPL since:7d"
+            }
+            Prompt::AsOf => "  Enter:Go Esc:Cancel  e.g. yesterday 09:00, 18h, 2026-01-31 14:00",
+            Prompt::Subreddit => {
+                "  Enter:Go Esc:Cancel  r/name, m/group or all; +name saves, -name removes"
+            }
+            Prompt::HnSearch => {
+                "  Enter:Search Esc:Cancel  e.g. rust by:pg type:comment points:100 since:7d sort:date"
+            }
+            Prompt::ArxivSearch => {
+                "  Enter:Search Esc:Cancel  e.g. au:hinton ti:\"deep learning\" cat:cs.LG sort:date"
+            }
+            Prompt::Ticker => "  Enter:Filter Esc:Cancel  e.g. TSLA or $NVDA; empty clears the filter",
+        }
+    }
+}
 
 /// Render dashboard with split layout
 #[allow(clippy::too_many_arguments)]
@@ -24,6 +95,7 @@ pub fn render(
     selected_idx: usize,
     status_message: Option<&str>,
     loading: bool,
+    options: &ListOptions,
 ) {
     let size = f.size();
 
@@ -55,9 +127,23 @@ pub fn render(
         ])
         .split(main_chunks[1]);
 
-    render_feed_list(f, content_chunks[0], items, selected_idx, provider_color);
-    render_preview_panel(f, content_chunks[1], items, selected_idx);
-    render_status_bar(f, main_chunks[2], status_message, loading);
+    render_feed_list(
+        f,
+        content_chunks[0],
+        items,
+        selected_idx,
+        provider_color,
+        options,
+    );
+    render_preview_panel(f, content_chunks[1], items, selected_idx, options.history);
+    if let Some((prompt, input)) = options.prompt {
+        render_prompt(f, main_chunks[2], prompt.label(), input, prompt.hint());
+    } else if options.filter_editing {
+        let hint = format!("  {} matches  Enter:Keep Esc:Clear", items.len());
+        render_prompt(f, main_chunks[2], "Filter: ", options.filter, &hint);
+    } else {
+        render_status_bar(f, main_chunks[2], status_message, loading);
+    }
 }
 
 fn render_header(f: &mut Frame, area: Rect, name: &str, icon: &str, color: Color, count: usize) {
@@ -87,29 +173,61 @@ fn render_feed_list(
     items: &[FeedItem],
     selected_idx: usize,
     accent: Color,
+    options: &ListOptions,
 ) {
     let visible_height = area.height.saturating_sub(2) as usize;
 
-    // Calculate scroll offset
-    let scroll_offset = if selected_idx >= visible_height {
-        selected_idx - visible_height + 1
+    // Interleave group headers with items; `None` rows are headers
+    let mut rows: Vec<(Option<usize>, Option<String>)> = Vec::with_capacity(items.len());
+    let mut current_group = None;
+    for (idx, item) in items.iter().enumerate() {
+        let group = options.group_by.label(item);
+        if group.is_some() && group != current_group {
+            rows.push((None, group.clone()));
+            current_group = group;
+        }
+        rows.push((Some(idx), None));
+    }
+
+    // Calculate scroll offset from the selected row, not the item index
+    let selected_row = rows
+        .iter()
+        .position(|(idx, _)| *idx == Some(selected_idx))
+        .unwrap_or(0);
+    let scroll_offset = if selected_row >= visible_height {
+        selected_row - visible_height + 1
     } else {
         0
     };
 
-    let list_items: Vec<ListItem> = items
+    let list_items: Vec<ListItem> = rows
         .iter()
         .skip(scroll_offset)
         .take(visible_height)
-        .enumerate()
-        .map(|(display_idx, item)| {
-            let actual_idx = scroll_offset + display_idx;
-            let is_selected = actual_idx == selected_idx;
-
-            render_feed_item(item, is_selected, area.width as usize, accent)
+        .map(|(idx, header)| match (idx, header) {
+            (Some(idx), _) => render_feed_item(
+                &items[*idx],
+                *idx == selected_idx,
+                area.width as usize,
+                accent,
+                options.filter,
+                thread_key(&items[*idx]).and_then(|key| options.new_comments.get(&key).copied()),
+            ),
+            (None, header) => ListItem::new(Line::from(Span::styled(
+                format!("── {} ", header.as_deref().unwrap_or_default()),
+                Style::default().fg(accent).add_modifier(Modifier::BOLD),
+            ))),
         })
         .collect();
 
+    let mut title = format!(" Feed | {} ", options.sort.as_str());
+    if options.group_by != GroupBy::None {
+        title.push_str(&format!("by {} ", options.group_by.as_str().to_lowercase()));
+    }
+    if !options.filter.is_empty() {
+        title.push_str(&format!("| filter: {} ", options.filter));
+    }
+
     let list = List::new(list_items).block(
         Block::default()
             .borders(Borders::ALL)
@@ -118,7 +236,7 @@ fn render_feed_list(
             } else {
                 Theme::style_border()
             })
-            .title(" Feed "),
+            .title(title),
     );
 
     f.render_widget(list, area);
@@ -129,20 +247,33 @@ fn render_feed_item(
     is_selected: bool,
     width: usize,
     _accent: Color,
+    filter: &str,
+    new_comments: Option<i32>,
 ) -> ListItem<'static> {
-    let style = if is_selected {
-        Theme::style_selected()
-    } else {
-        Theme::style_title()
+    let highlight = item
+        .metadata
+        .highlight
+        .as_deref()
+        .and_then(Theme::highlight);
+    let style = match (is_selected, highlight) {
+        (true, _) => Theme::style_selected(),
+        (false, Some(colour)) => Style::default().fg(colour),
+        (false, None) => Theme::style_title(),
     };
 
     let prefix = if is_selected { "> " } else { "  " };
     let title = truncate(&item.title, width.saturating_sub(25));
 
-    let mut line1_spans = vec![
-        Span::styled(prefix.to_string(), style),
-        Span::styled(title, style),
-    ];
+    let mut line1_spans = vec![Span::styled(prefix.to_string(), style)];
+    if item.metadata.pinned {
+        line1_spans.push(Span::styled("📌 ", style));
+    }
+    if filter.is_empty() {
+        line1_spans.push(Span::styled(title, style));
+    } else {
+        let positions = fuzzy::title_positions(filter, &item.title);
+        line1_spans.extend(highlight_matches(&title, &positions, style));
+    }
 
     // Add score if available
     if let Some(score) = item.score_display() {
@@ -155,13 +286,40 @@ fn render_feed_item(
         line1_spans.push(Span::raw(" "));
         line1_spans.push(Span::styled(comments, Theme::style_comments()));
     }
+    if let Some(count) = new_comments {
+        line1_spans.push(Span::styled(
+            format!(" +{} new", count),
+            Style::default().fg(Color::Yellow),
+        ));
+    }
 
-    // Second line: metadata
+    // Second line: metadata, led by a sentiment marker
     let meta_prefix = "  ";
-    let mut line2_spans = vec![
-        Span::raw(meta_prefix),
-        Span::styled(item.source.clone(), Theme::style_muted()),
-    ];
+    let mut line2_spans = vec![Span::raw(meta_prefix)];
+
+    if let Some(sentiment) = &item.metadata.sentiment {
+        let marker = match sentiment.label {
+            SentimentLabel::Positive => "+ ",
+            SentimentLabel::Negative => "- ",
+            SentimentLabel::Neutral => "= ",
+        };
+        line2_spans.push(Span::styled(
+            marker,
+            Style::default().fg(Theme::sentiment(&sentiment.label)),
+        ));
+    }
+
+    line2_spans.push(Span::styled(item.source.clone(), Theme::style_muted()));
+
+    if let Some(flair) = &item.metadata.flair {
+        line2_spans.push(Span::styled(format!(" [{}]", flair), Theme::style_muted()));
+    }
+    if item.metadata.nsfw {
+        line2_spans.push(Span::styled(" NSFW", Style::default().fg(Color::Red)));
+    }
+    if item.metadata.spoiler {
+        line2_spans.push(Span::styled(" spoiler", Style::default().fg(Color::Yellow)));
+    }
 
     if let Some(author) = &item.author {
         line2_spans.push(Span::styled(
@@ -175,27 +333,85 @@ fn render_feed_item(
         Theme::style_time(),
     ));
 
+    let symbols: Vec<String> = item
+        .metadata
+        .tags
+        .iter()
+        .filter(|t| is_symbol_tag(t))
+        .take(3)
+        .map(|t| format!(" ${}", t))
+        .collect();
+    if !symbols.is_empty() {
+        line2_spans.push(Span::styled(
+            symbols.concat(),
+            Style::default().fg(Theme::accent_primary()),
+        ));
+    }
+
     ListItem::new(vec![Line::from(line1_spans), Line::from(line2_spans)])
 }
 
-fn render_preview_panel(f: &mut Frame, area: Rect, items: &[FeedItem], selected_idx: usize) {
+/// Split text into spans, emphasising the characters at `positions`
+fn highlight_matches(text: &str, positions: &[usize], base: Style) -> Vec<Span<'static>> {
+    let matched = base
+        .fg(Theme::accent_primary())
+        .add_modifier(Modifier::BOLD | Modifier::UNDERLINED);
+
+    let mut spans = Vec::new();
+    let mut run = String::new();
+    let mut run_matched = false;
+
+    for (idx, c) in text.chars().enumerate() {
+        let is_match = positions.binary_search(&idx).is_ok();
+        if is_match != run_matched && !run.is_empty() {
+            let style = if run_matched { matched } else { base };
+            spans.push(Span::styled(std::mem::take(&mut run), style));
+        }
+        run_matched = is_match;
+        run.push(c);
+    }
+    if !run.is_empty() {
+        let style = if run_matched { matched } else { base };
+        spans.push(Span::styled(run, style));
+    }
+
+    spans
+}
+
+fn render_preview_panel(
+    f: &mut Frame,
+    area: Rect,
+    items: &[FeedItem],
+    selected_idx: usize,
+    history: &[HistoryPoint],
+) {
     let item = items.get(selected_idx);
 
     match item {
-        Some(item) => render_item_preview(f, area, item),
+        Some(item) => render_item_preview(f, area, item, history),
         None => render_empty_preview(f, area),
     }
 }
 
-fn render_item_preview(f: &mut Frame, area: Rect, item: &FeedItem) {
+fn render_item_preview(f: &mut Frame, area: Rect, item: &FeedItem, history: &[HistoryPoint]) {
+    // A single snapshot says nothing about movement
+    let history_height = if history.len() > 1 { 2 } else { 0 };
+    let link_height = if item.metadata.link_preview.is_some() {
+        4
+    } else {
+        0
+    };
+
     let inner_chunks = Layout::default()
         .direction(Direction::Vertical)
         .margin(1)
         .constraints([
-            Constraint::Length(3), // Title
-            Constraint::Length(2), // Metadata
-            Constraint::Min(5),    // Content
-            Constraint::Length(2), // Actions hint
+            Constraint::Length(3),              // Title
+            Constraint::Length(2),              // Metadata
+            Constraint::Length(history_height), // Rank history
+            Constraint::Length(link_height),    // Link preview
+            Constraint::Min(5),                 // Content
+            Constraint::Length(2),              // Actions hint
         ])
         .split(area);
 
@@ -236,6 +452,13 @@ fn render_item_preview(f: &mut Frame, area: Rect, item: &FeedItem) {
         ));
     }
 
+    if let Some(ratio) = item.metadata.upvote_ratio {
+        meta_spans.push(Span::styled(
+            format!(" ({:.0}% upvoted)", ratio * 100.0),
+            Theme::style_score(),
+        ));
+    }
+
     if let Some(comments) = item.metadata.comments {
         meta_spans.push(Span::styled(
             format!(" | {} comments", comments),
@@ -243,9 +466,24 @@ fn render_item_preview(f: &mut Frame, area: Rect, item: &FeedItem) {
         ));
     }
 
+    if let Some(sentiment) = &item.metadata.sentiment {
+        meta_spans.push(Span::styled(
+            format!(" | {} {:+.2}", sentiment.label.as_str(), sentiment.score),
+            Style::default().fg(Theme::sentiment(&sentiment.label)),
+        ));
+    }
+
     let meta = Paragraph::new(Line::from(meta_spans));
     f.render_widget(meta, inner_chunks[1]);
 
+    if history_height > 0 {
+        render_history(f, inner_chunks[2], history);
+    }
+
+    if let Some(preview) = &item.metadata.link_preview {
+        render_link_preview(f, inner_chunks[3], preview);
+    }
+
     // Content preview
     let content = item
         .summary
@@ -257,7 +495,7 @@ fn render_item_preview(f: &mut Frame, area: Rect, item: &FeedItem) {
     let content_para = Paragraph::new(content)
         .style(Theme::style_meta())
         .wrap(Wrap { trim: true });
-    f.render_widget(content_para, inner_chunks[2]);
+    f.render_widget(content_para, inner_chunks[4]);
 
     // Actions hint
     let actions = Paragraph::new(Line::from(vec![
@@ -269,7 +507,82 @@ fn render_item_preview(f: &mut Frame, area: Rect, item: &FeedItem) {
         Span::raw(":Browser"),
     ]))
     .style(Theme::style_muted());
-    f.render_widget(actions, inner_chunks[3]);
+    f.render_widget(actions, inner_chunks[5]);
+}
+
+/// Most recent archived ranks, e.g. "#5 ▲120 → #3 ▲210 → #1 ▲450"
+fn render_history(f: &mut Frame, area: Rect, history: &[HistoryPoint]) {
+    const SHOWN: usize = 5;
+
+    let mut spans = vec![Span::styled("History ", Theme::style_muted())];
+    if history.len() > SHOWN {
+        spans.push(Span::styled(
+            format!("(+{} earlier) ", history.len() - SHOWN),
+            Theme::style_muted(),
+        ));
+    }
+
+    let recent = &history[history.len().saturating_sub(SHOWN)..];
+    for (idx, point) in recent.iter().enumerate() {
+        if idx > 0 {
+            spans.push(Span::styled(" → ", Theme::style_muted()));
+        }
+        spans.push(Span::styled(
+            format!("#{}", point.rank),
+            Style::default().fg(Theme::text_primary()),
+        ));
+        if let Some(score) = point.score {
+            spans.push(Span::styled(format!(" ▲{}", score), Theme::style_score()));
+        }
+    }
+
+    let line = Paragraph::new(Line::from(spans)).wrap(Wrap { trim: true });
+    f.render_widget(line, area);
+}
+
+fn render_link_preview(f: &mut Frame, area: Rect, preview: &LinkPreview) {
+    let mut spans = Vec::new();
+
+    if let Some(site_name) = &preview.site_name {
+        spans.push(Span::styled(
+            site_name.clone(),
+            Style::default()
+                .fg(Color::Magenta)
+                .add_modifier(Modifier::BOLD),
+        ));
+    }
+
+    if let Some(content_type) = &preview.content_type {
+        if !spans.is_empty() {
+            spans.push(Span::styled(" | ", Theme::style_muted()));
+        }
+        spans.push(Span::styled(content_type.clone(), Theme::style_meta()));
+    }
+
+    if let Some(minutes) = preview.reading_time {
+        if !spans.is_empty() {
+            spans.push(Span::styled(" | ", Theme::style_muted()));
+        }
+        spans.push(Span::styled(
+            format!("~{} min read", minutes),
+            Theme::style_time(),
+        ));
+    }
+
+    let mut lines = vec![Line::from(spans)];
+    if let Some(description) = &preview.description {
+        lines.push(Line::from(Span::styled(
+            description.clone(),
+            Style::default().fg(Theme::text_primary()),
+        )));
+    }
+
+    let link = Paragraph::new(lines).wrap(Wrap { trim: true }).block(
+        Block::default()
+            .borders(Borders::BOTTOM)
+            .border_style(Theme::style_border()),
+    );
+    f.render_widget(link, area);
 }
 
 fn render_empty_preview(f: &mut Frame, area: Rect) {
@@ -285,12 +598,32 @@ fn render_empty_preview(f: &mut Frame, area: Rect) {
     f.render_widget(empty, area);
 }
 
+fn render_prompt(f: &mut Frame, area: Rect, label: &str, input: &str, hint: &str) {
+    let prompt = Paragraph::new(Line::from(vec![
+        Span::styled(
+            label.to_string(),
+            Style::default().fg(Theme::accent_primary()),
+        ),
+        Span::styled(format!("{}█", input), Theme::style_title()),
+        Span::styled(hint.to_string(), Theme::style_muted()),
+    ]))
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Theme::style_border_focus()),
+    );
+
+    f.render_widget(prompt, area);
+}
+
 fn render_status_bar(f: &mut Frame, area: Rect, message: Option<&str>, loading: bool) {
     let status = if loading {
         "Loading...".to_string()
     } else {
         message
-            .unwrap_or("jk:Navigate Enter:Open c:Comments o:Browser ?:Help q:Quit")
+            .unwrap_or(
+                "jk:Navigate Enter:Open c:Comments o:Browser /:Search f:Filter O:Sort H:Group S:Sentiment T:Ticker ?:Help q:Quit",
+            )
             .to_string()
     };
 
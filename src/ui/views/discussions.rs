@@ -0,0 +1,142 @@
+//! Discussions view
+//!
+//! Lists the Hacker News stories and Reddit posts found for a link
+
+use crate::models::FeedItem;
+use crate::ui::ProviderColors;
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph},
+    Frame,
+};
+
+/// Render the discussions found for `url`, most discussed first
+pub fn render(
+    f: &mut Frame,
+    url: &str,
+    discussions: &[FeedItem],
+    selected_idx: usize,
+    status_message: Option<&str>,
+) {
+    let size = f.size();
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Header
+            Constraint::Min(5),    // Discussions
+            Constraint::Length(2), // Help bar
+        ])
+        .split(size);
+
+    let header = Paragraph::new(Line::from(vec![
+        Span::styled(
+            format!(" 💬 Discussions ({}) ", discussions.len()),
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(url.to_string(), Style::default().fg(Color::DarkGray)),
+    ]))
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan)),
+    );
+    f.render_widget(header, chunks[0]);
+
+    render_list(f, chunks[1], discussions, selected_idx);
+
+    let mut help = vec![
+        Span::styled("j/k", Style::default().fg(Color::Yellow)),
+        Span::raw(":Navigate "),
+        Span::styled("Enter", Style::default().fg(Color::Yellow)),
+        Span::raw(":Comments "),
+        Span::styled("o", Style::default().fg(Color::Yellow)),
+        Span::raw(":Browser "),
+        Span::styled("Esc", Style::default().fg(Color::Yellow)),
+        Span::raw(":Back"),
+    ];
+    if let Some(message) = status_message {
+        help.push(Span::styled(
+            format!("  {}", message),
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+    let help = Paragraph::new(Line::from(help)).block(
+        Block::default()
+            .borders(Borders::TOP)
+            .border_style(Style::default().fg(Color::DarkGray)),
+    );
+    f.render_widget(help, chunks[2]);
+}
+
+fn render_list(f: &mut Frame, area: Rect, discussions: &[FeedItem], selected_idx: usize) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::DarkGray));
+
+    if discussions.is_empty() {
+        let empty = Paragraph::new(Span::styled(
+            "Nobody has submitted this link to Hacker News or Reddit yet.",
+            Style::default().fg(Color::DarkGray),
+        ))
+        .block(block);
+        f.render_widget(empty, area);
+        return;
+    }
+
+    // Two lines per entry; keep the selection on screen
+    let visible = (area.height.saturating_sub(2) as usize / 2).max(1);
+    let skip = selected_idx.saturating_sub(visible - 1);
+
+    let items: Vec<ListItem> = discussions
+        .iter()
+        .enumerate()
+        .skip(skip)
+        .take(visible)
+        .map(|(idx, item)| render_entry(item, idx == selected_idx))
+        .collect();
+
+    f.render_widget(List::new(items).block(block), area);
+}
+
+fn render_entry(item: &FeedItem, is_selected: bool) -> ListItem<'static> {
+    let title_style = if is_selected {
+        Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD)
+    } else {
+        Style::default()
+    };
+
+    let mut header = vec![Span::styled(
+        format!("{} ", item.source),
+        Style::default().fg(ProviderColors::for_provider(&item.provider_id)),
+    )];
+    if let Some(score) = item.score_display() {
+        header.push(Span::styled(
+            format!("{} ", score),
+            Style::default().fg(Color::Green),
+        ));
+    }
+    if let Some(comments) = item.comments_display() {
+        header.push(Span::styled(
+            format!("{} ", comments),
+            Style::default().fg(Color::Cyan),
+        ));
+    }
+    header.push(Span::styled(
+        item.time_ago(),
+        Style::default().fg(Color::DarkGray),
+    ));
+
+    let title = Line::from(vec![
+        Span::styled(if is_selected { "> " } else { "  " }, title_style),
+        Span::styled(item.title.clone(), title_style),
+    ]);
+
+    ListItem::new(vec![Line::from(header), title])
+}
@@ -54,7 +54,8 @@ fn render_header(f: &mut Frame, area: Rect, name: &str, icon: &str, count: usize
     f.render_widget(header, area);
 }
 
-fn render_items(f: &mut Frame, area: Rect, items: &[FeedItem], selected_idx: usize) {
+/// Render a list of items with score, comment count and age
+pub fn render_items(f: &mut Frame, area: Rect, items: &[FeedItem], selected_idx: usize) {
     let visible_height = area.height.saturating_sub(2) as usize;
 
     // Calculate scroll offset
@@ -1,6 +1,10 @@
 pub mod article;
 pub mod comments;
 pub mod dashboard;
+pub mod discussions;
 pub mod feed;
 pub mod help;
 pub mod landing;
+pub mod notifications;
+pub mod reading_queue;
+pub mod user;
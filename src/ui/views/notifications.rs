@@ -0,0 +1,128 @@
+//! Notification log view
+//!
+//! Lists the items that raised a notification, when and why
+
+use crate::notifications::Notification;
+use crate::ui::ProviderColors;
+use chrono::Local;
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph},
+    Frame,
+};
+use std::collections::VecDeque;
+
+/// Render the notification log, most recent first
+pub fn render(f: &mut Frame, log: &VecDeque<Notification>, selected_idx: usize) {
+    let size = f.size();
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Header
+            Constraint::Min(5),    // Log
+            Constraint::Length(2), // Help bar
+        ])
+        .split(size);
+
+    let header = Paragraph::new(Line::from(vec![Span::styled(
+        format!(" 🔔 Notifications ({}) ", log.len()),
+        Style::default()
+            .fg(Color::Cyan)
+            .add_modifier(Modifier::BOLD),
+    )]))
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan)),
+    );
+    f.render_widget(header, chunks[0]);
+
+    render_log(f, chunks[1], log, selected_idx);
+
+    let help = Paragraph::new(Line::from(vec![
+        Span::styled("j/k", Style::default().fg(Color::Yellow)),
+        Span::raw(":Navigate "),
+        Span::styled("Enter", Style::default().fg(Color::Yellow)),
+        Span::raw(":Open "),
+        Span::styled("o", Style::default().fg(Color::Yellow)),
+        Span::raw(":Browser "),
+        Span::styled("Esc", Style::default().fg(Color::Yellow)),
+        Span::raw(":Back"),
+    ]))
+    .block(
+        Block::default()
+            .borders(Borders::TOP)
+            .border_style(Style::default().fg(Color::DarkGray)),
+    );
+    f.render_widget(help, chunks[2]);
+}
+
+fn render_log(f: &mut Frame, area: Rect, log: &VecDeque<Notification>, selected_idx: usize) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::DarkGray));
+
+    if log.is_empty() {
+        let empty = Paragraph::new(Span::styled(
+            "Nothing has fired yet. Notifications come from notify rules and [notifications].",
+            Style::default().fg(Color::DarkGray),
+        ))
+        .block(block);
+        f.render_widget(empty, area);
+        return;
+    }
+
+    // Two lines per entry; keep the selection on screen
+    let visible = (area.height.saturating_sub(2) as usize / 2).max(1);
+    let skip = selected_idx.saturating_sub(visible - 1);
+
+    let items: Vec<ListItem> = log
+        .iter()
+        .enumerate()
+        .skip(skip)
+        .take(visible)
+        .map(|(idx, notification)| render_entry(notification, idx == selected_idx))
+        .collect();
+
+    f.render_widget(List::new(items).block(block), area);
+}
+
+fn render_entry(notification: &Notification, is_selected: bool) -> ListItem<'static> {
+    let item = &notification.item;
+    let title_style = if is_selected {
+        Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD)
+    } else {
+        Style::default()
+    };
+
+    let header = Line::from(vec![
+        Span::styled(
+            notification
+                .at
+                .with_timezone(&Local)
+                .format("%a %H:%M ")
+                .to_string(),
+            Style::default().fg(Color::DarkGray),
+        ),
+        Span::styled(
+            format!("[{}] ", notification.reason),
+            Style::default().fg(Color::Magenta),
+        ),
+        Span::styled(
+            item.source.clone(),
+            Style::default().fg(ProviderColors::for_provider(&item.provider_id)),
+        ),
+    ]);
+
+    let title = Line::from(vec![
+        Span::styled(if is_selected { "> " } else { "  " }, title_style),
+        Span::styled(item.title.clone(), title_style),
+    ]);
+
+    ListItem::new(vec![header, title])
+}
@@ -0,0 +1,164 @@
+//! Reading queue view
+//!
+//! Lists queued arXiv papers grouped by reading state
+
+use crate::papers::{QueueEntry, ReadingState};
+use crate::ui::ProviderColors;
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph},
+    Frame,
+};
+
+/// Render the queue: to read, then reading, then done
+pub fn render(
+    f: &mut Frame,
+    entries: &[QueueEntry],
+    selected_idx: usize,
+    status_message: Option<&str>,
+) {
+    let size = f.size();
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Header
+            Constraint::Min(5),    // Entries
+            Constraint::Length(2), // Help bar
+        ])
+        .split(size);
+
+    let count = |state: ReadingState| entries.iter().filter(|e| e.state == state).count();
+    let header = Paragraph::new(Line::from(vec![
+        Span::styled(
+            format!(" 📚 Reading queue ({}) ", entries.len()),
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            format!(
+                "{} to read · {} reading · {} done",
+                count(ReadingState::ToRead),
+                count(ReadingState::Reading),
+                count(ReadingState::Done)
+            ),
+            Style::default().fg(Color::DarkGray),
+        ),
+    ]))
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan)),
+    );
+    f.render_widget(header, chunks[0]);
+
+    render_list(f, chunks[1], entries, selected_idx);
+
+    let mut help = vec![
+        Span::styled("j/k", Style::default().fg(Color::Yellow)),
+        Span::raw(":Navigate "),
+        Span::styled("Enter", Style::default().fg(Color::Yellow)),
+        Span::raw(":Open "),
+        Span::styled("w", Style::default().fg(Color::Yellow)),
+        Span::raw(":Next state "),
+        Span::styled("x", Style::default().fg(Color::Yellow)),
+        Span::raw(":Remove "),
+        Span::styled("p", Style::default().fg(Color::Yellow)),
+        Span::raw(":PDF "),
+        Span::styled("o", Style::default().fg(Color::Yellow)),
+        Span::raw(":Browser "),
+        Span::styled("Esc", Style::default().fg(Color::Yellow)),
+        Span::raw(":Back"),
+    ];
+    if let Some(message) = status_message {
+        help.push(Span::styled(
+            format!("  {}", message),
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+    let help = Paragraph::new(Line::from(help)).block(
+        Block::default()
+            .borders(Borders::TOP)
+            .border_style(Style::default().fg(Color::DarkGray)),
+    );
+    f.render_widget(help, chunks[2]);
+}
+
+fn render_list(f: &mut Frame, area: Rect, entries: &[QueueEntry], selected_idx: usize) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::DarkGray));
+
+    if entries.is_empty() {
+        let empty = Paragraph::new(Span::styled(
+            "No papers queued. Press w on an arXiv paper to add it.",
+            Style::default().fg(Color::DarkGray),
+        ))
+        .block(block);
+        f.render_widget(empty, area);
+        return;
+    }
+
+    // Two lines per entry; keep the selection on screen
+    let visible = (area.height.saturating_sub(2) as usize / 2).max(1);
+    let skip = selected_idx.saturating_sub(visible - 1);
+
+    let items: Vec<ListItem> = entries
+        .iter()
+        .enumerate()
+        .skip(skip)
+        .take(visible)
+        .map(|(idx, entry)| render_entry(entry, idx == selected_idx))
+        .collect();
+
+    f.render_widget(List::new(items).block(block), area);
+}
+
+fn render_entry(entry: &QueueEntry, is_selected: bool) -> ListItem<'static> {
+    let title_style = if is_selected {
+        Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD)
+    } else if entry.state == ReadingState::Done {
+        Style::default().fg(Color::DarkGray)
+    } else {
+        Style::default()
+    };
+    let badge_color = match entry.state {
+        ReadingState::ToRead => Color::Yellow,
+        ReadingState::Reading => Color::Cyan,
+        ReadingState::Done => Color::Green,
+    };
+
+    let item = &entry.item;
+    let mut header = vec![
+        Span::styled(
+            format!("[{}] ", entry.state.as_str()),
+            Style::default().fg(badge_color),
+        ),
+        Span::styled(
+            format!("{} ", item.source),
+            Style::default().fg(ProviderColors::for_provider(&item.provider_id)),
+        ),
+    ];
+    if let Some(author) = &item.author {
+        header.push(Span::styled(
+            format!("{} ", author),
+            Style::default().fg(Color::Gray),
+        ));
+    }
+    header.push(Span::styled(
+        format!("queued {}", entry.added_at.format("%Y-%m-%d")),
+        Style::default().fg(Color::DarkGray),
+    ));
+
+    let title = Line::from(vec![
+        Span::styled(if is_selected { "> " } else { "  " }, title_style),
+        Span::styled(item.title.clone(), title_style),
+    ]);
+
+    ListItem::new(vec![Line::from(header), title])
+}
@@ -0,0 +1,183 @@
+//! User profile view
+//!
+//! Shows a Hacker News user's karma, age and about text, with their recent
+//! submissions and comments in the feed and comment list styles
+
+use crate::providers::HnProfile;
+use crate::ui::views::{comments, feed};
+use crate::utils::wrap_text;
+use chrono::DateTime;
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+use std::collections::HashSet;
+
+/// Lines of the about text shown above the lists
+const ABOUT_LINES: usize = 4;
+
+/// Render a profile; `show_comments` picks the comments tab
+pub fn render(
+    f: &mut Frame,
+    profile: &HnProfile,
+    show_comments: bool,
+    selected_idx: usize,
+    status_message: Option<&str>,
+) {
+    let size = f.size();
+    let about = about_lines(profile, size.width.saturating_sub(4) as usize);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),                      // Header
+            Constraint::Length(about.len() as u16 + 1), // About
+            Constraint::Length(1),                      // Tabs
+            Constraint::Min(5),                         // Submissions or comments
+            Constraint::Length(2),                      // Help bar
+        ])
+        .split(size);
+
+    render_header(f, chunks[0], profile);
+
+    let about = Paragraph::new(about).block(
+        Block::default()
+            .borders(Borders::BOTTOM)
+            .border_style(Style::default().fg(Color::DarkGray)),
+    );
+    f.render_widget(about, chunks[1]);
+
+    render_tabs(f, chunks[2], profile, show_comments);
+
+    if show_comments {
+        comments::render_comments(
+            f,
+            chunks[3],
+            &profile.comments,
+            &HashSet::new(),
+            selected_idx,
+            0,
+        );
+    } else {
+        feed::render_items(f, chunks[3], &profile.submissions, selected_idx);
+    }
+
+    render_help(f, chunks[4], status_message);
+}
+
+fn render_header(f: &mut Frame, area: Rect, profile: &HnProfile) {
+    let user = &profile.user;
+    let joined = DateTime::from_timestamp(user.created, 0)
+        .map(|at| at.format("joined %b %Y").to_string())
+        .unwrap_or_default();
+
+    let header = Paragraph::new(Line::from(vec![
+        Span::styled(
+            format!(" 👤 {} ", user.id),
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            format!("{} karma", user.karma),
+            Style::default().fg(Color::Green),
+        ),
+        Span::raw(" | "),
+        Span::styled(joined, Style::default().fg(Color::DarkGray)),
+        Span::raw(" | "),
+        Span::styled(
+            format!("{} submitted", user.submitted.len()),
+            Style::default().fg(Color::DarkGray),
+        ),
+    ]))
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan)),
+    );
+    f.render_widget(header, area);
+}
+
+/// The about text as plain, wrapped lines
+fn about_lines(profile: &HnProfile, width: usize) -> Vec<Line<'static>> {
+    let about = profile
+        .user
+        .about
+        .as_deref()
+        .map(|html| html2text::from_read(html.as_bytes(), width.max(20)))
+        .unwrap_or_default();
+    let mut lines: Vec<Line> = about
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .flat_map(|l| wrap_text(l, width.max(20)))
+        .take(ABOUT_LINES)
+        .map(|l| Line::from(format!(" {}", l)))
+        .collect();
+    if lines.is_empty() {
+        lines.push(Line::from(Span::styled(
+            " No about text",
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+    lines
+}
+
+fn render_tabs(f: &mut Frame, area: Rect, profile: &HnProfile, show_comments: bool) {
+    let tab = |label: String, active: bool| {
+        if active {
+            Span::styled(
+                label,
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+            )
+        } else {
+            Span::styled(label, Style::default().fg(Color::DarkGray))
+        }
+    };
+
+    let tabs = Paragraph::new(Line::from(vec![
+        Span::raw(" "),
+        tab(
+            format!("Submissions ({})", profile.submissions.len()),
+            !show_comments,
+        ),
+        Span::raw("  "),
+        tab(
+            format!("Comments ({})", profile.comments.len()),
+            show_comments,
+        ),
+    ]));
+    f.render_widget(tabs, area);
+}
+
+fn render_help(f: &mut Frame, area: Rect, status_message: Option<&str>) {
+    let mut help = vec![
+        Span::styled("j/k", Style::default().fg(Color::Yellow)),
+        Span::raw(":Navigate "),
+        Span::styled("Tab", Style::default().fg(Color::Yellow)),
+        Span::raw(":Submissions/Comments "),
+        Span::styled("Enter", Style::default().fg(Color::Yellow)),
+        Span::raw(":Open "),
+        Span::styled("o", Style::default().fg(Color::Yellow)),
+        Span::raw(":Browser "),
+        Span::styled("Esc", Style::default().fg(Color::Yellow)),
+        Span::raw(":Back"),
+    ];
+    if let Some(message) = status_message {
+        help.push(Span::styled(
+            format!("  {}", message),
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+
+    let help = Paragraph::new(Line::from(help)).block(
+        Block::default()
+            .borders(Borders::TOP)
+            .border_style(Style::default().fg(Color::DarkGray)),
+    );
+    f.render_widget(help, area);
+}
@@ -0,0 +1,67 @@
+//! Clipboard
+//!
+//! Copies text with the OSC 52 escape, which the terminal turns into a
+//! clipboard write. It needs no clipboard tool and works over SSH; inside
+//! tmux the sequence is passed through (tmux needs `set-clipboard on`).
+
+use crate::utils::terminal;
+use std::io::{self, Write};
+
+const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Escape sequence that puts `text` on the system clipboard
+pub fn osc52(text: &str, tmux: bool) -> String {
+    let sequence = format!("\x1b]52;c;{}\x07", base64(text.as_bytes()));
+    if tmux {
+        terminal::tmux_passthrough(&sequence)
+    } else {
+        sequence
+    }
+}
+
+/// Copy `text` by writing the OSC 52 escape straight to the terminal
+pub fn copy(text: &str) -> io::Result<()> {
+    let escape = osc52(text, terminal::in_tmux());
+    // Written around ratatui: the escape doesn't move the cursor
+    let mut stdout = io::stdout();
+    stdout.write_all(escape.as_bytes())?;
+    stdout.flush()
+}
+
+/// Standard padded base64
+fn base64(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(BASE64[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_osc52() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foo"), "Zm9v");
+        assert_eq!(base64(b"@misc{x}\n"), "QG1pc2N7eH0K");
+
+        assert_eq!(osc52("hi", false), "\x1b]52;c;aGk=\x07");
+        assert_eq!(osc52("hi", true), "\x1bPtmux;\x1b\x1b]52;c;aGk=\x07\x1b\\");
+    }
+}
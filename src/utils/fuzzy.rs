@@ -0,0 +1,168 @@
+//! Fuzzy matching
+//!
+//! fzf-style subsequence matching used by the local list filter. Query
+//! characters must appear in order; contiguous runs and matches at word
+//! starts score higher. Matching is case-insensitive unless the query
+//! contains an uppercase letter.
+
+use crate::models::FeedItem;
+
+const SCORE_MATCH: i64 = 16;
+const BONUS_CONSECUTIVE: i64 = 12;
+const BONUS_WORD_START: i64 = 10;
+const BONUS_FIRST_CHAR: i64 = 8;
+const PENALTY_GAP: i64 = 1;
+
+/// A successful match with character positions in the matched text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FuzzyMatch {
+    pub score: i64,
+    pub positions: Vec<usize>,
+}
+
+/// Match `pattern` as a subsequence of `text`
+pub fn fuzzy_match(pattern: &str, text: &str) -> Option<FuzzyMatch> {
+    if pattern.is_empty() {
+        return Some(FuzzyMatch {
+            score: 0,
+            positions: Vec::new(),
+        });
+    }
+
+    let case_sensitive = pattern.chars().any(|c| c.is_uppercase());
+    let fold = |c: char| {
+        if case_sensitive {
+            c
+        } else {
+            c.to_lowercase().next().unwrap_or(c)
+        }
+    };
+
+    let pattern: Vec<char> = pattern.chars().map(fold).collect();
+    let text: Vec<char> = text.chars().collect();
+
+    // Find where the match ends scanning forward, then walk back from there to
+    // get the tightest window ending at that position (same trick fzf v1 uses)
+    let mut p = 0;
+    let mut end = None;
+    for (idx, &c) in text.iter().enumerate() {
+        if fold(c) == pattern[p] {
+            p += 1;
+            if p == pattern.len() {
+                end = Some(idx);
+                break;
+            }
+        }
+    }
+    let end = end?;
+
+    let mut positions = Vec::with_capacity(pattern.len());
+    let mut p = pattern.len();
+    for idx in (0..=end).rev() {
+        if p > 0 && fold(text[idx]) == pattern[p - 1] {
+            p -= 1;
+            positions.push(idx);
+        }
+    }
+    positions.reverse();
+
+    let mut score = 0;
+    for (n, &pos) in positions.iter().enumerate() {
+        score += SCORE_MATCH;
+        if pos == 0 {
+            score += BONUS_FIRST_CHAR;
+        }
+        if pos == 0 || !text[pos - 1].is_alphanumeric() {
+            score += BONUS_WORD_START;
+        }
+        if n > 0 {
+            let gap = pos - positions[n - 1] - 1;
+            if gap == 0 {
+                score += BONUS_CONSECUTIVE;
+            } else {
+                score -= PENALTY_GAP * gap as i64;
+            }
+        }
+    }
+
+    Some(FuzzyMatch { score, positions })
+}
+
+/// Score an item against a whitespace-separated query
+///
+/// Every term must match the title, source, author or one of the tags;
+/// each term takes its best field. Title matches are preferred.
+pub fn match_item(query: &str, item: &FeedItem) -> Option<i64> {
+    let mut total = 0;
+
+    for term in query.split_whitespace() {
+        let title = fuzzy_match(term, &item.title).map(|m| m.score + SCORE_MATCH);
+        let others = std::iter::once(item.source.as_str())
+            .chain(item.author.as_deref())
+            .chain(item.metadata.tags.iter().map(|t| t.as_str()))
+            .filter_map(|field| fuzzy_match(term, field).map(|m| m.score));
+
+        total += title.into_iter().chain(others).max()?;
+    }
+
+    Some(total)
+}
+
+/// Character positions in `title` to highlight for a query
+pub fn title_positions(query: &str, title: &str) -> Vec<usize> {
+    let mut positions: Vec<usize> = query
+        .split_whitespace()
+        .filter_map(|term| fuzzy_match(term, title))
+        .flat_map(|m| m.positions)
+        .collect();
+    positions.sort_unstable();
+    positions.dedup();
+    positions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    #[test]
+    fn test_subsequence_and_positions() {
+        let m = fuzzy_match("nvda", "NVIDIA data center").unwrap();
+        assert_eq!(m.positions, vec![0, 1, 3, 5]);
+        assert!(fuzzy_match("xyz", "NVIDIA").is_none());
+    }
+
+    #[test]
+    fn test_smart_case() {
+        assert!(fuzzy_match("rust", "Rust 2024").is_some());
+        assert!(fuzzy_match("Rust", "rust 2024").is_none());
+    }
+
+    #[test]
+    fn test_prefers_tight_word_start_matches() {
+        let tight = fuzzy_match("fed", "Fed holds rates").unwrap();
+        let loose = fuzzy_match("fed", "for every dollar").unwrap();
+        assert!(tight.score > loose.score);
+    }
+
+    #[test]
+    fn test_match_item_fields() {
+        let mut item = FeedItem::new(
+            "1".to_string(),
+            "reddit".to_string(),
+            "Earnings call recap".to_string(),
+            "r/stocks".to_string(),
+            Utc::now(),
+        )
+        .with_author("marketwatcher".to_string());
+        item.metadata.tags = vec!["AAPL".to_string()];
+
+        assert!(match_item("earn aapl", &item).is_some());
+        assert!(match_item("stocks watcher", &item).is_some());
+        assert!(match_item("earn tsla", &item).is_none());
+        assert_eq!(
+            title_positions("rec", "Earnings call recap"),
+            vec![14, 15, 16]
+        );
+    }
+}
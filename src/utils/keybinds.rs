@@ -27,9 +27,28 @@ pub enum Action {
     NextResult,
     PrevResult,
     SwitchTab,
+    PrevTab,
     Refresh,
     NextArticle,
     PrevArticle,
+    CycleSentimentFilter,
+    FilterTicker,
+    FuzzyFilter,
+    CycleSort,
+    CycleGroup,
+    TimeTravel,
+    NotificationLog,
+    PickSubreddit,
+    OpenMedia,
+    SearchHn,
+    FindDiscussions,
+    ViewUser,
+    DownloadPdf,
+    CopyBibtex,
+    AppendBibtex,
+    QueuePaper,
+    ReadingQueue,
+    Remove,
     None,
 }
 
@@ -51,7 +70,7 @@ pub fn map_key_event(event: KeyEvent, vim_mode: bool) -> Action {
 
         // Tab switching
         (KeyCode::Tab, KeyModifiers::NONE) => Action::SwitchTab,
-        (KeyCode::BackTab, KeyModifiers::SHIFT) => Action::SwitchTab, // Shift+Tab
+        (KeyCode::BackTab, KeyModifiers::SHIFT) => Action::PrevTab, // Shift+Tab
 
         // macOS-style Back navigation (Cmd+[ or Cmd+Left or Backspace or Esc)
         (KeyCode::Esc, KeyModifiers::NONE) => Action::Back,
@@ -106,9 +125,35 @@ pub fn map_key_event(event: KeyEvent, vim_mode: bool) -> Action {
         (KeyCode::Char('o'), KeyModifiers::NONE) => Action::OpenInBrowser,
         (KeyCode::Char('o'), KeyModifiers::SUPER) => Action::OpenInBrowser, // Cmd+O
         (KeyCode::Char('c'), KeyModifiers::NONE) => Action::ViewComments,
+        (KeyCode::Char('m'), KeyModifiers::NONE) => Action::OpenMedia,
+        (KeyCode::Char('D'), KeyModifiers::SHIFT) => Action::FindDiscussions,
+        (KeyCode::Char('U'), KeyModifiers::SHIFT) => Action::ViewUser,
         (KeyCode::Char('s'), KeyModifiers::NONE) => Action::SaveBookmark,
         (KeyCode::Char('y'), KeyModifiers::NONE) => Action::CopyUrl,
         (KeyCode::Char('c'), KeyModifiers::SUPER) => Action::CopyUrl, // Cmd+C copies URL
+        (KeyCode::Char('x'), KeyModifiers::NONE) => Action::Remove,
+        (KeyCode::Delete, KeyModifiers::NONE) => Action::Remove,
+
+        // arXiv papers
+        (KeyCode::Char('p'), KeyModifiers::NONE) => Action::DownloadPdf,
+        (KeyCode::Char('b'), KeyModifiers::NONE) => Action::CopyBibtex,
+        (KeyCode::Char('B'), KeyModifiers::SHIFT) => Action::AppendBibtex,
+        (KeyCode::Char('w'), KeyModifiers::NONE) => Action::QueuePaper,
+        (KeyCode::Char('W'), KeyModifiers::SHIFT) => Action::ReadingQueue,
+
+        // List filters
+        (KeyCode::Char('S'), KeyModifiers::SHIFT) => Action::CycleSentimentFilter,
+        (KeyCode::Char('T'), KeyModifiers::SHIFT) => Action::FilterTicker,
+        (KeyCode::Char('f'), KeyModifiers::NONE) => Action::FuzzyFilter,
+        (KeyCode::Char('f'), KeyModifiers::CONTROL) => Action::FuzzyFilter,
+        (KeyCode::Char('F'), KeyModifiers::SHIFT) => Action::SearchHn,
+
+        // List ordering
+        (KeyCode::Char('O'), KeyModifiers::SHIFT) => Action::CycleSort,
+        (KeyCode::Char('H'), KeyModifiers::SHIFT) => Action::CycleGroup,
+        (KeyCode::Char('A'), KeyModifiers::SHIFT) => Action::TimeTravel,
+        (KeyCode::Char('L'), KeyModifiers::SHIFT) => Action::NotificationLog,
+        (KeyCode::Char('R'), KeyModifiers::SHIFT) => Action::PickSubreddit,
 
         // Search navigation
         (KeyCode::Char('n'), KeyModifiers::NONE) => Action::NextResult,
@@ -126,7 +171,7 @@ pub fn get_help_text(vim_mode: bool) -> Vec<(&'static str, &'static str)> {
         ("q / Cmd+Q", "Quit application"),
         ("? / F1", "Show help"),
         ("/ / Cmd+F", "Search"),
-        ("Tab", "Switch panes"),
+        ("Tab / Shift+Tab", "Next/previous tab (Reddit subreddits)"),
         ("r / Cmd+R / F5", "Refresh"),
         ("", ""),
         ("Navigation", ""),
@@ -135,6 +180,25 @@ pub fn get_help_text(vim_mode: bool) -> Vec<(&'static str, &'static str)> {
         ("Enter", "Open article"),
         ("[ / ]", "Prev/Next article"),
         ("Home/End", "Go to top/bottom"),
+        ("S", "Filter by sentiment"),
+        ("T", "Filter by ticker"),
+        ("f / Ctrl+F", "Fuzzy filter list"),
+        (
+            "F",
+            "Search Hacker News (by:, type:, points:, since:, sort:date)",
+        ),
+        (
+            "F (arXiv feed)",
+            "Search arXiv (au:, ti:, abs:, cat:, sort:date)",
+        ),
+        ("O", "Cycle sort order"),
+        ("H", "Group by provider/day"),
+        ("A", "Browse archive as of a time (←/→ step)"),
+        ("L", "Notification log"),
+        (
+            "R",
+            "Reddit: pick, add (+name) or remove (-name) a subreddit",
+        ),
         ("", ""),
         ("Back/Forward", ""),
         ("Esc / ⌫", "Go back"),
@@ -144,9 +208,26 @@ pub fn get_help_text(vim_mode: bool) -> Vec<(&'static str, &'static str)> {
         ("Article View", ""),
         ("o / Cmd+O", "Open in browser"),
         ("c", "View comments"),
+        ("D", "Find HN/Reddit discussions of the link"),
+        ("U", "HN profile of the author (or selected commenter)"),
+        ("m", "Open image/video (again: next gallery image)"),
+        ("s", "Bookmark / unbookmark"),
         ("Cmd+C / y", "Copy URL"),
         ("PgUp/PgDn", "Scroll page"),
         ("", ""),
+        ("arXiv Papers", ""),
+        ("p", "Download the PDF to the papers directory"),
+        ("b", "Copy a BibTeX entry"),
+        ("B", "Append the BibTeX entry to the .bib file"),
+        (
+            "w",
+            "Add to reading queue / next state (to read, reading, done)",
+        ),
+        ("W", "Reading queue (x: remove)"),
+        ("", ""),
+        ("Comments", ""),
+        ("n / N", "Next/previous comment new since last visit"),
+        ("", ""),
         ("Search", ""),
         ("n / Cmd+G", "Next result"),
         ("N", "Previous result"),
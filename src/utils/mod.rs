@@ -1,5 +1,8 @@
+pub mod clipboard;
+pub mod fuzzy;
 pub mod keybinds;
 pub mod parser;
+pub mod terminal;
 
 pub use keybinds::*;
 pub use parser::*;
@@ -1,3 +1,17 @@
+use chrono::{DateTime, Duration, Local, NaiveDate, NaiveTime, Utc};
+use ratatui::style::Color;
+use regex::Regex;
+use std::sync::OnceLock;
+use url::Url;
+
+/// Query parameters that only track where a click came from
+const TRACKING_PARAMS: &[&str] = &["fbclid", "gclid", "ref", "ref_src", "source"];
+
+/// Parse a colour name or hex code ("yellow", "lightblue", "#ff8800")
+pub fn parse_color(name: &str) -> Option<Color> {
+    name.trim().parse().ok()
+}
+
 /// Parse HTML content and extract clean, readable text  
 pub fn extract_readable_text(html: &str) -> String {
     html2text::from_read(html.as_bytes(), 80)
@@ -12,6 +26,180 @@ pub fn clean_text(text: &str) -> String {
         .join("\n")
 }
 
+/// Convert Markdown (as written in Reddit self-posts) to plain text
+///
+/// Keeps paragraphs, lists, quotes and code blocks readable; links become
+/// `text (url)` and emphasis markers are dropped.
+pub fn markdown_to_text(markdown: &str) -> String {
+    let mut lines: Vec<String> = Vec::new();
+    let mut in_fence = false;
+
+    for line in markdown.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            lines.push(format!("    {}", line.trim_end()));
+            continue;
+        }
+
+        if is_rule(trimmed) {
+            lines.push("────────".to_string());
+            continue;
+        }
+
+        // Table separator row
+        if trimmed.starts_with('|') && trimmed.chars().all(|c| matches!(c, '|' | '-' | ':' | ' ')) {
+            continue;
+        }
+
+        let indent = " ".repeat((line.len() - line.trim_start().len()).min(8));
+        let mut rest = trimmed;
+        let mut prefix = String::new();
+        while let Some(quoted) = rest.strip_prefix('>') {
+            prefix.push_str("│ ");
+            rest = quoted.trim_start();
+        }
+
+        let text = if let Some(heading) = strip_heading(rest) {
+            inline_markdown(heading)
+        } else if let Some(item) = ["* ", "- ", "+ "]
+            .iter()
+            .find_map(|bullet| rest.strip_prefix(bullet))
+        {
+            format!("• {}", inline_markdown(item))
+        } else if rest.starts_with('|') {
+            rest.trim_matches('|')
+                .split('|')
+                .map(|cell| inline_markdown(cell.trim()))
+                .collect::<Vec<_>>()
+                .join(" | ")
+        } else {
+            inline_markdown(rest)
+        };
+
+        if text.is_empty() && prefix.is_empty() {
+            lines.push(String::new());
+        } else {
+            lines.push(format!("{}{}{}", indent, prefix, text));
+        }
+    }
+
+    // Collapse runs of blank lines left by removed markup
+    let mut out: Vec<String> = Vec::new();
+    for line in lines {
+        if line.is_empty() && out.last().map_or("", String::as_str).is_empty() {
+            continue;
+        }
+        out.push(line);
+    }
+    while out.last().is_some_and(String::is_empty) {
+        out.pop();
+    }
+    out.join("\n")
+}
+
+/// `---`, `***` or `___`, optionally spaced
+fn is_rule(line: &str) -> bool {
+    let marks: Vec<char> = line.chars().filter(|c| !c.is_whitespace()).collect();
+    marks.len() >= 3 && matches!(marks[0], '-' | '*' | '_') && marks.iter().all(|&c| c == marks[0])
+}
+
+/// Heading text without its leading `#` marks
+fn strip_heading(line: &str) -> Option<&str> {
+    let hashes = line.chars().take_while(|&c| c == '#').count();
+    if (1..=6).contains(&hashes) {
+        let rest = &line[hashes..];
+        if rest.is_empty() || rest.starts_with(' ') {
+            return Some(rest.trim().trim_end_matches('#').trim_end());
+        }
+    }
+    None
+}
+
+/// Strip inline Markdown: links, emphasis, code spans, escapes and entities
+fn inline_markdown(text: &str) -> String {
+    static LINK: OnceLock<Regex> = OnceLock::new();
+    static EMPHASIS: OnceLock<Regex> = OnceLock::new();
+    let link = LINK.get_or_init(|| {
+        Regex::new(r"!?\[([^\]]*)\]\(([^)\s]+)(?:\s+[^)]*)?\)").expect("valid link pattern")
+    });
+    let emphasis = EMPHASIS.get_or_init(|| {
+        Regex::new(
+            r"\*\*(\S(?:.*?\S)?)\*\*|~~(\S(?:.*?\S)?)~~|\*(\S(?:.*?\S)?)\*|`([^`]+)`|\b__(\S(?:.*?\S)?)__\b",
+        )
+        .expect("valid emphasis pattern")
+    });
+
+    // Park escaped punctuation in the private use area so it survives as-is
+    const ESCAPED: u32 = 0xF0000;
+    let mut unescaped = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match chars.peek() {
+            Some(&next) if c == '\\' && next.is_ascii_punctuation() => {
+                unescaped.extend(char::from_u32(ESCAPED + next as u32));
+                chars.next();
+            }
+            _ => unescaped.push(c),
+        }
+    }
+
+    let text = link.replace_all(&unescaped, |caps: &regex::Captures| {
+        let label = &caps[1];
+        let url = &caps[2];
+        if label.is_empty() || label == url {
+            url.to_string()
+        } else {
+            format!("{} ({})", label, url)
+        }
+    });
+    let text = emphasis.replace_all(&text, |caps: &regex::Captures| {
+        caps.iter()
+            .skip(1)
+            .flatten()
+            .map(|m| m.as_str().to_string())
+            .next()
+            .unwrap_or_default()
+    });
+    let out: String = text
+        .chars()
+        .map(|c| match (c as u32).checked_sub(ESCAPED) {
+            Some(offset) if offset < 0x80 => char::from_u32(offset).unwrap_or(c),
+            _ => c,
+        })
+        .collect();
+
+    out.replace("&#x200B;", "")
+        .replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+        .trim()
+        .to_string()
+}
+
+/// Host and path without scheme, `www.`, fragment or tracking parameters
+pub fn normalize_url(raw: &str) -> Option<String> {
+    let url = Url::parse(raw).ok()?;
+    let host = url.host_str()?.trim_start_matches("www.").to_lowercase();
+
+    let params: Vec<String> = url
+        .query_pairs()
+        .filter(|(k, _)| !k.starts_with("utm_") && !TRACKING_PARAMS.contains(&k.as_ref()))
+        .map(|(k, v)| format!("{}={}", k, v))
+        .collect();
+
+    let mut key = format!("{}{}", host, url.path().trim_end_matches('/'));
+    if !params.is_empty() {
+        key.push('?');
+        key.push_str(&params.join("&"));
+    }
+    Some(key)
+}
+
 /// Truncate text to a maximum length with ellipsis (Unicode-safe)
 pub fn truncate(text: &str, max_len: usize) -> String {
     let char_count: usize = text.chars().count();
@@ -32,6 +220,66 @@ pub fn wrap_text(text: &str, width: usize) -> Vec<String> {
         .collect()
 }
 
+/// Parse a relative duration such as "30m", "24h", "7d" or "2w"
+pub fn parse_duration(text: &str) -> Option<Duration> {
+    let text = text.trim();
+    let split = text.find(|c: char| !c.is_ascii_digit())?;
+    let (amount, unit) = text.split_at(split);
+    let amount: i64 = amount.parse().ok()?;
+
+    match unit {
+        "m" | "min" => Some(Duration::minutes(amount)),
+        "h" => Some(Duration::hours(amount)),
+        "d" => Some(Duration::days(amount)),
+        "w" => Some(Duration::weeks(amount)),
+        _ => None,
+    }
+}
+
+/// Parse a point in time: a relative age ("7d" means 7 days before `now`)
+/// or a local calendar date ("2026-01-31", meaning its start)
+pub fn parse_since(text: &str, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    if let Some(duration) = parse_duration(text) {
+        return Some(now - duration);
+    }
+
+    let date = NaiveDate::parse_from_str(text.trim(), "%Y-%m-%d").ok()?;
+    date.and_hms_opt(0, 0, 0)?
+        .and_local_timezone(Local)
+        .earliest()
+        .map(|t| t.with_timezone(&Utc))
+}
+
+/// Parse a past moment for archive browsing: "18h" (ago), "09:00" (today),
+/// "yesterday 09:00", "2026-01-31" or "2026-01-31 09:00", in local time
+pub fn parse_as_of(text: &str, now: DateTime<Local>) -> Option<DateTime<Utc>> {
+    let text = text.trim().to_lowercase();
+    if let Some(duration) = parse_duration(&text) {
+        return Some((now - duration).with_timezone(&Utc));
+    }
+
+    let (day, time) = match text.split_once(' ') {
+        Some((day, time)) => (day, Some(time.trim())),
+        None if text.contains(':') => ("today", Some(text.as_str())),
+        None => (text.as_str(), None),
+    };
+
+    let date = match day {
+        "today" => now.date_naive(),
+        "yesterday" => now.date_naive().pred_opt()?,
+        _ => NaiveDate::parse_from_str(day, "%Y-%m-%d").ok()?,
+    };
+    let time = match time {
+        Some(time) => NaiveTime::parse_from_str(time, "%H:%M").ok()?,
+        None => NaiveTime::MIN,
+    };
+
+    date.and_time(time)
+        .and_local_timezone(Local)
+        .earliest()
+        .map(|t| t.with_timezone(&Utc))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -47,4 +295,40 @@ mod tests {
         let dirty = "  Hello  \n\n  World  \n  ";
         assert_eq!(clean_text(dirty), "Hello\nWorld");
     }
+
+    #[test]
+    fn test_markdown_to_text() {
+        let markdown = "## Earnings\n\n\n**Revenue** beat, see [the filing](https://sec.gov/x) \\*not* advice\n\n* first\n    - nested `code`\n> quoted ~~old~~ &amp; new\n\n---\n```\nlet x = 1;\n```\n&#x200B;\n";
+        assert_eq!(
+            markdown_to_text(markdown),
+            "Earnings\n\nRevenue beat, see the filing (https://sec.gov/x) *not* advice\n\n• first\n    • nested code\n│ quoted old & new\n\n────────\n    let x = 1;"
+        );
+    }
+
+    #[test]
+    fn test_parse_since() {
+        let now = Utc::now();
+        assert_eq!(parse_duration("24h"), Some(Duration::hours(24)));
+        assert_eq!(parse_since("7d", now), Some(now - Duration::days(7)));
+        assert!(parse_since("2026-01-31", now).is_some());
+        assert!(parse_since("soon", now).is_none());
+        assert!(parse_duration("h").is_none());
+    }
+
+    #[test]
+    fn test_parse_as_of() {
+        let now = Local::now();
+        let yesterday_nine = parse_as_of("yesterday 09:00", now).unwrap();
+        let local = yesterday_nine.with_timezone(&Local);
+        assert_eq!(local.date_naive(), now.date_naive().pred_opt().unwrap());
+        assert_eq!(local.time(), NaiveTime::from_hms_opt(9, 0, 0).unwrap());
+
+        assert_eq!(
+            parse_as_of("2h", now),
+            Some((now - Duration::hours(2)).with_timezone(&Utc))
+        );
+        assert!(parse_as_of("2026-01-31 17:30", now).is_some());
+        assert!(parse_as_of("07:15", now).is_some());
+        assert!(parse_as_of("last tuesday", now).is_none());
+    }
 }
@@ -0,0 +1,28 @@
+//! Terminal escape sequences
+//!
+//! tmux swallows OSC sequences it doesn't handle itself; wrapping them in a
+//! DCS passthrough hands them on to the outer terminal (tmux needs
+//! `allow-passthrough on`, or `set-clipboard on` for OSC 52).
+
+/// Check if running inside tmux
+pub fn in_tmux() -> bool {
+    std::env::var_os("TMUX").is_some()
+}
+
+/// Wrap an escape sequence in a tmux DCS passthrough
+pub fn tmux_passthrough(sequence: &str) -> String {
+    format!("\x1bPtmux;{}\x1b\\", sequence.replace('\x1b', "\x1b\x1b"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tmux_passthrough() {
+        assert_eq!(
+            tmux_passthrough("\x1b]9;hi\x07"),
+            "\x1bPtmux;\x1b\x1b]9;hi\x07\x1b\\"
+        );
+    }
+}
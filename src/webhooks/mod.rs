@@ -0,0 +1,449 @@
+//! Webhooks
+//!
+//! POSTs items matched by `[[rules]]` to configured URLs, from the TUI or the
+//! headless `finterm watch` loop. Each item is delivered once per webhook;
+//! delivered keys are kept in the cache database so restarts don't repeat
+//! them. Failed deliveries are retried with exponential backoff, and an item
+//! that still fails is forgotten so the next fetch tries it again.
+
+use crate::cache::CacheManager;
+use crate::config::{RuleConfig, WebhookConfig};
+use crate::models::FeedItem;
+use chrono::{Duration as ChronoDuration, Utc};
+use regex::{Captures, Regex};
+use reqwest::{Client, StatusCode};
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+use thiserror::Error;
+use tokio::task::JoinHandle;
+use url::Url;
+
+/// Tree in the cache database holding delivered `webhook\x1fprovider\x1fid` keys
+const SENT_TREE: &str = "webhooks:sent";
+
+/// How long delivered keys are remembered
+const SENT_RETENTION_DAYS: i64 = 30;
+
+/// Wait before the first retry; doubles on every further attempt
+const BASE_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+#[derive(Error, Debug)]
+pub enum WebhookError {
+    #[error("webhook '{webhook}': invalid url: {message}")]
+    Url { webhook: String, message: String },
+
+    #[error("webhook '{webhook}': no rule named '{rule}'")]
+    UnknownRule { webhook: String, rule: String },
+
+    #[error("Database error: {0}")]
+    Database(#[from] sled::Error),
+
+    #[error("Cache error: {0}")]
+    Cache(String),
+
+    #[error("HTTP client error: {0}")]
+    Http(#[from] reqwest::Error),
+}
+
+pub type Result<T> = std::result::Result<T, WebhookError>;
+
+/// A webhook with its URL parsed and rule names resolved
+#[derive(Debug, Clone)]
+pub struct Webhook {
+    pub name: String,
+    url: Url,
+    rules: Vec<String>,
+    template: Option<String>,
+    max_retries: u32,
+    backoff: Duration,
+}
+
+impl Webhook {
+    /// Check a webhook against its config; `rules` are the configured `[[rules]]`
+    pub fn compile(config: &WebhookConfig, index: usize, rules: &[RuleConfig]) -> Result<Self> {
+        let name = if config.name.is_empty() {
+            format!("#{}", index + 1)
+        } else {
+            config.name.clone()
+        };
+
+        let url = Url::parse(&config.url)
+            .map_err(|e| e.to_string())
+            .and_then(|url| match url.scheme() {
+                "http" | "https" => Ok(url),
+                scheme => Err(format!("unsupported scheme '{}'", scheme)),
+            })
+            .map_err(|message| WebhookError::Url {
+                webhook: name.clone(),
+                message,
+            })?;
+
+        // Unnamed rules are called "#n" by the rule engine
+        let rule_names: Vec<String> = rules
+            .iter()
+            .enumerate()
+            .map(|(idx, r)| {
+                if r.name.is_empty() {
+                    format!("#{}", idx + 1)
+                } else {
+                    r.name.clone()
+                }
+            })
+            .collect();
+        if let Some(rule) = config.rules.iter().find(|r| !rule_names.contains(r)) {
+            return Err(WebhookError::UnknownRule {
+                webhook: name,
+                rule: rule.clone(),
+            });
+        }
+
+        Ok(Self {
+            url,
+            rules: config.rules.clone(),
+            template: config.template.clone(),
+            max_retries: config.max_retries,
+            backoff: BASE_BACKOFF,
+            name,
+        })
+    }
+
+    /// Compile all enabled webhooks, failing on the first invalid one
+    pub fn compile_all(configs: &[WebhookConfig], rules: &[RuleConfig]) -> Result<Vec<Self>> {
+        configs
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| c.enabled)
+            .map(|(idx, c)| Self::compile(c, idx, rules))
+            .collect()
+    }
+
+    /// Whether an item's rule matches should trigger this webhook
+    pub fn wants(&self, item: &FeedItem) -> bool {
+        if self.rules.is_empty() {
+            item.metadata.notify
+        } else {
+            item.metadata
+                .matched_rules
+                .iter()
+                .any(|r| self.rules.contains(r))
+        }
+    }
+
+    /// Request body for an item
+    pub fn payload(&self, item: &FeedItem) -> String {
+        match &self.template {
+            Some(template) => render_template(template, item),
+            None => item_json(item),
+        }
+    }
+
+    /// POST a body, retrying server errors, rate limits and network failures;
+    /// returns the number of attempts made
+    pub async fn deliver(&self, client: &Client, body: String) -> std::result::Result<u32, String> {
+        let mut attempt = 0;
+        loop {
+            attempt += 1;
+            let result = client
+                .post(self.url.clone())
+                .header("Content-Type", "application/json")
+                .header("User-Agent", concat!("finterm/", env!("CARGO_PKG_VERSION")))
+                .body(body.clone())
+                .send()
+                .await;
+
+            let error = match result {
+                Ok(response) if response.status().is_success() => return Ok(attempt),
+                Ok(response) => {
+                    let status = response.status();
+                    if !is_retryable(status) {
+                        return Err(format!("HTTP {}", status));
+                    }
+                    format!("HTTP {}", status)
+                }
+                Err(e) => e.to_string(),
+            };
+
+            if attempt > self.max_retries {
+                return Err(format!("{} after {} attempts", error, attempt));
+            }
+            let delay = self.backoff.saturating_mul(1 << (attempt - 1).min(16));
+            tokio::time::sleep(delay.min(MAX_BACKOFF)).await;
+        }
+    }
+}
+
+fn is_retryable(status: StatusCode) -> bool {
+    status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS
+}
+
+/// The item as JSON, without a loaded comment thread
+fn item_json(item: &FeedItem) -> String {
+    let mut item = item.clone();
+    item.metadata.comments_data = None;
+    serde_json::to_string(&item).unwrap_or_default()
+}
+
+/// Fill `{{field}}` placeholders; text values are JSON-escaped (without
+/// quotes) and `{{item}}` is the whole item as a JSON object
+fn render_template(template: &str, item: &FeedItem) -> String {
+    static PLACEHOLDER: OnceLock<Regex> = OnceLock::new();
+    let placeholder =
+        PLACEHOLDER.get_or_init(|| Regex::new(r"\{\{\s*(\w+)\s*\}\}").expect("valid regex"));
+
+    let text = |value: &str| {
+        let quoted = serde_json::to_string(value).unwrap_or_default();
+        quoted[1..quoted.len() - 1].to_string()
+    };
+
+    placeholder
+        .replace_all(template, |caps: &Captures| match &caps[1] {
+            "item" => item_json(item),
+            "id" => text(&item.id),
+            "provider" => text(&item.provider_id),
+            "title" => text(&item.title),
+            "url" => text(item.url.as_deref().unwrap_or_default()),
+            "discussion_url" => text(&item.discussion_url().unwrap_or_default()),
+            "source" => text(&item.source),
+            "author" => text(item.author.as_deref().unwrap_or_default()),
+            "summary" => text(item.summary.as_deref().unwrap_or_default()),
+            "published" => item.published_at.to_rfc3339(),
+            "score" => item.metadata.score.unwrap_or(0).to_string(),
+            "comments" => item.metadata.comments.unwrap_or(0).to_string(),
+            "tags" => text(&item.metadata.tags.join(", ")),
+            "rules" => text(&item.metadata.matched_rules.join(", ")),
+            _ => caps[0].to_string(),
+        })
+        .into_owned()
+}
+
+/// Outcome of one delivery
+#[derive(Debug, Clone)]
+pub struct Delivery {
+    pub webhook: String,
+    pub title: String,
+    /// Attempts made on success, or the last error
+    pub result: std::result::Result<u32, String>,
+}
+
+/// Sends newly matched items to every webhook that wants them
+pub struct WebhookDispatcher {
+    hooks: Vec<Arc<Webhook>>,
+    client: Client,
+    sent: sled::Tree,
+}
+
+impl WebhookDispatcher {
+    /// Dispatcher for the configured webhooks, forgetting deliveries past retention
+    pub fn open(hooks: Vec<Webhook>, cache: &CacheManager) -> Result<Self> {
+        let sent = cache
+            .open_tree(SENT_TREE)
+            .map_err(|e| WebhookError::Cache(e.to_string()))?;
+        let client = Client::builder().timeout(Duration::from_secs(15)).build()?;
+
+        let dispatcher = Self {
+            hooks: hooks.into_iter().map(Arc::new).collect(),
+            client,
+            sent,
+        };
+        dispatcher.prune()?;
+        Ok(dispatcher)
+    }
+
+    pub fn len(&self) -> usize {
+        self.hooks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.hooks.is_empty()
+    }
+
+    /// Start delivering items not yet sent to each webhook that wants them
+    ///
+    /// Items are marked sent when queued, so a refresh during retries does
+    /// not send them twice; the mark is dropped again if every attempt fails.
+    /// Await the handles to learn the outcomes.
+    pub fn dispatch(&self, items: &[FeedItem]) -> Vec<JoinHandle<Delivery>> {
+        let now = Utc::now().timestamp().to_be_bytes();
+        let mut handles = Vec::new();
+
+        for hook in &self.hooks {
+            for item in items.iter().filter(|item| hook.wants(item)) {
+                let key = format!("{}\u{1f}{}\u{1f}{}", hook.name, item.provider_id, item.id);
+                // Only the first to insert the key delivers
+                match self.sent.compare_and_swap(
+                    key.as_bytes(),
+                    None as Option<&[u8]>,
+                    Some(&now[..]),
+                ) {
+                    Ok(Ok(())) => {}
+                    Ok(Err(_)) => continue,
+                    Err(e) => {
+                        tracing::warn!("Webhook store unavailable: {}", e);
+                        continue;
+                    }
+                }
+
+                let hook = Arc::clone(hook);
+                let client = self.client.clone();
+                let sent = self.sent.clone();
+                let body = hook.payload(item);
+                let title = item.title.clone();
+                handles.push(tokio::spawn(async move {
+                    let result = hook.deliver(&client, body).await;
+                    if let Err(e) = &result {
+                        tracing::warn!("Webhook '{}' failed for '{}': {}", hook.name, title, e);
+                        // Not delivered: let the next fetch try again
+                        if let Err(e) = sent.remove(key.as_bytes()) {
+                            tracing::warn!("Webhook store unavailable: {}", e);
+                        }
+                    }
+                    Delivery {
+                        webhook: hook.name.clone(),
+                        title,
+                        result,
+                    }
+                }));
+            }
+        }
+
+        handles
+    }
+
+    /// Drop delivered keys older than the retention period
+    fn prune(&self) -> Result<()> {
+        let cutoff = (Utc::now() - ChronoDuration::days(SENT_RETENTION_DAYS)).timestamp();
+        for entry in self.sent.iter() {
+            let (key, value) = entry?;
+            let sent_at = value
+                .as_ref()
+                .try_into()
+                .map(i64::from_be_bytes)
+                .unwrap_or(0);
+            if sent_at < cutoff {
+                self.sent.remove(key)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn config(url: &str, rules: &[&str], template: Option<&str>) -> WebhookConfig {
+        WebhookConfig {
+            name: "test".to_string(),
+            enabled: true,
+            url: url.to_string(),
+            rules: rules.iter().map(|r| r.to_string()).collect(),
+            template: template.map(str::to_string),
+            max_retries: 2,
+        }
+    }
+
+    fn rule(name: &str) -> RuleConfig {
+        toml::from_str(&format!("name = \"{}\"\nnotify = true", name)).unwrap()
+    }
+
+    #[test]
+    fn test_compile_and_template() {
+        let rules = vec![rule("watchlist")];
+        assert!(matches!(
+            Webhook::compile(&config("ftp://example.com", &[], None), 0, &rules),
+            Err(WebhookError::Url { .. })
+        ));
+        assert!(matches!(
+            Webhook::compile(&config("https://example.com", &["nope"], None), 0, &rules),
+            Err(WebhookError::UnknownRule { .. })
+        ));
+
+        let template = r#"{"text": "{{title}} ({{score}}) [{{rules}}] {{missing}}"}"#;
+        let hook = Webhook::compile(
+            &config("https://example.com/hook", &["watchlist"], Some(template)),
+            0,
+            &rules,
+        )
+        .unwrap();
+        let matched = FeedItem::test("1", "hackernews")
+            .with_title("\"NVDA\" beats 1")
+            .with_score(250)
+            .with_rules(&["watchlist"]);
+        assert!(hook.wants(&matched));
+        assert!(!hook.wants(&FeedItem::test("2", "hackernews").with_score(250)));
+
+        let payload: serde_json::Value = serde_json::from_str(&hook.payload(&matched)).unwrap();
+        assert_eq!(
+            payload["text"],
+            r#""NVDA" beats 1 (250) [watchlist] {{missing}}"#
+        );
+
+        let hook = Webhook::compile(&config("https://example.com", &[], None), 0, &rules).unwrap();
+        let payload: FeedItem = serde_json::from_str(&hook.payload(&matched)).unwrap();
+        assert_eq!(payload.id, "1");
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_retries_and_sends_once() {
+        let mut server = mockito::Server::new_async().await;
+        let failing = server
+            .mock("POST", "/fail")
+            .with_status(503)
+            .expect(6)
+            .create_async()
+            .await;
+        let ok = server
+            .mock("POST", "/ok")
+            .match_header("content-type", "application/json")
+            .with_status(204)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let dir = tempdir().unwrap();
+        let cache = CacheManager::new(dir.path().to_path_buf(), 10).unwrap();
+        let hooks = ["fail", "ok"]
+            .iter()
+            .map(|path| {
+                let mut config = config(&format!("{}/{}", server.url(), path), &[], None);
+                config.name = path.to_string();
+                let mut hook = Webhook::compile(&config, 0, &[]).unwrap();
+                hook.backoff = Duration::from_millis(1);
+                hook
+            })
+            .collect();
+        let dispatcher = WebhookDispatcher::open(hooks, &cache).unwrap();
+
+        let items = vec![
+            FeedItem::test("1", "hackernews")
+                .with_score(250)
+                .with_rules(&["watchlist"]),
+            FeedItem::test("2", "hackernews").with_score(250),
+        ];
+        let mut deliveries = Vec::new();
+        for handle in dispatcher.dispatch(&items) {
+            deliveries.push(handle.await.unwrap());
+        }
+        deliveries.sort_by(|a, b| a.webhook.cmp(&b.webhook));
+
+        assert_eq!(deliveries.len(), 2);
+        assert!(deliveries[0]
+            .result
+            .as_ref()
+            .unwrap_err()
+            .contains("3 attempts"));
+        assert_eq!(deliveries[1].result, Ok(1));
+
+        // The delivered item is not sent again; the failed one is retried
+        let retried = dispatcher.dispatch(&items);
+        assert_eq!(retried.len(), 1);
+        for handle in retried {
+            assert_eq!(handle.await.unwrap().webhook, "fail");
+        }
+
+        failing.assert_async().await;
+        ok.assert_async().await;
+    }
+}